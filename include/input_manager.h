@@ -0,0 +1,36 @@
+#ifndef INPUT_MANAGER_H
+#define INPUT_MANAGER_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct FfiManager FfiManager;
+
+FfiManager *input_manager_new(void);
+void input_manager_free(FfiManager *manager);
+
+void input_manager_add_axis_binding(FfiManager *manager, uint32_t axis, uint32_t input);
+void input_manager_add_button_binding(FfiManager *manager, uint32_t button, uint32_t input);
+
+void input_manager_apply_button(FfiManager *manager, uint32_t button, bool pressed);
+void input_manager_apply_axis_position(FfiManager *manager, uint32_t axis, double position);
+void input_manager_apply_axis_direction(FfiManager *manager, uint32_t axis, int32_t direction, bool held);
+
+bool input_manager_process_button(FfiManager *manager, uint32_t input, bool pressed);
+bool input_manager_process_axis(FfiManager *manager, uint32_t input, double position);
+
+void input_manager_tick(FfiManager *manager, double delta_seconds);
+
+double input_manager_get_axis(const FfiManager *manager, uint32_t axis);
+bool input_manager_get_button(const FfiManager *manager, uint32_t button);
+bool input_manager_get_button_pressed(const FfiManager *manager, uint32_t button);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,49 @@
+use change::ValueChange;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+/// Sums offsets until read with `take`, for scroll amounts and camera orbit
+/// deltas. It never falls or clamps, and `Set` replaces the running total.
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct Accumulator {
+    pub total: f64,
+}
+
+impl Hash for Accumulator {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.total.to_bits().hash(state);
+    }
+}
+
+pub trait IAccumulator {
+    fn apply(&mut self, change: ValueChange);
+    fn tick(&mut self, delta: Duration);
+    fn take(&mut self) -> f64;
+}
+
+impl IAccumulator for Accumulator {
+    fn apply(&mut self, change: ValueChange) {
+        match change {
+            ValueChange::Set(total) => self.total = total,
+            ValueChange::Offset(offset) => self.total += offset,
+        }
+    }
+    fn tick(&mut self, _delta: Duration) {}
+    fn take(&mut self) -> f64 {
+        ::std::mem::take(&mut self.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn accumulates_until_taken() {
+        let mut accumulator = Accumulator::default();
+        accumulator.apply(ValueChange::Offset(3.0));
+        accumulator.tick(Duration::from_secs(1));
+        accumulator.apply(ValueChange::Offset(-0.5));
+        assert_eq!(accumulator.take(), 2.5);
+        assert_eq!(accumulator.take(), 0.0);
+    }
+}
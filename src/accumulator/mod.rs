@@ -0,0 +1,2 @@
+mod accumulator;
+pub use self::accumulator::*;
@@ -0,0 +1,128 @@
+use change::{AxisChange, ButtonChange};
+use extension::{ControllerExtension, ExtensionInput, ExtensionInputKind};
+use manager::Change;
+use polling::InputSource;
+use std::fmt::Debug;
+
+/// A key's travel can drive a button that actuates at the given depth, or a
+/// unipolar axis that follows the travel directly.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AnalogKeyMode<C> {
+    Button { input: C, actuation: f64 },
+    Axis(C),
+}
+
+/// Turns per-key travel (0..1) reported by an analog keyboard backend into
+/// changes, exposed as a controller extension.
+#[derive(Debug, Clone)]
+pub struct AnalogKeyboard<K, C> {
+    keys: Vec<(K, AnalogKeyMode<C>, bool)>,
+    queued: Vec<(C, Change)>,
+}
+
+pub trait IAnalogKeyboard<K, C> {
+    fn new() -> Self
+    where
+        Self: Sized;
+    fn bind_key(&mut self, key: K, mode: AnalogKeyMode<C>);
+    fn update(&mut self, key: &K, travel: f64);
+}
+
+impl<K: PartialEq, C: Clone> IAnalogKeyboard<K, C> for AnalogKeyboard<K, C> {
+    fn new() -> Self {
+        AnalogKeyboard {
+            keys: Vec::new(),
+            queued: Vec::new(),
+        }
+    }
+    fn bind_key(&mut self, key: K, mode: AnalogKeyMode<C>) {
+        self.keys.push((key, mode, false));
+    }
+    fn update(&mut self, key: &K, travel: f64) {
+        let travel = travel.clamp(0.0, 1.0);
+        for (bound, mode, pressed) in self.keys.iter_mut() {
+            if bound != key {
+                continue;
+            }
+            match *mode {
+                AnalogKeyMode::Button {
+                    ref input,
+                    actuation,
+                } => {
+                    let now_pressed = travel >= actuation;
+                    if now_pressed != *pressed {
+                        *pressed = now_pressed;
+                        self.queued
+                            .push((input.clone(), ButtonChange(now_pressed).into()));
+                    }
+                }
+                AnalogKeyMode::Axis(ref input) => {
+                    self.queued
+                        .push((input.clone(), AxisChange::Position(travel).into()));
+                }
+            }
+        }
+    }
+}
+
+impl<K: Send, C: Send> InputSource<C> for AnalogKeyboard<K, C> {
+    fn poll(&mut self) -> Vec<(C, Change)> {
+        self.queued.drain(..).collect()
+    }
+}
+
+impl<K: Debug + Send, C: Clone + Send> ControllerExtension<C> for AnalogKeyboard<K, C> {
+    fn name(&self) -> &str {
+        "analog_keyboard"
+    }
+    fn inputs(&self) -> Vec<ExtensionInput<C>> {
+        self.keys
+            .iter()
+            .map(|(key, mode, _)| match *mode {
+                AnalogKeyMode::Button { ref input, .. } => ExtensionInput {
+                    input: input.clone(),
+                    name: format!("{:?}", key),
+                    kind: ExtensionInputKind::Button,
+                },
+                AnalogKeyMode::Axis(ref input) => ExtensionInput {
+                    input: input.clone(),
+                    name: format!("{:?}", key),
+                    kind: ExtensionInputKind::Axis,
+                },
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_drives_button_and_axis() {
+        let mut keyboard = AnalogKeyboard::new();
+        keyboard.bind_key(
+            'w',
+            AnalogKeyMode::Button {
+                input: "sprint",
+                actuation: 0.8,
+            },
+        );
+        keyboard.bind_key('w', AnalogKeyMode::Axis("walk"));
+        keyboard.update(&'w', 0.5);
+        assert_eq!(
+            keyboard.poll(),
+            vec![("walk", AxisChange::Position(0.5).into())]
+        );
+        keyboard.update(&'w', 0.9);
+        keyboard.update(&'x', 0.9);
+        assert_eq!(
+            keyboard.poll(),
+            vec![
+                ("sprint", ButtonChange(true).into()),
+                ("walk", AxisChange::Position(0.9).into()),
+            ]
+        );
+        assert_eq!(keyboard.inputs()[0].kind, ExtensionInputKind::Button);
+    }
+}
@@ -0,0 +1,2 @@
+mod analog_keyboard;
+pub use self::analog_keyboard::*;
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+/// A float fed to an animation blend tree from an action's state. The value
+/// eases toward its target with `smoothing` as the time constant, so it
+/// covers about two thirds of the distance in that time. Zero follows the
+/// target exactly.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct AnimationParameter {
+    pub smoothing: Duration,
+    value: f64,
+}
+
+pub trait IAnimationParameter {
+    fn new(smoothing: Duration) -> Self
+    where
+        Self: Sized;
+    fn sample(&mut self, target: f64, delta: Duration);
+    fn value(&self) -> f64;
+}
+
+impl IAnimationParameter for AnimationParameter {
+    fn new(smoothing: Duration) -> Self {
+        AnimationParameter {
+            smoothing,
+            value: 0.0,
+        }
+    }
+    fn sample(&mut self, target: f64, delta: Duration) {
+        if self.smoothing == Duration::default() {
+            self.value = target;
+            return;
+        }
+        let blend = 1.0 - (-delta.as_secs_f64() / self.smoothing.as_secs_f64()).exp();
+        self.value += (target - self.value) * blend;
+    }
+    fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn eases_toward_target() {
+        let mut parameter = AnimationParameter::new(Duration::from_millis(100));
+        parameter.sample(1.0, Duration::from_millis(100));
+        assert!((parameter.value() - (1.0 - (-1.0f64).exp())).abs() < 1e-9);
+        for _ in 0..20 {
+            parameter.sample(1.0, Duration::from_millis(100));
+        }
+        assert!((parameter.value() - 1.0).abs() < 1e-6);
+        let mut instant = AnimationParameter::new(Duration::default());
+        instant.sample(0.5, Duration::from_millis(16));
+        assert_eq!(instant.value(), 0.5);
+    }
+}
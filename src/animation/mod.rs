@@ -0,0 +1,2 @@
+mod animation;
+pub use self::animation::*;
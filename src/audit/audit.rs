@@ -0,0 +1,131 @@
+use std::hash::{Hash, Hasher};
+
+/// FNV-1a with fixed-width, little-endian integer encoding so that checksums
+/// agree across platforms and compiler versions.
+pub struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as u64);
+    }
+}
+
+pub fn stable_hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = StableHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct AuditFrame {
+    pub changes: Vec<u64>,
+    pub checksum: u64,
+}
+
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct AuditTrace {
+    pub frames: Vec<AuditFrame>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AuditDivergence {
+    Changes(usize),
+    Checksum(usize),
+    Length(usize),
+}
+
+pub trait IAuditTrace {
+    fn record_change(&mut self, change: u64);
+    fn end_frame(&mut self, checksum: u64);
+    fn compare(&self, other: &Self) -> Option<AuditDivergence>;
+}
+
+impl IAuditTrace for AuditTrace {
+    fn record_change(&mut self, change: u64) {
+        if self.frames.is_empty() {
+            self.frames.push(AuditFrame::default());
+        }
+        if let Some(frame) = self.frames.last_mut() {
+            frame.changes.push(change);
+        }
+    }
+    fn end_frame(&mut self, checksum: u64) {
+        if self.frames.is_empty() {
+            self.frames.push(AuditFrame::default());
+        }
+        if let Some(frame) = self.frames.last_mut() {
+            frame.checksum = checksum;
+        }
+        self.frames.push(AuditFrame::default());
+    }
+    fn compare(&self, other: &Self) -> Option<AuditDivergence> {
+        for (index, (mine, theirs)) in self.frames.iter().zip(other.frames.iter()).enumerate() {
+            if mine.changes != theirs.changes {
+                return Some(AuditDivergence::Changes(index));
+            }
+            if mine.checksum != theirs.checksum {
+                return Some(AuditDivergence::Checksum(index));
+            }
+        }
+        if self.frames.len() != other.frames.len() {
+            Some(AuditDivergence::Length(
+                self.frames.len().min(other.frames.len()),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn stable_hash_is_fixed() {
+        assert_eq!(stable_hash(&0u8), 0xaf63_bd4c_8601_b7df);
+        assert_eq!(stable_hash(&1usize), stable_hash(&1u64));
+    }
+    #[test]
+    fn compare_finds_first_divergence() {
+        let mut left = AuditTrace::default();
+        let mut right = AuditTrace::default();
+        left.record_change(1);
+        right.record_change(1);
+        left.end_frame(10);
+        right.end_frame(10);
+        assert_eq!(left.compare(&right), None);
+        right.record_change(2);
+        assert_eq!(left.compare(&right), Some(AuditDivergence::Changes(1)));
+        left.record_change(2);
+        left.end_frame(11);
+        right.end_frame(12);
+        assert_eq!(left.compare(&right), Some(AuditDivergence::Checksum(1)));
+    }
+}
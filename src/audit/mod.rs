@@ -0,0 +1,2 @@
+mod audit;
+pub use self::audit::*;
@@ -1,39 +1,503 @@
 use change::{AxisChange, Direction};
+#[cfg(feature = "encoding")]
+use encoding::{BitReader, BitWriter, Decode, DecodeError, Encode};
+use std::hash::{Hash, Hasher};
+use std::ptr;
 use std::time::Duration;
 
-#[derive(Default, Debug, PartialEq)]
+/// How an axis resolves both directions being held at once.
+#[derive(Default, Debug, PartialEq, Clone, Hash)]
+pub enum SocdPolicy {
+    #[default]
+    LastInput,
+    FirstInput,
+    Neutral,
+}
+
+/// `dead_zone` is the magnitude an axis must exceed to count as active for
+/// its started/stopped moving edges. `zones` are named thresholds on a
+/// unipolar trigger, the axis is inside a zone while its position is at or
+/// above the threshold. `max_speed` is in units per second and defaults to
+/// 2.0, `gravity` is the speed it falls back to center at and defaults to
+/// `max_speed`. `acceleration` and `deceleration` are in units per second
+/// squared and change speed instantly when unset. `quantization` snaps the
+/// reported `quantized` value to fixed steps, and `response` maps the
+/// position to the axis `output`.
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct AxisConfig {
+    pub socd: SocdPolicy,
+    pub dead_zone: f64,
+    pub zones: Vec<TriggerZone>,
+    pub max_speed: Option<f64>,
+    pub gravity: Option<f64>,
+    pub acceleration: Option<f64>,
+    pub deceleration: Option<f64>,
+    pub quantization: Option<AxisQuantization>,
+    pub response: ResponseCurve,
+}
+
+impl Hash for AxisConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.socd.hash(state);
+        self.dead_zone.to_bits().hash(state);
+        self.zones.hash(state);
+        self.max_speed.map(f64::to_bits).hash(state);
+        self.gravity.map(f64::to_bits).hash(state);
+        self.acceleration.map(f64::to_bits).hash(state);
+        self.deceleration.map(f64::to_bits).hash(state);
+        self.quantization.hash(state);
+        self.response.hash(state);
+    }
+}
+
+/// `Power` keeps the sign and raises the magnitude to the exponent, so values
+/// above 1 give finer control near the center. `Custom` is called with the
+/// position as is.
+#[derive(Default, Debug, Clone, Copy)]
+pub enum ResponseCurve {
+    #[default]
+    Linear,
+    Power(f64),
+    Custom(fn(f64) -> f64),
+}
+
+impl ResponseCurve {
+    pub fn apply(&self, position: f64) -> f64 {
+        match *self {
+            ResponseCurve::Linear => position,
+            ResponseCurve::Power(exponent) => position.signum() * position.abs().powf(exponent),
+            ResponseCurve::Custom(curve) => curve(position),
+        }
+    }
+}
+
+impl PartialEq for ResponseCurve {
+    fn eq(&self, other: &ResponseCurve) -> bool {
+        match (*self, *other) {
+            (ResponseCurve::Linear, ResponseCurve::Linear) => true,
+            (ResponseCurve::Power(a), ResponseCurve::Power(b)) => a == b,
+            (ResponseCurve::Custom(a), ResponseCurve::Custom(b)) => ptr::fn_addr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// `Custom` hashes as its tag alone, since function addresses differ between
+/// builds and processes and would break cross-machine checksums.
+impl Hash for ResponseCurve {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match *self {
+            ResponseCurve::Linear => 0u8.hash(state),
+            ResponseCurve::Power(exponent) => {
+                1u8.hash(state);
+                exponent.to_bits().hash(state);
+            }
+            ResponseCurve::Custom(_) => 2u8.hash(state),
+        }
+    }
+}
+
+/// `steps` per direction, so 1 gives -1, 0 and 1. The position has to move
+/// `hysteresis` steps past the halfway point before the step changes.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AxisQuantization {
+    pub steps: u32,
+    pub hysteresis: f64,
+}
+
+impl Hash for AxisQuantization {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.steps.hash(state);
+        self.hysteresis.to_bits().hash(state);
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TriggerZone {
+    pub name: String,
+    pub threshold: f64,
+}
+
+impl Hash for TriggerZone {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.threshold.to_bits().hash(state);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum ZoneEvent {
+    Enter(String),
+    Exit(String),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum AxisActivity {
+    Started,
+    Stopped,
+}
+
+/// How a stick's two axes share a dead zone. `Radial` measures the distance
+/// from the center, `Axial` gives each axis its own dead zone, `Cross` adds
+/// a band of `dead_zone` width around each axis that snaps to the pure
+/// direction and `Bowtie` widens that band with distance from the center.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum DeadZoneShape {
+    #[default]
+    Radial,
+    Axial,
+    Cross,
+    Bowtie,
+}
+
+/// Shaping for positions coming from one physical input, so the same axis
+/// can use a different dead zone for a stick than for a gyro. Positions
+/// inside `dead_zone` read as zero, the rest is rescaled to start from zero,
+/// raised to `curve` and multiplied by `sensitivity`. A non-zero
+/// `anti_dead_zone` rescales that output to start from there instead, to step
+/// over a dead zone the game applies itself, and `output_min` is the smallest
+/// magnitude reported outside the dead zone. Positions at or past
+/// `saturation` read as full. `dead_zone_shape` only applies to sticks.
+#[derive(Debug, PartialEq, Clone)]
+pub struct InputAxisConfig {
+    pub dead_zone: f64,
+    pub saturation: f64,
+    pub curve: f64,
+    pub sensitivity: f64,
+    pub anti_dead_zone: f64,
+    pub output_min: f64,
+    pub dead_zone_shape: DeadZoneShape,
+}
+
+impl Default for InputAxisConfig {
+    fn default() -> Self {
+        InputAxisConfig {
+            dead_zone: 0.0,
+            saturation: 1.0,
+            curve: 1.0,
+            sensitivity: 1.0,
+            anti_dead_zone: 0.0,
+            output_min: 0.0,
+            dead_zone_shape: DeadZoneShape::default(),
+        }
+    }
+}
+
+impl Hash for InputAxisConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dead_zone.to_bits().hash(state);
+        self.saturation.to_bits().hash(state);
+        self.curve.to_bits().hash(state);
+        self.sensitivity.to_bits().hash(state);
+        self.anti_dead_zone.to_bits().hash(state);
+        self.output_min.to_bits().hash(state);
+        self.dead_zone_shape.hash(state);
+    }
+}
+
+fn dead_band(value: f64, width: f64, saturation: f64) -> f64 {
+    if value.abs() <= width {
+        return 0.0;
+    }
+    value.signum() * ((value.abs() - width) / (saturation - width)).min(1.0)
+}
+
+impl InputAxisConfig {
+    pub fn shape(&self, position: f64) -> f64 {
+        let magnitude = position.abs();
+        if magnitude <= self.dead_zone {
+            return 0.0;
+        }
+        let scaled = ((magnitude - self.dead_zone) / (self.saturation - self.dead_zone)).min(1.0);
+        let curved = self.anti_dead_zone + (1.0 - self.anti_dead_zone) * scaled.powf(self.curve);
+        position.signum() * (curved * self.sensitivity).max(self.output_min)
+    }
+
+    /// Runs a stick through the same shaping, with the dead zone in
+    /// `dead_zone_shape`, keeping each stage so calibration screens can draw
+    /// them.
+    pub fn stick_stages(&self, x: f64, y: f64) -> StickStages {
+        let raw = (x, y);
+        let dead_zoned = self.stick_dead_zone(x, y);
+        let scaled = dead_zoned.0.hypot(dead_zoned.1);
+        if scaled == 0.0 {
+            return StickStages {
+                raw,
+                ..StickStages::default()
+            };
+        }
+        let along = |length: f64| {
+            (
+                dead_zoned.0 / scaled * length,
+                dead_zoned.1 / scaled * length,
+            )
+        };
+        let curved = self.anti_dead_zone + (1.0 - self.anti_dead_zone) * scaled.powf(self.curve);
+        let curved = (curved * self.sensitivity).max(self.output_min);
+        StickStages {
+            raw,
+            dead_zoned,
+            curved: along(curved),
+            clamped: along(curved.min(1.0)),
+        }
+    }
+
+    fn stick_dead_zone(&self, x: f64, y: f64) -> (f64, f64) {
+        if let DeadZoneShape::Axial = self.dead_zone_shape {
+            return (
+                dead_band(x, self.dead_zone, self.saturation),
+                dead_band(y, self.dead_zone, self.saturation),
+            );
+        }
+        let magnitude = x.hypot(y);
+        if magnitude <= self.dead_zone {
+            return (0.0, 0.0);
+        }
+        let scaled = ((magnitude - self.dead_zone) / (self.saturation - self.dead_zone)).min(1.0);
+        let (x, y) = (x / magnitude * scaled, y / magnitude * scaled);
+        match self.dead_zone_shape {
+            DeadZoneShape::Cross => (
+                dead_band(x, self.dead_zone, 1.0),
+                dead_band(y, self.dead_zone, 1.0),
+            ),
+            DeadZoneShape::Bowtie => (
+                dead_band(x, self.dead_zone * y.abs(), 1.0),
+                dead_band(y, self.dead_zone * x.abs(), 1.0),
+            ),
+            _ => (x, y),
+        }
+    }
+}
+
+/// Each stage of shaping a stick position, in pipeline order.
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+pub struct StickStages {
+    pub raw: (f64, f64),
+    pub dead_zoned: (f64, f64),
+    pub curved: (f64, f64),
+    pub clamped: (f64, f64),
+}
+
+#[derive(Default, Debug, PartialEq, Clone)]
 pub struct Axis {
     pub position: f64,
     pub velocity: Option<Direction>,
+    speed: f64,
     falling: bool,
+    config: AxisConfig,
+    held: Vec<Direction>,
+    last_changed_at: Option<Duration>,
+    active: bool,
+    activity: Option<AxisActivity>,
+    zones: Vec<String>,
+    zone_events: Vec<ZoneEvent>,
+    step: i64,
+    raw: Option<f64>,
+    center: f64,
+}
+
+#[cfg(feature = "encoding")]
+impl Encode for Axis {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.position.encode(writer);
+        self.velocity.encode(writer);
+        self.speed.encode(writer);
+        self.falling.encode(writer);
+        self.config.encode(writer);
+        self.held.encode(writer);
+        self.last_changed_at.encode(writer);
+        self.active.encode(writer);
+        self.activity.encode(writer);
+        self.zones.encode(writer);
+        self.zone_events.encode(writer);
+        self.step.encode(writer);
+        self.raw.encode(writer);
+        self.center.encode(writer);
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl Decode for Axis {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(Axis {
+            position: f64::decode(reader)?,
+            velocity: Option::decode(reader)?,
+            speed: f64::decode(reader)?,
+            falling: bool::decode(reader)?,
+            config: AxisConfig::decode(reader)?,
+            held: Vec::decode(reader)?,
+            last_changed_at: Option::decode(reader)?,
+            active: bool::decode(reader)?,
+            activity: Option::decode(reader)?,
+            zones: Vec::decode(reader)?,
+            zone_events: Vec::decode(reader)?,
+            step: i64::decode(reader)?,
+            raw: Option::decode(reader)?,
+            center: f64::decode(reader)?,
+        })
+    }
+}
+
+impl Hash for Axis {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.position.to_bits().hash(state);
+        self.velocity.hash(state);
+        self.speed.to_bits().hash(state);
+        self.falling.hash(state);
+        self.config.hash(state);
+        self.held.hash(state);
+        self.last_changed_at.hash(state);
+        self.active.hash(state);
+        self.activity.hash(state);
+        self.zones.hash(state);
+        self.zone_events.hash(state);
+        self.step.hash(state);
+        self.raw.map(f64::to_bits).hash(state);
+        self.center.to_bits().hash(state);
+    }
 }
 
 pub trait IAxis {
     fn apply(&mut self, AxisChange);
+    fn apply_at(&mut self, change: AxisChange, at: Duration);
     fn tick(&mut self, Duration);
+    /// Returns false and keeps the current config if it quantizes to zero
+    /// steps.
+    fn configure(&mut self, config: AxisConfig) -> bool;
+    fn last_changed_at(&self) -> Option<Duration>;
+    fn is_active(&self) -> bool;
+    fn activity(&self) -> Option<AxisActivity>;
+    fn in_zone(&self, zone: &str) -> bool;
+    fn zone_events(&self) -> &[ZoneEvent];
+    fn quantized(&self) -> f64;
+    /// The position through the configured response curve.
+    fn output(&self) -> f64;
+    /// Treats the last position applied as the new zero, rescaling both
+    /// sides so the ends still reach -1 and 1. Returns false and leaves the
+    /// center alone if no position has been applied yet.
+    fn recenter(&mut self) -> bool;
+    #[cfg(feature = "strict-invariants")]
+    fn assert_invariants(&self);
+}
+
+impl Axis {
+    fn recentered(&self, raw: f64) -> f64 {
+        let range = if raw >= self.center {
+            1.0 - self.center
+        } else {
+            1.0 + self.center
+        };
+        if range <= 0.0 {
+            return 0.0;
+        }
+        (raw - self.center) / range
+    }
 }
 
 impl IAxis for Axis {
     fn apply(&mut self, change: AxisChange) {
-        self.falling = false;
+        self.last_changed_at = None;
         use AxisChange::*;
         match change {
-            Position(pos) => self.position = pos,
-            Velocity(vel) => self.velocity = Some(vel),
+            Position(pos) => {
+                self.falling = false;
+                self.raw = Some(pos);
+                self.position = self.recentered(pos);
+            }
+            Velocity(vel) => {
+                self.falling = false;
+                if !self.held.contains(&vel) {
+                    self.held.push(vel.clone());
+                }
+                if self.held.len() > 1 {
+                    match self.config.socd {
+                        SocdPolicy::LastInput => self.velocity = Some(vel),
+                        SocdPolicy::FirstInput => self.velocity = Some(self.held[0].clone()),
+                        SocdPolicy::Neutral => {
+                            self.falling = true;
+                            self.velocity = None;
+                        }
+                    }
+                } else {
+                    self.velocity = Some(vel);
+                }
+            }
             Falling(dir) => {
-                use Direction::*;
-                match (dir, &self.velocity) {
-                    (Down, Some(Down)) | (Up, Some(Up)) => {
-                        self.falling = true;
-                        self.velocity = None;
+                let was_held = self.held.contains(&dir);
+                self.held.retain(|held| held != &dir);
+                if self.velocity.as_ref() == Some(&dir) || (was_held && self.velocity.is_none()) {
+                    match self.held.last().cloned() {
+                        Some(remaining) => {
+                            self.falling = false;
+                            self.velocity = Some(remaining);
+                        }
+                        None => {
+                            self.falling = true;
+                            self.velocity = None;
+                        }
                     }
-                    _ => {}
                 }
             }
         }
     }
+    fn apply_at(&mut self, change: AxisChange, at: Duration) {
+        self.apply(change);
+        self.last_changed_at = Some(at);
+    }
+    fn configure(&mut self, config: AxisConfig) -> bool {
+        if matches!(config.quantization, Some(AxisQuantization { steps: 0, .. })) {
+            return false;
+        }
+        self.config = config;
+        true
+    }
+    fn last_changed_at(&self) -> Option<Duration> {
+        self.last_changed_at
+    }
+    fn is_active(&self) -> bool {
+        self.active
+    }
+    fn activity(&self) -> Option<AxisActivity> {
+        self.activity
+    }
+    fn in_zone(&self, zone: &str) -> bool {
+        self.zones.iter().any(|name| name == zone)
+    }
+    fn zone_events(&self) -> &[ZoneEvent] {
+        &self.zone_events
+    }
+    fn recenter(&mut self) -> bool {
+        let raw = match self.raw {
+            Some(raw) => raw,
+            None => return false,
+        };
+        self.center = raw;
+        self.position = self.recentered(raw);
+        true
+    }
+    fn quantized(&self) -> f64 {
+        match &self.config.quantization {
+            Some(quantization) => self.step as f64 / quantization.steps as f64,
+            None => self.position,
+        }
+    }
+    fn output(&self) -> f64 {
+        self.config.response.apply(self.position)
+    }
+    #[cfg(feature = "strict-invariants")]
+    fn assert_invariants(&self) {
+        debug_assert!(!self.position.is_nan(), "axis position is NaN");
+        debug_assert!(
+            !self.falling || self.velocity.is_none(),
+            "falling axis has a velocity"
+        );
+        debug_assert!(
+            self.velocity.as_ref().is_none_or(|v| self.held.contains(v)),
+            "axis velocity is not a held direction"
+        );
+    }
     fn tick(&mut self, delta: Duration) {
-        let dx = if self.falling {
+        let dx: f64 = if self.falling {
             if self.position > 0.0 {
                 -1.0
             } else {
@@ -46,11 +510,31 @@ impl IAxis for Axis {
                 .map(|val| val.into())
                 .unwrap_or(0.0)
         };
-        self.position +=
-            dx * (delta.as_secs() * 1000 + (delta.subsec_millis() as u64)) as f64 / 500.0;
+        let millis = delta.as_nanos() as f64 / 1_000_000.0;
+        let max_speed = if self.falling {
+            self.config.gravity.or(self.config.max_speed)
+        } else {
+            self.config.max_speed
+        };
+        let target = dx * max_speed.unwrap_or(2.0);
+        let speeding_up = target * self.speed >= 0.0 && target.abs() > self.speed.abs();
+        let rate = if speeding_up {
+            self.config.acceleration
+        } else {
+            self.config.deceleration
+        };
+        self.speed = match rate {
+            Some(rate) => {
+                let step = rate * millis / 1000.0;
+                self.speed + (target - self.speed).clamp(-step, step)
+            }
+            None => target,
+        };
+        self.position += self.speed * millis / 1000.0;
         self.position = if self.falling {
             if dx * self.position > 0.0 {
                 self.falling = false;
+                self.speed = 0.0;
                 0.0
             } else {
                 self.position
@@ -63,6 +547,39 @@ impl IAxis for Axis {
             } else {
                 self.position
             }
+        };
+        let active = self.position.abs() > self.config.dead_zone;
+        self.activity = match (self.active, active) {
+            (false, true) => Some(AxisActivity::Started),
+            (true, false) => Some(AxisActivity::Stopped),
+            _ => None,
+        };
+        self.active = active;
+        let zones: Vec<String> = self
+            .config
+            .zones
+            .iter()
+            .filter(|zone| self.position >= zone.threshold)
+            .map(|zone| zone.name.clone())
+            .collect();
+        self.zone_events = self
+            .zones
+            .iter()
+            .filter(|name| !zones.contains(name))
+            .map(|name| ZoneEvent::Exit(name.clone()))
+            .chain(
+                zones
+                    .iter()
+                    .filter(|name| !self.zones.contains(name))
+                    .map(|name| ZoneEvent::Enter(name.clone())),
+            )
+            .collect();
+        self.zones = zones;
+        if let Some(quantization) = &self.config.quantization {
+            let scaled = self.position * quantization.steps as f64;
+            if (scaled - self.step as f64).abs() > 0.5 + quantization.hysteresis {
+                self.step = scaled.round() as i64;
+            }
         }
     }
 }
@@ -106,6 +623,53 @@ mod tests {
         assert_eq!(axis.velocity, Some(Direction::Up));
         assert_eq!(axis.falling, false);
     }
+    fn press_both(socd: SocdPolicy) -> Axis {
+        let mut axis = Axis::default();
+        axis.configure(AxisConfig {
+            socd,
+            ..AxisConfig::default()
+        });
+        axis.apply(AxisChange::Velocity(Direction::Down));
+        axis.apply(AxisChange::Velocity(Direction::Up));
+        axis
+    }
+    #[test]
+    fn socd_last_input_wins() {
+        let axis = press_both(SocdPolicy::LastInput);
+        assert_eq!(axis.velocity, Some(Direction::Up));
+    }
+    #[test]
+    fn socd_first_input_wins() {
+        let mut axis = press_both(SocdPolicy::FirstInput);
+        assert_eq!(axis.velocity, Some(Direction::Down));
+        axis.apply(AxisChange::Falling(Direction::Up));
+        assert_eq!(axis.velocity, Some(Direction::Down));
+    }
+    #[test]
+    fn socd_neutral_cancels() {
+        let axis = press_both(SocdPolicy::Neutral);
+        assert_eq!(axis.velocity, None);
+        assert!(axis.falling);
+    }
+    #[test]
+    fn release_resumes_held_direction() {
+        for socd in &[
+            SocdPolicy::LastInput,
+            SocdPolicy::FirstInput,
+            SocdPolicy::Neutral,
+        ] {
+            let mut axis = press_both(socd.clone());
+            axis.apply(AxisChange::Falling(Direction::Up));
+            assert_eq!(axis.velocity, Some(Direction::Down), "{:?}", socd);
+            assert!(!axis.falling);
+            let mut axis = press_both(socd.clone());
+            axis.apply(AxisChange::Falling(Direction::Down));
+            assert_eq!(axis.velocity, Some(Direction::Up), "{:?}", socd);
+            axis.apply(AxisChange::Falling(Direction::Up));
+            assert_eq!(axis.velocity, None);
+            assert!(axis.falling);
+        }
+    }
     mod tick {
         use super::*;
         #[test]
@@ -115,6 +679,7 @@ mod tests {
                 position: 0.0,
                 velocity: Some(Direction::Up),
                 falling: false,
+                ..Axis::default()
             };
             axis.tick(delta);
             assert_eq!(axis.position, 0.2);
@@ -131,6 +696,7 @@ mod tests {
                 position: 0.9,
                 velocity: Some(Direction::Up),
                 falling: false,
+                ..Axis::default()
             };
             axis.tick(delta);
             assert_eq!(axis.position, 1.0);
@@ -142,6 +708,7 @@ mod tests {
                 position: -0.9,
                 velocity: Some(Direction::Down),
                 falling: false,
+                ..Axis::default()
             };
             axis.tick(delta);
             assert_eq!(axis.position, -1.0);
@@ -153,6 +720,7 @@ mod tests {
                 position: 0.5,
                 velocity: None,
                 falling: true,
+                ..Axis::default()
             };
             axis.tick(delta);
             assert_eq!(axis.position, 0.3);
@@ -165,9 +733,279 @@ mod tests {
                 position: 0.5,
                 velocity: None,
                 falling: true,
+                ..Axis::default()
             };
             axis.tick(delta);
             assert_eq!(axis.position, 0.0);
         }
     }
+
+    #[test]
+    fn input_config_shapes_positions() {
+        let config = InputAxisConfig {
+            dead_zone: 0.5,
+            curve: 2.0,
+            sensitivity: 0.5,
+            ..InputAxisConfig::default()
+        };
+        assert_eq!(config.shape(0.1), 0.0);
+        assert_eq!(config.shape(-0.75), -0.125);
+        assert_eq!(config.shape(1.0), 0.5);
+    }
+
+    #[test]
+    fn reports_activity_edges() {
+        let mut axis = Axis::default();
+        axis.configure(AxisConfig {
+            dead_zone: 0.1,
+            ..AxisConfig::default()
+        });
+        axis.apply(AxisChange::Position(0.05));
+        axis.tick(Duration::from_millis(16));
+        assert_eq!(axis.activity(), None);
+        axis.apply(AxisChange::Position(0.5));
+        axis.tick(Duration::from_millis(16));
+        assert_eq!(axis.activity(), Some(AxisActivity::Started));
+        axis.tick(Duration::from_millis(16));
+        assert_eq!(axis.activity(), None);
+        assert!(axis.is_active());
+        axis.apply(AxisChange::Position(0.0));
+        axis.tick(Duration::from_millis(16));
+        assert_eq!(axis.activity(), Some(AxisActivity::Stopped));
+    }
+
+    #[test]
+    fn reports_trigger_zone_edges() {
+        let mut axis = Axis::default();
+        axis.configure(AxisConfig {
+            zones: vec![
+                TriggerZone {
+                    name: "focus".to_string(),
+                    threshold: 0.3,
+                },
+                TriggerZone {
+                    name: "shoot".to_string(),
+                    threshold: 0.9,
+                },
+            ],
+            ..AxisConfig::default()
+        });
+        axis.apply(AxisChange::Position(0.5));
+        axis.tick(Duration::from_millis(16));
+        assert_eq!(axis.zone_events(), &[ZoneEvent::Enter("focus".to_string())]);
+        axis.apply(AxisChange::Position(1.0));
+        axis.tick(Duration::from_millis(16));
+        assert_eq!(axis.zone_events(), &[ZoneEvent::Enter("shoot".to_string())]);
+        assert!(axis.in_zone("focus") && axis.in_zone("shoot"));
+        axis.apply(AxisChange::Position(0.0));
+        axis.tick(Duration::from_millis(16));
+        assert_eq!(
+            axis.zone_events(),
+            &[
+                ZoneEvent::Exit("focus".to_string()),
+                ZoneEvent::Exit("shoot".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn accelerates_up_to_max_speed() {
+        let mut axis = Axis::default();
+        axis.configure(AxisConfig {
+            max_speed: Some(1.0),
+            acceleration: Some(2.0),
+            deceleration: Some(4.0),
+            ..AxisConfig::default()
+        });
+        axis.apply(AxisChange::Velocity(Direction::Up));
+        axis.tick(Duration::from_millis(250));
+        assert_eq!(axis.position, 0.125);
+        axis.tick(Duration::from_millis(500));
+        assert_eq!(axis.position, 0.625);
+        axis.apply(AxisChange::Position(0.5));
+        axis.apply(AxisChange::Falling(Direction::Up));
+        axis.tick(Duration::from_millis(125));
+        assert_eq!(axis.position, 0.5625);
+        axis.tick(Duration::from_millis(125));
+        assert_eq!(axis.position, 0.5625);
+        axis.tick(Duration::from_millis(125));
+        assert_eq!(axis.position, 0.53125);
+    }
+
+    #[test]
+    fn anti_dead_zone_starts_output_past_it() {
+        let config = InputAxisConfig {
+            dead_zone: 0.5,
+            anti_dead_zone: 0.25,
+            output_min: 0.5,
+            ..InputAxisConfig::default()
+        };
+        assert_eq!(config.shape(0.25), 0.0);
+        assert_eq!(config.shape(0.625), 0.5);
+        assert_eq!(config.shape(-0.75), -0.625);
+        assert_eq!(config.shape(1.0), 1.0);
+    }
+
+    #[test]
+    fn quantizes_with_hysteresis() {
+        let mut axis = Axis::default();
+        axis.configure(AxisConfig {
+            quantization: Some(AxisQuantization {
+                steps: 2,
+                hysteresis: 0.25,
+            }),
+            ..AxisConfig::default()
+        });
+        axis.apply(AxisChange::Position(0.3));
+        axis.tick(Duration::from_millis(16));
+        assert_eq!(axis.quantized(), 0.0);
+        axis.apply(AxisChange::Position(0.5));
+        axis.tick(Duration::from_millis(16));
+        assert_eq!(axis.quantized(), 0.5);
+        axis.apply(AxisChange::Position(0.2));
+        axis.tick(Duration::from_millis(16));
+        assert_eq!(axis.quantized(), 0.5);
+        axis.apply(AxisChange::Position(-1.0));
+        axis.tick(Duration::from_millis(16));
+        assert_eq!(axis.quantized(), -1.0);
+    }
+
+    #[test]
+    fn rejects_zero_quantization_steps() {
+        let mut axis = Axis::default();
+        assert!(!axis.configure(AxisConfig {
+            quantization: Some(AxisQuantization {
+                steps: 0,
+                hysteresis: 0.0,
+            }),
+            ..AxisConfig::default()
+        }));
+        axis.apply(AxisChange::Position(0.5));
+        axis.tick(Duration::from_millis(16));
+        assert_eq!(axis.quantized(), 0.5);
+    }
+
+    #[test]
+    fn exposes_stick_stages() {
+        let config = InputAxisConfig {
+            dead_zone: 0.5,
+            sensitivity: 4.0,
+            ..InputAxisConfig::default()
+        };
+        assert_eq!(config.stick_stages(0.25, 0.25).clamped, (0.0, 0.0));
+        let stages = config.stick_stages(0.0, -0.75);
+        assert_eq!(stages.raw, (0.0, -0.75));
+        assert_eq!(stages.dead_zoned, (0.0, -0.5));
+        assert_eq!(stages.curved, (0.0, -2.0));
+        assert_eq!(stages.clamped, (0.0, -1.0));
+    }
+
+    #[test]
+    fn recenter_rescales_around_new_zero() {
+        let mut axis = Axis::default();
+        axis.apply(AxisChange::Position(0.5));
+        assert!(axis.recenter());
+        assert_eq!(axis.position, 0.0);
+        axis.apply(AxisChange::Position(0.75));
+        assert_eq!(axis.position, 0.5);
+        axis.apply(AxisChange::Position(-1.0));
+        assert_eq!(axis.position, -1.0);
+        axis.apply(AxisChange::Position(1.0));
+        assert_eq!(axis.position, 1.0);
+    }
+
+    #[test]
+    fn cross_and_bowtie_snap_near_axes() {
+        let cross = InputAxisConfig {
+            dead_zone: 0.25,
+            dead_zone_shape: DeadZoneShape::Cross,
+            ..InputAxisConfig::default()
+        };
+        assert_eq!(cross.stick_dead_zone(0.0, 0.2), (0.0, 0.0));
+        let (x, y) = cross.stick_dead_zone(0.1, 1.0);
+        assert_eq!(x, 0.0);
+        assert!(y > 0.9);
+        let bowtie = InputAxisConfig {
+            dead_zone_shape: DeadZoneShape::Bowtie,
+            ..cross.clone()
+        };
+        assert_eq!(bowtie.stick_dead_zone(0.0, 0.5).0, 0.0);
+        assert_eq!(
+            bowtie.stick_dead_zone(0.5, 0.5).0,
+            bowtie.stick_dead_zone(0.5, 0.5).1
+        );
+        assert_eq!(bowtie.stick_dead_zone(0.1, 1.0).0, 0.0);
+        let axial = InputAxisConfig {
+            dead_zone_shape: DeadZoneShape::Axial,
+            ..cross
+        };
+        assert_eq!(axial.stick_dead_zone(0.625, 0.125), (0.5, 0.0));
+    }
+
+    #[test]
+    fn radial_dead_zone_saturates_before_the_rim() {
+        let config = InputAxisConfig {
+            dead_zone: 0.25,
+            saturation: 0.75,
+            ..InputAxisConfig::default()
+        };
+        assert_eq!(config.stick_dead_zone(0.0, 0.5), (0.0, 0.5));
+        assert_eq!(config.stick_dead_zone(0.6, 0.8), (0.6, 0.8));
+        assert_eq!(config.stick_dead_zone(-0.8, 0.0), (-1.0, 0.0));
+        assert_eq!(config.shape(0.5), 0.5);
+        assert_eq!(config.shape(-0.9), -1.0);
+    }
+
+    #[test]
+    fn gravity_sets_return_speed() {
+        let mut axis = Axis::default();
+        axis.configure(AxisConfig {
+            max_speed: Some(1.0),
+            gravity: Some(4.0),
+            ..AxisConfig::default()
+        });
+        axis.apply(AxisChange::Velocity(Direction::Up));
+        axis.tick(Duration::from_millis(500));
+        assert_eq!(axis.position, 0.5);
+        axis.apply(AxisChange::Falling(Direction::Up));
+        axis.tick(Duration::from_millis(100));
+        assert!((axis.position - 0.1).abs() < 1e-9);
+        axis.tick(Duration::from_millis(100));
+        assert_eq!(axis.position, 0.0);
+    }
+
+    #[test]
+    fn response_curve_shapes_output() {
+        let mut axis = Axis::default();
+        axis.configure(AxisConfig {
+            response: ResponseCurve::Power(2.0),
+            ..AxisConfig::default()
+        });
+        axis.apply(AxisChange::Position(-0.5));
+        assert_eq!(axis.position, -0.5);
+        assert_eq!(axis.output(), -0.25);
+        axis.configure(AxisConfig {
+            response: ResponseCurve::Custom(|position| position * 2.0),
+            ..AxisConfig::default()
+        });
+        assert_eq!(axis.output(), -1.0);
+    }
+
+    #[test]
+    fn custom_curves_hash_without_their_address() {
+        use std::collections::hash_map::DefaultHasher;
+        let hash = |curve: ResponseCurve| {
+            let mut hasher = DefaultHasher::new();
+            curve.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(
+            hash(ResponseCurve::Custom(|position| position)),
+            hash(ResponseCurve::Custom(|position| -position))
+        );
+        assert_ne!(
+            hash(ResponseCurve::Custom(|position| position)),
+            hash(ResponseCurve::Linear)
+        );
+    }
 }
@@ -0,0 +1,149 @@
+use axis::InputAxisConfig;
+use change::Axis2dChange;
+#[cfg(feature = "encoding")]
+use encoding::{BitReader, BitWriter, Decode, DecodeError, Encode};
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+/// Both axes of a stick in one state, so the dead zone and curve see the
+/// whole vector. `x` and `y` are the shaped position, `raw` what was applied.
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct Axis2d {
+    pub x: f64,
+    pub y: f64,
+    pub new_event: bool,
+    raw: (f64, f64),
+    config: InputAxisConfig,
+    last_changed_at: Option<Duration>,
+}
+
+#[cfg(feature = "encoding")]
+impl Encode for Axis2d {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.x.encode(writer);
+        self.y.encode(writer);
+        self.new_event.encode(writer);
+        self.raw.encode(writer);
+        self.config.encode(writer);
+        self.last_changed_at.encode(writer);
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl Decode for Axis2d {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(Axis2d {
+            x: f64::decode(reader)?,
+            y: f64::decode(reader)?,
+            new_event: bool::decode(reader)?,
+            raw: Decode::decode(reader)?,
+            config: InputAxisConfig::decode(reader)?,
+            last_changed_at: Option::decode(reader)?,
+        })
+    }
+}
+
+impl Hash for Axis2d {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.to_bits().hash(state);
+        self.y.to_bits().hash(state);
+        self.new_event.hash(state);
+        self.raw.0.to_bits().hash(state);
+        self.raw.1.to_bits().hash(state);
+        self.config.hash(state);
+        self.last_changed_at.hash(state);
+    }
+}
+
+pub trait IAxis2d {
+    fn apply(&mut self, change: Axis2dChange);
+    fn apply_at(&mut self, change: Axis2dChange, at: Duration);
+    fn tick(&mut self, delta: Duration);
+    fn end_frame(&mut self);
+    fn configure(&mut self, config: InputAxisConfig);
+    fn raw(&self) -> (f64, f64);
+    fn magnitude(&self) -> f64;
+    fn last_changed_at(&self) -> Option<Duration>;
+    #[cfg(feature = "strict-invariants")]
+    fn assert_invariants(&self);
+}
+
+impl Axis2d {
+    fn reshape(&mut self) {
+        let (x, y) = self.config.stick_stages(self.raw.0, self.raw.1).clamped;
+        if (x, y) != (self.x, self.y) {
+            self.x = x;
+            self.y = y;
+            self.new_event = true;
+        }
+    }
+}
+
+impl IAxis2d for Axis2d {
+    fn apply(&mut self, change: Axis2dChange) {
+        self.last_changed_at = None;
+        self.raw = (change.x, change.y);
+        self.reshape();
+    }
+    fn apply_at(&mut self, change: Axis2dChange, at: Duration) {
+        self.apply(change);
+        self.last_changed_at = Some(at);
+    }
+    fn tick(&mut self, _delta: Duration) {
+        self.end_frame();
+    }
+    fn end_frame(&mut self) {
+        self.new_event = false;
+    }
+    fn configure(&mut self, config: InputAxisConfig) {
+        self.config = config;
+        self.reshape();
+    }
+    fn raw(&self) -> (f64, f64) {
+        self.raw
+    }
+    fn magnitude(&self) -> f64 {
+        self.x.hypot(self.y)
+    }
+    fn last_changed_at(&self) -> Option<Duration> {
+        self.last_changed_at
+    }
+    #[cfg(feature = "strict-invariants")]
+    fn assert_invariants(&self) {
+        debug_assert!(self.magnitude() <= 1.0 + 1e-9, "axis2d outside unit circle");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axis::DeadZoneShape;
+    #[test]
+    fn shapes_the_whole_vector() {
+        let mut stick = Axis2d::default();
+        stick.configure(InputAxisConfig {
+            dead_zone: 0.5,
+            ..InputAxisConfig::default()
+        });
+        stick.apply(Axis2dChange { x: 0.25, y: 0.25 });
+        assert_eq!((stick.x, stick.y), (0.0, 0.0));
+        assert!(!stick.new_event);
+        stick.apply(Axis2dChange { x: 0.0, y: -0.75 });
+        assert_eq!((stick.x, stick.y), (0.0, -0.5));
+        assert!(stick.new_event);
+        stick.apply(Axis2dChange { x: 1.0, y: 1.0 });
+        assert!((stick.magnitude() - 1.0).abs() < 1e-9);
+        assert_eq!(stick.raw(), (1.0, 1.0));
+    }
+    #[test]
+    fn axial_dead_zone_keeps_square_response() {
+        let mut stick = Axis2d::default();
+        stick.configure(InputAxisConfig {
+            dead_zone: 0.5,
+            dead_zone_shape: DeadZoneShape::Axial,
+            ..InputAxisConfig::default()
+        });
+        stick.apply(Axis2dChange { x: 0.75, y: 0.375 });
+        assert_eq!((stick.x, stick.y), (0.5, 0.0));
+    }
+}
@@ -0,0 +1,2 @@
+mod axis2d;
+pub use self::axis2d::*;
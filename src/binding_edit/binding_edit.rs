@@ -0,0 +1,127 @@
+use manager::{Binding, IBindingEdits};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum BindingConflict<A, B, C> {
+    Rebound {
+        input: C,
+        from: Binding<A, B>,
+        to: Binding<A, B>,
+    },
+    Unbound(Binding<A, B>),
+}
+
+/// A copy of a manager's bindings that can be edited and previewed before
+/// being committed back to it or discarded.
+#[derive(Debug, Clone)]
+pub struct BindingEdit<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> {
+    original: HashMap<C, Binding<A, B>>,
+    bindings: HashMap<C, Binding<A, B>>,
+}
+
+pub trait IBindingEdit<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> {
+    fn new(bindings: HashMap<C, Binding<A, B>>) -> Self
+    where
+        Self: Sized;
+    fn add_axis_binding(&mut self, axis: A, input: C);
+    fn add_button_binding(&mut self, button: B, input: C);
+    fn remove_binding(&mut self, input: &C);
+    fn get_binding(&self, input: &C) -> Option<&Binding<A, B>>;
+    fn conflicts(&self) -> Vec<BindingConflict<A, B, C>>;
+    fn into_bindings(self) -> HashMap<C, Binding<A, B>>;
+    fn commit(self, manager: &mut dyn IBindingEdits<A, B, C>);
+    fn discard(self);
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq + Clone> IBindingEdit<A, B, C>
+    for BindingEdit<A, B, C>
+{
+    fn new(bindings: HashMap<C, Binding<A, B>>) -> Self {
+        BindingEdit {
+            original: bindings.clone(),
+            bindings,
+        }
+    }
+    fn add_axis_binding(&mut self, axis: A, input: C) {
+        self.bindings.insert(input, Binding::Axis(axis));
+    }
+    fn add_button_binding(&mut self, button: B, input: C) {
+        self.bindings.insert(input, Binding::Button(button));
+    }
+    fn remove_binding(&mut self, input: &C) {
+        self.bindings.remove(input);
+    }
+    fn get_binding(&self, input: &C) -> Option<&Binding<A, B>> {
+        self.bindings.get(input)
+    }
+    fn conflicts(&self) -> Vec<BindingConflict<A, B, C>> {
+        let mut conflicts: Vec<BindingConflict<A, B, C>> = self
+            .bindings
+            .iter()
+            .filter_map(|(input, to)| match self.original.get(input) {
+                Some(from) if from != to => Some(BindingConflict::Rebound {
+                    input: input.clone(),
+                    from: from.clone(),
+                    to: to.clone(),
+                }),
+                _ => None,
+            })
+            .collect();
+        for binding in self.original.values() {
+            let unbound = !self.bindings.values().any(|bound| bound == binding);
+            let reported = conflicts
+                .iter()
+                .any(|conflict| conflict == &BindingConflict::Unbound(binding.clone()));
+            if unbound && !reported {
+                conflicts.push(BindingConflict::Unbound(binding.clone()));
+            }
+        }
+        conflicts
+    }
+    fn into_bindings(self) -> HashMap<C, Binding<A, B>> {
+        self.bindings
+    }
+    fn commit(self, manager: &mut dyn IBindingEdits<A, B, C>) {
+        manager.commit_binding_edit(self);
+    }
+    fn discard(self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn edit() -> BindingEdit<&'static str, &'static str, char> {
+        let mut bindings = HashMap::new();
+        bindings.insert('w', Binding::Axis("Vertical"));
+        bindings.insert(' ', Binding::Button("Jump"));
+        bindings.insert('f', Binding::Button("Fire"));
+        BindingEdit::new(bindings)
+    }
+    #[test]
+    fn no_conflicts_without_changes() {
+        assert!(edit().conflicts().is_empty());
+    }
+    #[test]
+    fn reports_rebound_input_and_unbound_action() {
+        let mut edit = edit();
+        edit.add_button_binding("Jump", 'f');
+        let conflicts = edit.conflicts();
+        assert_eq!(conflicts.len(), 2);
+        assert!(conflicts.contains(&BindingConflict::Rebound {
+            input: 'f',
+            from: Binding::Button("Fire"),
+            to: Binding::Button("Jump"),
+        }));
+        assert!(conflicts.contains(&BindingConflict::Unbound(Binding::Button("Fire"))));
+    }
+    #[test]
+    fn removing_last_input_unbinds() {
+        let mut edit = edit();
+        edit.remove_binding(&'w');
+        assert_eq!(
+            edit.conflicts(),
+            vec![BindingConflict::Unbound(Binding::Axis("Vertical"))]
+        );
+    }
+}
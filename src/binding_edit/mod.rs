@@ -0,0 +1,2 @@
+mod binding_edit;
+pub use self::binding_edit::*;
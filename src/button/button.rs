@@ -1,25 +1,402 @@
-use change::ButtonChange;
+use change::{ButtonChange, PressureChange};
+#[cfg(feature = "encoding")]
+use encoding::{BitReader, BitWriter, Decode, DecodeError, Encode};
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
 
-#[derive(Default, Debug, PartialEq)]
+#[derive(Default, Debug, PartialEq, Clone, Hash)]
+pub enum ButtonEdge {
+    #[default]
+    Press,
+    Release,
+}
+
+/// `debounce` ignores a repress within the given time of the last release,
+/// `slow_keys` requires a press to be held that long before it registers and
+/// `turbo` pulses the button once per given period while it is held. `edge`
+/// picks whether the button fires on press or, for negative edge, on release.
+/// `cooldown` ignores presses for the given time after one registers, while
+/// `max_rate` caps registered presses per second and coalesces the excess
+/// into a single press once the rate allows it. `repeat` fires the press
+/// again while held, like keyboard repeat, without releasing in between.
+/// `actuation` is the pressure at which an analog button counts as pressed,
+/// 0.5 when unset.
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct ButtonConfig {
+    pub debounce: Option<Duration>,
+    pub slow_keys: Option<Duration>,
+    pub turbo: Option<Duration>,
+    pub edge: ButtonEdge,
+    pub cooldown: Option<Duration>,
+    pub max_rate: Option<u32>,
+    pub repeat: Option<ButtonRepeat>,
+    pub actuation: Option<f64>,
+}
+
+impl Hash for ButtonConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.debounce.hash(state);
+        self.slow_keys.hash(state);
+        self.turbo.hash(state);
+        self.edge.hash(state);
+        self.cooldown.hash(state);
+        self.max_rate.hash(state);
+        self.repeat.hash(state);
+        self.actuation.map(f64::to_bits).hash(state);
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Hash)]
+pub struct ButtonRepeat {
+    pub delay: Duration,
+    pub interval: Duration,
+}
+
+#[derive(Default, Debug, PartialEq, Clone)]
 pub struct Button {
     pub pressed: bool,
+    /// How far the button is pushed, 1 or 0 for digital changes.
+    pub value: f64,
     pub new_event: bool,
+    /// Set for the frame in which the button went from pressed to released.
+    pub just_released: bool,
+    /// How long the button has been physically held. It keeps its value
+    /// through the frame it is released in.
+    pub held_duration: Duration,
+    config: ButtonConfig,
+    since_release: Option<Duration>,
+    pending_press: Option<Duration>,
+    held: bool,
+    turbo_elapsed: Duration,
+    last_changed_at: Option<Duration>,
+    pending_edges: Vec<ButtonEdge>,
+    edges: Vec<ButtonEdge>,
+    cooldown_remaining: Duration,
+    since_trigger: Option<Duration>,
+    coalesced_press: bool,
+    coalesced_release: bool,
+    repeat_elapsed: Duration,
+    repeats: u32,
+}
+
+#[cfg(feature = "encoding")]
+impl Encode for Button {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.pressed.encode(writer);
+        self.value.encode(writer);
+        self.new_event.encode(writer);
+        self.just_released.encode(writer);
+        self.held_duration.encode(writer);
+        self.config.encode(writer);
+        self.since_release.encode(writer);
+        self.pending_press.encode(writer);
+        self.held.encode(writer);
+        self.turbo_elapsed.encode(writer);
+        self.last_changed_at.encode(writer);
+        self.pending_edges.encode(writer);
+        self.edges.encode(writer);
+        self.cooldown_remaining.encode(writer);
+        self.since_trigger.encode(writer);
+        self.coalesced_press.encode(writer);
+        self.coalesced_release.encode(writer);
+        self.repeat_elapsed.encode(writer);
+        self.repeats.encode(writer);
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl Decode for Button {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(Button {
+            pressed: bool::decode(reader)?,
+            value: f64::decode(reader)?,
+            new_event: bool::decode(reader)?,
+            just_released: bool::decode(reader)?,
+            held_duration: Duration::decode(reader)?,
+            config: ButtonConfig::decode(reader)?,
+            since_release: Option::decode(reader)?,
+            pending_press: Option::decode(reader)?,
+            held: bool::decode(reader)?,
+            turbo_elapsed: Duration::decode(reader)?,
+            last_changed_at: Option::decode(reader)?,
+            pending_edges: Vec::decode(reader)?,
+            edges: Vec::decode(reader)?,
+            cooldown_remaining: Duration::decode(reader)?,
+            since_trigger: Option::decode(reader)?,
+            coalesced_press: bool::decode(reader)?,
+            coalesced_release: bool::decode(reader)?,
+            repeat_elapsed: Duration::decode(reader)?,
+            repeats: u32::decode(reader)?,
+        })
+    }
+}
+
+impl Hash for Button {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.pressed.hash(state);
+        self.value.to_bits().hash(state);
+        self.new_event.hash(state);
+        self.just_released.hash(state);
+        self.held_duration.hash(state);
+        self.config.hash(state);
+        self.since_release.hash(state);
+        self.pending_press.hash(state);
+        self.held.hash(state);
+        self.turbo_elapsed.hash(state);
+        self.last_changed_at.hash(state);
+        self.pending_edges.hash(state);
+        self.edges.hash(state);
+        self.cooldown_remaining.hash(state);
+        self.since_trigger.hash(state);
+        self.coalesced_press.hash(state);
+        self.coalesced_release.hash(state);
+        self.repeat_elapsed.hash(state);
+        self.repeats.hash(state);
+    }
 }
 
 pub trait IButton {
     fn apply(&mut self, ButtonChange);
-    fn tick(&mut self);
+    fn apply_at(&mut self, change: ButtonChange, at: Duration);
+    fn apply_pressure(&mut self, change: PressureChange);
+    fn apply_pressure_at(&mut self, change: PressureChange, at: Duration);
+    fn tick(&mut self, Duration);
+    fn advance(&mut self, delta: Duration);
+    fn end_frame(&mut self);
+    fn configure(&mut self, config: ButtonConfig);
+    fn last_changed_at(&self) -> Option<Duration>;
+    fn fired(&self) -> bool;
+    fn fired_since(&self, was_pressed: bool) -> bool;
+    /// Edges published by the last `tick` or `end_frame`, both from applied
+    /// changes and from timed ones such as turbo and repeat.
+    fn edges(&self) -> &[ButtonEdge];
+    fn is_held(&self) -> bool;
+    fn cooldown_remaining(&self) -> Duration;
+    #[cfg(feature = "strict-invariants")]
+    fn assert_invariants(&self);
+}
+
+impl Button {
+    fn rate_interval(&self) -> Option<Duration> {
+        self.config
+            .max_rate
+            .filter(|&rate| rate > 0)
+            .map(|rate| Duration::from_secs(1) / rate)
+    }
+    fn clear_flags(&mut self) {
+        self.new_event = false;
+        self.just_released = false;
+    }
+    fn publish_edges(&mut self) {
+        self.edges.clear();
+        std::mem::swap(&mut self.edges, &mut self.pending_edges);
+    }
 }
 
 impl IButton for Button {
     fn apply(&mut self, change: ButtonChange) {
-        if self.pressed != change.0 {
+        self.last_changed_at = None;
+        let pressed = change.0;
+        if pressed && !self.held {
+            if self.cooldown_remaining > Duration::default() {
+                return;
+            }
+            if let (Some(debounce), Some(since_release)) =
+                (self.config.debounce, self.since_release)
+            {
+                if since_release < debounce {
+                    return;
+                }
+            }
+            if let (Some(interval), Some(since_trigger)) =
+                (self.rate_interval(), self.since_trigger)
+            {
+                if since_trigger < interval {
+                    self.coalesced_press = true;
+                    self.held = true;
+                    return;
+                }
+            }
+            if self.config.slow_keys.is_some() {
+                if self.pending_press.is_none() {
+                    self.pending_press = Some(Duration::default());
+                }
+                return;
+            }
+        }
+        if !pressed {
+            self.pending_press = None;
+            if self.held {
+                self.since_release = Some(Duration::default());
+            }
+        }
+        if self.pressed != pressed {
             self.new_event = true;
+            self.just_released |= !pressed;
+            self.pending_edges.push(if pressed {
+                ButtonEdge::Press
+            } else {
+                ButtonEdge::Release
+            });
+        }
+        if self.held != pressed {
+            self.turbo_elapsed = Duration::default();
+        }
+        if pressed && !self.held {
+            self.held_duration = Duration::default();
+            self.cooldown_remaining = self.config.cooldown.unwrap_or_default();
+            self.since_trigger = Some(Duration::default());
         }
-        self.pressed = change.0;
+        self.pressed = pressed;
+        self.held = pressed;
+        self.value = if pressed { 1.0 } else { 0.0 };
     }
-    fn tick(&mut self) {
-        self.new_event = false;
+    fn tick(&mut self, delta: Duration) {
+        self.clear_flags();
+        self.advance(delta);
+        self.publish_edges();
+    }
+    fn end_frame(&mut self) {
+        self.clear_flags();
+        self.publish_edges();
+    }
+    fn advance(&mut self, delta: Duration) {
+        self.cooldown_remaining = self.cooldown_remaining.saturating_sub(delta);
+        self.held_duration = if self.held {
+            self.held_duration.saturating_add(delta)
+        } else {
+            Duration::default()
+        };
+        if self.coalesced_release {
+            self.coalesced_release = false;
+            if !self.held && self.pressed {
+                self.pressed = false;
+                self.new_event = true;
+                self.just_released = true;
+                self.pending_edges.push(ButtonEdge::Release);
+            }
+        }
+        if let Some(since_trigger) = self.since_trigger {
+            let since_trigger = since_trigger.saturating_add(delta);
+            self.since_trigger = Some(since_trigger);
+            if self.coalesced_press && Some(since_trigger) >= self.rate_interval() {
+                self.coalesced_press = false;
+                self.since_trigger = Some(Duration::default());
+                if !self.pressed {
+                    self.pressed = true;
+                    self.value = 1.0;
+                    self.new_event = true;
+                    self.pending_edges.push(ButtonEdge::Press);
+                    self.coalesced_release = !self.held;
+                }
+            }
+        }
+        if let Some(since_release) = self.since_release {
+            self.since_release = Some(since_release.saturating_add(delta));
+        }
+        if let Some(held) = self.pending_press {
+            let held = held.saturating_add(delta);
+            if held >= self.config.slow_keys.unwrap_or_default() {
+                self.pending_press = None;
+                self.pressed = true;
+                self.value = 1.0;
+                self.held = true;
+                self.new_event = true;
+                self.pending_edges.push(ButtonEdge::Press);
+                self.cooldown_remaining = self.config.cooldown.unwrap_or_default();
+                self.turbo_elapsed = Duration::default();
+            } else {
+                self.pending_press = Some(held);
+            }
+        } else if let (Some(period), true) = (self.config.turbo, self.held) {
+            let half_period = period / 2;
+            self.turbo_elapsed = self.turbo_elapsed.saturating_add(delta);
+            if self.turbo_elapsed >= half_period && half_period > Duration::default() {
+                self.turbo_elapsed = Duration::from_nanos(
+                    (self.turbo_elapsed.as_nanos() % half_period.as_nanos()) as u64,
+                );
+                self.pressed = !self.pressed;
+                self.new_event = true;
+                self.just_released |= !self.pressed;
+                self.pending_edges.push(if self.pressed {
+                    ButtonEdge::Press
+                } else {
+                    ButtonEdge::Release
+                });
+            }
+        }
+        match (&self.config.repeat, self.held && self.pressed) {
+            (Some(repeat), true) => {
+                self.repeat_elapsed = self.repeat_elapsed.saturating_add(delta);
+                let due = match self.repeat_elapsed.checked_sub(repeat.delay) {
+                    Some(_) if repeat.interval == Duration::default() => 1,
+                    Some(since) => 1 + (since.as_nanos() / repeat.interval.as_nanos()) as u32,
+                    None => 0,
+                };
+                if due > self.repeats {
+                    self.repeats = due;
+                    self.new_event = true;
+                    self.pending_edges.push(ButtonEdge::Press);
+                }
+            }
+            _ => {
+                self.repeat_elapsed = Duration::default();
+                self.repeats = 0;
+            }
+        }
+    }
+    fn apply_at(&mut self, change: ButtonChange, at: Duration) {
+        self.apply(change);
+        self.last_changed_at = Some(at);
+    }
+    fn apply_pressure(&mut self, change: PressureChange) {
+        let value = change.0.clamp(0.0, 1.0);
+        let pressed = value >= self.config.actuation.unwrap_or(0.5);
+        if pressed != self.held {
+            self.apply(ButtonChange(pressed));
+        }
+        self.last_changed_at = None;
+        self.value = value;
+    }
+    fn apply_pressure_at(&mut self, change: PressureChange, at: Duration) {
+        self.apply_pressure(change);
+        self.last_changed_at = Some(at);
+    }
+    fn configure(&mut self, config: ButtonConfig) {
+        self.config = config;
+    }
+    fn last_changed_at(&self) -> Option<Duration> {
+        self.last_changed_at
+    }
+    fn edges(&self) -> &[ButtonEdge] {
+        &self.edges
+    }
+    fn is_held(&self) -> bool {
+        self.held
+    }
+    fn cooldown_remaining(&self) -> Duration {
+        self.cooldown_remaining
+    }
+    #[cfg(feature = "strict-invariants")]
+    fn assert_invariants(&self) {
+        debug_assert!(
+            !self.pressed || self.held || self.coalesced_release,
+            "button pressed but not held"
+        );
+        debug_assert!(
+            !self.new_event || !self.pending_edges.is_empty() || !self.edges.is_empty(),
+            "button new_event without an edge"
+        );
+    }
+    fn fired(&self) -> bool {
+        self.pending_edges.contains(&self.config.edge)
+            || (self.new_event && self.fired_since(!self.pressed))
+    }
+    fn fired_since(&self, was_pressed: bool) -> bool {
+        match self.config.edge {
+            ButtonEdge::Press => !was_pressed && self.pressed,
+            ButtonEdge::Release => was_pressed && !self.pressed,
+        }
     }
 }
 
@@ -50,6 +427,7 @@ mod tests {
         button = Button {
             pressed: true,
             new_event: false,
+            ..Button::default()
         };
         button.apply(ButtonChange(true));
         assert_eq!(button.new_event, false);
@@ -59,8 +437,316 @@ mod tests {
         let mut button = Button {
             pressed: true,
             new_event: true,
+            ..Button::default()
         };
-        button.tick();
+        button.tick(Duration::default());
         assert_eq!(button.new_event, false);
     }
+    #[test]
+    fn debounce_ignores_quick_repress() {
+        let mut button = Button::default();
+        button.configure(ButtonConfig {
+            debounce: Some(Duration::from_millis(50)),
+            ..ButtonConfig::default()
+        });
+        button.apply(ButtonChange(true));
+        button.apply(ButtonChange(false));
+        button.tick(Duration::from_millis(20));
+        button.apply(ButtonChange(true));
+        assert!(!button.pressed);
+        button.tick(Duration::from_millis(40));
+        button.apply(ButtonChange(true));
+        assert!(button.pressed);
+    }
+    #[test]
+    fn slow_keys_requires_hold() {
+        let mut button = Button::default();
+        button.configure(ButtonConfig {
+            slow_keys: Some(Duration::from_millis(100)),
+            ..ButtonConfig::default()
+        });
+        button.apply(ButtonChange(true));
+        assert!(!button.pressed);
+        button.tick(Duration::from_millis(60));
+        assert!(!button.pressed);
+        button.tick(Duration::from_millis(60));
+        assert!(button.pressed);
+        assert!(button.new_event);
+    }
+    #[test]
+    fn slow_keys_cancelled_by_early_release() {
+        let mut button = Button::default();
+        button.configure(ButtonConfig {
+            slow_keys: Some(Duration::from_millis(100)),
+            ..ButtonConfig::default()
+        });
+        button.apply(ButtonChange(true));
+        button.tick(Duration::from_millis(60));
+        button.apply(ButtonChange(false));
+        button.tick(Duration::from_millis(60));
+        assert!(!button.pressed);
+        assert!(!button.new_event);
+    }
+    #[test]
+    fn turbo_pulses_while_held() {
+        let mut button = Button::default();
+        button.configure(ButtonConfig {
+            turbo: Some(Duration::from_millis(100)),
+            ..ButtonConfig::default()
+        });
+        button.apply(ButtonChange(true));
+        assert!(button.pressed);
+        button.tick(Duration::from_millis(30));
+        assert!(button.pressed);
+        assert!(!button.new_event);
+        button.tick(Duration::from_millis(30));
+        assert!(!button.pressed);
+        assert!(button.new_event);
+        button.tick(Duration::from_millis(50));
+        assert!(button.pressed);
+        assert!(button.new_event);
+        button.apply(ButtonChange(false));
+        button.tick(Duration::from_millis(50));
+        button.tick(Duration::from_millis(50));
+        assert!(!button.pressed);
+    }
+    #[test]
+    fn fires_on_configured_edge() {
+        let mut button = Button::default();
+        button.apply(ButtonChange(true));
+        assert!(button.fired());
+        button.configure(ButtonConfig {
+            edge: ButtonEdge::Release,
+            ..ButtonConfig::default()
+        });
+        assert!(!button.fired());
+        button.apply(ButtonChange(false));
+        assert!(button.fired());
+        button.tick(Duration::default());
+        assert!(!button.fired());
+    }
+
+    #[test]
+    fn press_and_release_within_frame_reports_both_edges() {
+        let mut button = Button::default();
+        button.apply(ButtonChange(true));
+        button.apply(ButtonChange(false));
+        assert!(button.edges().is_empty());
+        button.tick(Duration::default());
+        assert_eq!(button.edges(), &[ButtonEdge::Press, ButtonEdge::Release]);
+        button.tick(Duration::default());
+        assert!(button.edges().is_empty());
+    }
+
+    #[test]
+    fn tap_within_frame_fires_and_releases() {
+        let mut button = Button::default();
+        button.apply(ButtonChange(true));
+        button.apply(ButtonChange(false));
+        assert!(button.fired());
+        assert!(button.just_released);
+        button.tick(Duration::default());
+        assert!(!button.fired());
+        assert!(!button.just_released);
+    }
+
+    #[test]
+    fn cooldown_ignores_presses_until_elapsed() {
+        let mut button = Button::default();
+        button.configure(ButtonConfig {
+            cooldown: Some(Duration::from_millis(500)),
+            ..ButtonConfig::default()
+        });
+        button.apply(ButtonChange(true));
+        button.apply(ButtonChange(false));
+        button.tick(Duration::from_millis(200));
+        assert_eq!(button.cooldown_remaining(), Duration::from_millis(300));
+        button.apply(ButtonChange(true));
+        assert!(!button.pressed);
+        button.apply(ButtonChange(false));
+        button.tick(Duration::from_millis(300));
+        button.apply(ButtonChange(true));
+        assert!(button.pressed);
+    }
+
+    #[test]
+    fn held_duration_saturates() {
+        let mut button = Button::default();
+        button.apply(ButtonChange(true));
+        button.held_duration = Duration::MAX;
+        button.tick(Duration::from_millis(16));
+        assert_eq!(button.held_duration, Duration::MAX);
+    }
+
+    #[test]
+    fn press_during_cooldown_keeps_value_at_rest() {
+        let mut button = Button::default();
+        button.configure(ButtonConfig {
+            cooldown: Some(Duration::from_millis(500)),
+            ..ButtonConfig::default()
+        });
+        button.apply(ButtonChange(true));
+        assert_eq!(button.value, 1.0);
+        button.apply(ButtonChange(false));
+        button.tick(Duration::from_millis(200));
+        button.apply(ButtonChange(true));
+        assert!(!button.pressed);
+        assert_eq!(button.value, 0.0);
+    }
+
+    #[test]
+    fn publishes_timed_edges_at_end_frame() {
+        let mut button = Button::default();
+        button.configure(ButtonConfig {
+            turbo: Some(Duration::from_millis(100)),
+            ..ButtonConfig::default()
+        });
+        button.apply(ButtonChange(true));
+        button.end_frame();
+        button.advance(Duration::from_millis(50));
+        assert!(!button.pressed);
+        assert_eq!(button.edges(), &[ButtonEdge::Press]);
+        button.end_frame();
+        assert_eq!(button.edges(), &[ButtonEdge::Release]);
+        button.tick(Duration::from_millis(50));
+        assert_eq!(button.edges(), &[ButtonEdge::Press]);
+    }
+
+    #[test]
+    fn turbo_pulse_keeps_release_within_frame() {
+        let mut button = Button::default();
+        button.configure(ButtonConfig {
+            turbo: Some(Duration::from_millis(100)),
+            ..ButtonConfig::default()
+        });
+        button.apply(ButtonChange(true));
+        button.end_frame();
+        button.apply(ButtonChange(false));
+        button.apply(ButtonChange(true));
+        button.advance(Duration::from_millis(50));
+        button.advance(Duration::from_millis(50));
+        assert!(button.pressed);
+        assert!(button.just_released);
+        button.end_frame();
+        assert!(!button.just_released);
+    }
+
+    #[test]
+    fn max_rate_coalesces_excess_presses() {
+        let mut button = Button::default();
+        button.configure(ButtonConfig {
+            max_rate: Some(2),
+            ..ButtonConfig::default()
+        });
+        button.apply(ButtonChange(true));
+        button.apply(ButtonChange(false));
+        for _ in 0..3 {
+            button.apply(ButtonChange(true));
+            button.apply(ButtonChange(false));
+        }
+        assert!(!button.pressed);
+        button.tick(Duration::from_millis(400));
+        assert!(!button.fired());
+        button.tick(Duration::from_millis(100));
+        assert!(button.fired());
+        assert_eq!(button.edges(), &[ButtonEdge::Press]);
+        button.tick(Duration::from_millis(16));
+        assert!(!button.pressed);
+        assert_eq!(button.edges(), &[ButtonEdge::Release]);
+    }
+
+    #[test]
+    fn max_rate_ignores_debounced_presses() {
+        let mut button = Button::default();
+        button.configure(ButtonConfig {
+            max_rate: Some(2),
+            debounce: Some(Duration::from_millis(50)),
+            ..ButtonConfig::default()
+        });
+        button.apply(ButtonChange(true));
+        button.tick(Duration::from_millis(16));
+        button.apply(ButtonChange(false));
+        button.tick(Duration::from_millis(10));
+        button.apply(ButtonChange(true));
+        button.apply(ButtonChange(false));
+        button.tick(Duration::from_millis(600));
+        assert!(!button.pressed);
+        assert!(!button.fired());
+        assert!(button.edges().is_empty());
+    }
+
+    #[test]
+    fn repeats_while_held() {
+        let mut button = Button::default();
+        button.configure(ButtonConfig {
+            repeat: Some(ButtonRepeat {
+                delay: Duration::from_millis(300),
+                interval: Duration::from_millis(100),
+            }),
+            ..ButtonConfig::default()
+        });
+        button.apply(ButtonChange(true));
+        button.tick(Duration::from_millis(200));
+        assert!(!button.fired());
+        button.tick(Duration::from_millis(100));
+        assert!(button.fired());
+        assert!(button.pressed);
+        button.tick(Duration::from_millis(50));
+        assert!(!button.fired());
+        button.tick(Duration::from_millis(50));
+        assert!(button.fired());
+        button.apply(ButtonChange(false));
+        button.tick(Duration::from_millis(400));
+        assert!(!button.fired());
+    }
+
+    #[test]
+    fn flags_release_for_one_frame() {
+        let mut button = Button::default();
+        button.apply(ButtonChange(true));
+        assert!(!button.just_released);
+        button.tick(Duration::from_millis(16));
+        button.apply(ButtonChange(false));
+        assert!(button.just_released);
+        button.tick(Duration::from_millis(16));
+        assert!(!button.just_released);
+    }
+
+    #[test]
+    fn accumulates_held_duration() {
+        let mut button = Button::default();
+        button.apply(ButtonChange(true));
+        button.tick(Duration::from_millis(100));
+        button.tick(Duration::from_millis(50));
+        assert_eq!(button.held_duration, Duration::from_millis(150));
+        button.apply(ButtonChange(false));
+        assert_eq!(button.held_duration, Duration::from_millis(150));
+        button.tick(Duration::from_millis(16));
+        assert_eq!(button.held_duration, Duration::default());
+    }
+
+    #[test]
+    fn pressure_flips_pressed_at_actuation() {
+        let mut button = Button::default();
+        button.configure(ButtonConfig {
+            actuation: Some(0.3),
+            ..ButtonConfig::default()
+        });
+        button.apply_pressure(PressureChange(0.2));
+        assert!(!button.pressed);
+        assert_eq!(button.value, 0.2);
+        button.apply_pressure(PressureChange(0.4));
+        assert!(button.pressed);
+        assert!(button.new_event);
+        assert_eq!(button.value, 0.4);
+        button.tick(Duration::from_millis(16));
+        button.apply_pressure(PressureChange(0.9));
+        assert!(!button.new_event);
+        assert_eq!(button.value, 0.9);
+        button.apply_pressure(PressureChange(0.1));
+        assert!(!button.pressed);
+        assert!(button.just_released);
+        button.apply(ButtonChange(true));
+        assert_eq!(button.value, 1.0);
+    }
 }
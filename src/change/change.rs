@@ -1,12 +1,89 @@
-#[derive(Clone)]
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, PartialEq, Clone, Hash)]
 pub struct ButtonChange(pub bool);
-#[derive(Clone)]
+
+/// How far a pressure-sensitive button is pushed, from 0 to 1.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PressureChange(pub f64);
+
+impl Hash for PressureChange {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+/// The new position of both axes of a stick.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Axis2dChange {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Hash for Axis2dChange {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.to_bits().hash(state);
+        self.y.to_bits().hash(state);
+    }
+}
+
+/// The new position of a trigger, from 0 at rest to 1 fully pulled.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TriggerChange(pub f64);
+
+impl Hash for TriggerChange {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum AxisChange {
     Position(f64),
     Velocity(Direction),
     Falling(Direction),
 }
 
+impl Hash for AxisChange {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            AxisChange::Position(pos) => {
+                0u8.hash(state);
+                pos.to_bits().hash(state);
+            }
+            AxisChange::Velocity(dir) => {
+                1u8.hash(state);
+                dir.hash(state);
+            }
+            AxisChange::Falling(dir) => {
+                2u8.hash(state);
+                dir.hash(state);
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ValueChange {
+    Set(f64),
+    Offset(f64),
+}
+
+impl Hash for ValueChange {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            ValueChange::Set(value) => {
+                0u8.hash(state);
+                value.to_bits().hash(state);
+            }
+            ValueChange::Offset(offset) => {
+                1u8.hash(state);
+                offset.to_bits().hash(state);
+            }
+        }
+    }
+}
+
 impl Into<f64> for Direction {
     fn into(self) -> f64 {
         match self {
@@ -16,7 +93,7 @@ impl Into<f64> for Direction {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Hash)]
 pub enum Direction {
     Up,
     Down,
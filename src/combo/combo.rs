@@ -0,0 +1,278 @@
+use change::Direction;
+#[cfg(feature = "encoding")]
+use encoding::{BitReader, BitWriter, Decode, DecodeError, Encode};
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ComboStep<A, B> {
+    Button(B),
+    Axis(A, Direction),
+}
+
+/// A named sequence of steps, each of which must follow the previous one
+/// within `window`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Combo<A, B> {
+    pub steps: Vec<ComboStep<A, B>>,
+    pub window: Duration,
+}
+
+#[derive(Debug, Clone)]
+struct ComboProgress<A, B> {
+    name: String,
+    combo: Combo<A, B>,
+    matched: usize,
+    since_step: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct ComboRegistry<A, B> {
+    combos: Vec<ComboProgress<A, B>>,
+    pending: Vec<String>,
+    completed: Vec<String>,
+}
+
+pub trait IComboRegistry<A, B> {
+    fn add_combo(&mut self, name: String, combo: Combo<A, B>);
+    fn feed(&mut self, step: ComboStep<A, B>);
+    fn tick(&mut self, delta: Duration);
+    fn get_completed(&self) -> &[String];
+}
+
+impl<A, B> Default for ComboRegistry<A, B> {
+    fn default() -> Self {
+        ComboRegistry {
+            combos: Vec::new(),
+            pending: Vec::new(),
+            completed: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl<A: Encode, B: Encode> Encode for ComboRegistry<A, B> {
+    fn encode(&self, writer: &mut BitWriter) {
+        (self.combos.len() as u32).encode(writer);
+        for progress in self.combos.iter() {
+            progress.name.encode(writer);
+            progress.combo.encode(writer);
+            (progress.matched as u32).encode(writer);
+            progress.since_step.encode(writer);
+        }
+        self.pending.encode(writer);
+        self.completed.encode(writer);
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl<A: Decode, B: Decode> Decode for ComboRegistry<A, B> {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        let len = u32::decode(reader)?;
+        let combos = (0..len)
+            .map(|_| {
+                let name = String::decode(reader)?;
+                let combo: Combo<A, B> = Combo::decode(reader)?;
+                let matched = u32::decode(reader)? as usize;
+                if matched > combo.steps.len() {
+                    return Err(DecodeError::InvalidComboProgress);
+                }
+                Ok(ComboProgress {
+                    name,
+                    combo,
+                    matched,
+                    since_step: Duration::decode(reader)?,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(ComboRegistry {
+            combos,
+            pending: Vec::decode(reader)?,
+            completed: Vec::decode(reader)?,
+        })
+    }
+}
+
+impl<A: PartialEq, B: PartialEq> IComboRegistry<A, B> for ComboRegistry<A, B> {
+    fn add_combo(&mut self, name: String, combo: Combo<A, B>) {
+        self.combos.retain(|progress| progress.name != name);
+        self.combos.push(ComboProgress {
+            name,
+            combo,
+            matched: 0,
+            since_step: Duration::default(),
+        });
+    }
+    fn feed(&mut self, step: ComboStep<A, B>) {
+        for progress in self.combos.iter_mut() {
+            let steps = &progress.combo.steps;
+            // On a mismatch, fall back to the longest matched suffix that is
+            // also a prefix of the combo, so `A A B` still completes `A B`.
+            let mut matched = progress.matched;
+            while matched > 0 && steps.get(matched) != Some(&step) {
+                matched = (0..matched)
+                    .rev()
+                    .find(|&len| steps[..len] == steps[matched - len..matched])
+                    .unwrap_or_default();
+            }
+            progress.matched = if steps.get(matched) == Some(&step) {
+                matched + 1
+            } else {
+                0
+            };
+            progress.since_step = Duration::default();
+            if progress.matched > 0 && progress.matched == progress.combo.steps.len() {
+                progress.matched = 0;
+                self.pending.push(progress.name.clone());
+            }
+        }
+    }
+    fn tick(&mut self, delta: Duration) {
+        for progress in self.combos.iter_mut() {
+            progress.since_step = progress.since_step.saturating_add(delta);
+            if progress.since_step > progress.combo.window {
+                progress.matched = 0;
+            }
+        }
+        self.completed = self.pending.drain(..).collect();
+    }
+    fn get_completed(&self) -> &[String] {
+        &self.completed
+    }
+}
+
+/// Two buttons that must be held together for `hold` before the confirm
+/// fires. It fires once and rearms only after either button is let go.
+#[derive(Debug, PartialEq, Clone)]
+pub struct HoldConfirm<B> {
+    pub first: B,
+    pub second: B,
+    pub hold: Duration,
+    held_for: Duration,
+    fired: bool,
+}
+
+pub trait IHoldConfirm<B> {
+    fn new(first: B, second: B, hold: Duration) -> Self
+    where
+        Self: Sized;
+    fn advance(&mut self, both_held: bool, delta: Duration) -> bool;
+}
+
+impl<B> IHoldConfirm<B> for HoldConfirm<B> {
+    fn new(first: B, second: B, hold: Duration) -> Self {
+        HoldConfirm {
+            first,
+            second,
+            hold,
+            held_for: Duration::default(),
+            fired: false,
+        }
+    }
+    fn advance(&mut self, both_held: bool, delta: Duration) -> bool {
+        if !both_held {
+            self.held_for = Duration::default();
+            self.fired = false;
+            return false;
+        }
+        self.held_for += delta;
+        if self.fired || self.held_for < self.hold {
+            return false;
+        }
+        self.fired = true;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn hadouken() -> Combo<&'static str, &'static str> {
+        Combo {
+            steps: vec![
+                ComboStep::Axis("Vertical", Direction::Down),
+                ComboStep::Axis("Horizontal", Direction::Up),
+                ComboStep::Button("Punch"),
+            ],
+            window: Duration::from_millis(200),
+        }
+    }
+    #[test]
+    fn completes_on_tick() {
+        let mut registry = ComboRegistry::default();
+        registry.add_combo("Hadouken".to_string(), hadouken());
+        registry.feed(ComboStep::Axis("Vertical", Direction::Down));
+        registry.feed(ComboStep::Axis("Horizontal", Direction::Up));
+        registry.feed(ComboStep::Button("Punch"));
+        assert!(registry.get_completed().is_empty());
+        registry.tick(Duration::from_millis(16));
+        assert_eq!(registry.get_completed(), &["Hadouken".to_string()]);
+        registry.tick(Duration::from_millis(16));
+        assert!(registry.get_completed().is_empty());
+    }
+    #[test]
+    fn expires_after_window() {
+        let mut registry = ComboRegistry::default();
+        registry.add_combo("Hadouken".to_string(), hadouken());
+        registry.feed(ComboStep::Axis("Vertical", Direction::Down));
+        registry.tick(Duration::from_millis(300));
+        registry.feed(ComboStep::Axis("Horizontal", Direction::Up));
+        registry.feed(ComboStep::Button("Punch"));
+        registry.tick(Duration::from_millis(16));
+        assert!(registry.get_completed().is_empty());
+    }
+    #[test]
+    fn wrong_step_restarts() {
+        let mut registry = ComboRegistry::default();
+        registry.add_combo("Hadouken".to_string(), hadouken());
+        registry.feed(ComboStep::Axis("Vertical", Direction::Down));
+        registry.feed(ComboStep::Button("Punch"));
+        registry.feed(ComboStep::Axis("Vertical", Direction::Down));
+        registry.feed(ComboStep::Axis("Horizontal", Direction::Up));
+        registry.feed(ComboStep::Button("Punch"));
+        registry.tick(Duration::default());
+        assert_eq!(registry.get_completed().len(), 1);
+    }
+    #[test]
+    fn repeated_step_falls_back_to_partial_match() {
+        let mut registry: ComboRegistry<&str, &str> = ComboRegistry::default();
+        registry.add_combo(
+            "Dash".to_string(),
+            Combo {
+                steps: vec![ComboStep::Button("A"), ComboStep::Button("B")],
+                window: Duration::from_millis(200),
+            },
+        );
+        registry.add_combo(
+            "Flurry".to_string(),
+            Combo {
+                steps: vec![
+                    ComboStep::Button("A"),
+                    ComboStep::Button("B"),
+                    ComboStep::Button("A"),
+                    ComboStep::Button("C"),
+                ],
+                window: Duration::from_millis(200),
+            },
+        );
+        for button in ["A", "A", "B", "A", "B", "A", "C"] {
+            registry.feed(ComboStep::Button(button));
+        }
+        registry.tick(Duration::default());
+        assert_eq!(
+            registry.get_completed(),
+            &["Dash".to_string(), "Dash".to_string(), "Flurry".to_string()]
+        );
+    }
+
+    #[test]
+    fn hold_confirm_fires_once_per_hold() {
+        let mut confirm = HoldConfirm::new("A", "B", Duration::from_millis(500));
+        let frame = Duration::from_millis(250);
+        assert!(!confirm.advance(true, frame));
+        assert!(confirm.advance(true, frame));
+        assert!(!confirm.advance(true, frame));
+        assert!(!confirm.advance(false, frame));
+        assert!(!confirm.advance(true, frame));
+        assert!(confirm.advance(true, frame));
+    }
+}
@@ -0,0 +1,2 @@
+mod combo;
+pub use self::combo::*;
@@ -0,0 +1,48 @@
+use manager::Change;
+use std::any::Any;
+use std::time::Duration;
+
+/// A user-defined stateful input kind. Bound inputs feed it standard changes
+/// tagged with the slot they were bound to, so a state can combine several
+/// inputs (e.g. a radial menu reading two axes).
+pub trait CustomState {
+    fn apply(&mut self, slot: usize, change: &Change);
+    fn tick(&mut self, delta: Duration);
+    fn as_any(&self) -> &dyn Any;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use change::ButtonChange;
+
+    #[derive(Default)]
+    struct Counter {
+        presses: u32,
+        elapsed: Duration,
+    }
+
+    impl CustomState for Counter {
+        fn apply(&mut self, _slot: usize, change: &Change) {
+            if *change == Change::Button(ButtonChange(true)) {
+                self.presses += 1;
+            }
+        }
+        fn tick(&mut self, delta: Duration) {
+            self.elapsed += delta;
+        }
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    #[test]
+    fn downcasts_to_concrete_state() {
+        let mut state: Box<dyn CustomState> = Box::new(Counter::default());
+        state.apply(0, &ButtonChange(true).into());
+        state.tick(Duration::from_millis(5));
+        let counter = state.as_any().downcast_ref::<Counter>().unwrap();
+        assert_eq!(counter.presses, 1);
+        assert_eq!(counter.elapsed, Duration::from_millis(5));
+    }
+}
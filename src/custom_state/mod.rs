@@ -0,0 +1,2 @@
+mod custom_state;
+pub use self::custom_state::*;
@@ -0,0 +1,111 @@
+use change::{Axis2dChange, AxisChange, ButtonChange, PressureChange, TriggerChange};
+use manager::Change;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum DeviceClass {
+    KeyboardMouse,
+    Gamepad(usize),
+    Remote(usize),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DeviceSwitch {
+    pub from: Option<DeviceClass>,
+    pub to: DeviceClass,
+}
+
+/// Axis positions closer to center than `activity_threshold` are treated as
+/// noise so a resting stick cannot steal focus from the keyboard.
+#[derive(Debug, Clone)]
+pub struct ActiveDeviceTracker {
+    pub activity_threshold: f64,
+    active: Option<DeviceClass>,
+    pending_switch: Option<DeviceSwitch>,
+    switch: Option<DeviceSwitch>,
+}
+
+impl Default for ActiveDeviceTracker {
+    fn default() -> Self {
+        ActiveDeviceTracker {
+            activity_threshold: 0.2,
+            active: None,
+            pending_switch: None,
+            switch: None,
+        }
+    }
+}
+
+pub trait IActiveDeviceTracker {
+    fn observe(&mut self, class: DeviceClass, change: &Change);
+    fn tick(&mut self);
+    fn get_active(&self) -> Option<DeviceClass>;
+    fn get_switch(&self) -> Option<&DeviceSwitch>;
+}
+
+impl IActiveDeviceTracker for ActiveDeviceTracker {
+    fn observe(&mut self, class: DeviceClass, change: &Change) {
+        let significant = match *change {
+            Change::Button(ButtonChange(pressed)) => pressed,
+            Change::Axis(AxisChange::Position(position)) => {
+                position.abs() >= self.activity_threshold
+            }
+            Change::Axis(AxisChange::Velocity(_)) => true,
+            Change::Axis(AxisChange::Falling(_)) => false,
+            Change::Value(_) => true,
+            Change::Pressure(PressureChange(pressure))
+            | Change::Trigger(TriggerChange(pressure)) => pressure >= self.activity_threshold,
+            Change::Axis2d(Axis2dChange { x, y }) => x.hypot(y) >= self.activity_threshold,
+        };
+        if !significant || self.active == Some(class) {
+            return;
+        }
+        let from = match self.pending_switch {
+            Some(ref pending) => pending.from,
+            None => self.active,
+        };
+        self.active = Some(class);
+        self.pending_switch = Some(DeviceSwitch { from, to: class });
+    }
+    fn tick(&mut self) {
+        self.switch = self.pending_switch.take();
+    }
+    fn get_active(&self) -> Option<DeviceClass> {
+        self.active
+    }
+    fn get_switch(&self) -> Option<&DeviceSwitch> {
+        self.switch.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_switch_for_one_tick() {
+        let mut tracker = ActiveDeviceTracker::default();
+        tracker.observe(DeviceClass::KeyboardMouse, &ButtonChange(true).into());
+        tracker.tick();
+        tracker.observe(DeviceClass::Gamepad(0), &ButtonChange(true).into());
+        assert_eq!(tracker.get_active(), Some(DeviceClass::Gamepad(0)));
+        tracker.tick();
+        assert_eq!(
+            tracker.get_switch(),
+            Some(&DeviceSwitch {
+                from: Some(DeviceClass::KeyboardMouse),
+                to: DeviceClass::Gamepad(0),
+            })
+        );
+        tracker.tick();
+        assert_eq!(tracker.get_switch(), None);
+    }
+
+    #[test]
+    fn ignores_releases_and_stick_noise() {
+        let mut tracker = ActiveDeviceTracker::default();
+        tracker.observe(DeviceClass::KeyboardMouse, &ButtonChange(true).into());
+        tracker.observe(DeviceClass::Gamepad(0), &AxisChange::Position(0.05).into());
+        tracker.observe(DeviceClass::Gamepad(1), &ButtonChange(false).into());
+        assert_eq!(tracker.get_active(), Some(DeviceClass::KeyboardMouse));
+    }
+}
@@ -0,0 +1,2 @@
+mod device;
+pub use self::device::*;
@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+/// `activity_threshold` is the position beyond which the stick is assumed to
+/// be deliberately moved, which restarts observation.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DriftConfig {
+    pub min_duration: Duration,
+    pub bias_threshold: f64,
+    pub activity_threshold: f64,
+}
+
+impl Default for DriftConfig {
+    fn default() -> Self {
+        DriftConfig {
+            min_duration: Duration::from_secs(2),
+            bias_threshold: 0.05,
+            activity_threshold: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DriftReport {
+    pub bias: f64,
+    pub suggested_dead_zone: f64,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DriftDetector {
+    config: DriftConfig,
+    elapsed: Duration,
+    weighted_sum: f64,
+    max_magnitude: f64,
+}
+
+pub trait IDriftDetector {
+    fn new(config: DriftConfig) -> Self
+    where
+        Self: Sized;
+    fn sample(&mut self, position: f64, delta: Duration);
+    fn reset(&mut self);
+    fn report(&self) -> Option<DriftReport>;
+}
+
+impl IDriftDetector for DriftDetector {
+    fn new(config: DriftConfig) -> Self {
+        DriftDetector {
+            config,
+            ..DriftDetector::default()
+        }
+    }
+    fn sample(&mut self, position: f64, delta: Duration) {
+        if position.abs() > self.config.activity_threshold {
+            self.reset();
+            return;
+        }
+        self.elapsed += delta;
+        self.weighted_sum += position * delta.as_secs_f64();
+        self.max_magnitude = self.max_magnitude.max(position.abs());
+    }
+    fn reset(&mut self) {
+        self.elapsed = Duration::default();
+        self.weighted_sum = 0.0;
+        self.max_magnitude = 0.0;
+    }
+    fn report(&self) -> Option<DriftReport> {
+        if self.elapsed < self.config.min_duration || self.elapsed == Duration::default() {
+            return None;
+        }
+        let bias = self.weighted_sum / self.elapsed.as_secs_f64();
+        if bias.abs() < self.config.bias_threshold {
+            return None;
+        }
+        Some(DriftReport {
+            bias,
+            suggested_dead_zone: (self.max_magnitude * 1.2).min(1.0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn reports_persistent_bias() {
+        let mut detector = DriftDetector::new(DriftConfig::default());
+        for _ in 0..30 {
+            detector.sample(0.1, Duration::from_millis(100));
+        }
+        let report = detector.report().unwrap();
+        assert!((report.bias - 0.1).abs() < 1e-9);
+        assert!((report.suggested_dead_zone - 0.12).abs() < 1e-9);
+    }
+    #[test]
+    fn ignores_noise_around_center() {
+        let mut detector = DriftDetector::new(DriftConfig::default());
+        for i in 0..30 {
+            let position = if i % 2 == 0 { 0.1 } else { -0.1 };
+            detector.sample(position, Duration::from_millis(100));
+        }
+        assert_eq!(detector.report(), None);
+    }
+    #[test]
+    fn deliberate_input_restarts_observation() {
+        let mut detector = DriftDetector::new(DriftConfig::default());
+        for _ in 0..30 {
+            detector.sample(0.1, Duration::from_millis(100));
+        }
+        detector.sample(0.9, Duration::from_millis(100));
+        assert_eq!(detector.report(), None);
+    }
+}
@@ -0,0 +1,2 @@
+mod drift;
+pub use self::drift::*;
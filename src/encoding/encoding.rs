@@ -0,0 +1,1076 @@
+use accumulator::Accumulator;
+#[cfg(feature = "audit")]
+use audit::{AuditFrame, AuditTrace};
+use axis::{
+    AxisActivity, AxisConfig, AxisQuantization, DeadZoneShape, InputAxisConfig, ResponseCurve,
+    SocdPolicy, TriggerZone, ZoneEvent,
+};
+use button::{ButtonConfig, ButtonEdge, ButtonRepeat};
+use change::{
+    Axis2dChange, AxisChange, ButtonChange, Direction, PressureChange, TriggerChange, ValueChange,
+};
+#[cfg(feature = "combo")]
+use combo::{Combo, ComboStep};
+#[cfg(feature = "recording")]
+use input_macro::{InputMacro, MacroStep};
+use manager::{Binding, Change, InputFrame};
+#[cfg(feature = "profile")]
+use profile::{BindingProfile, IBindingProfile, IPlayerProfiles, PlayerProfiles};
+use std::collections::VecDeque;
+#[cfg(feature = "profile")]
+use std::hash::Hash;
+use std::time::Duration;
+
+/// Writes values least-significant bit first into a byte buffer. Every
+/// encoded format in this crate goes through this writer, so the layout is
+/// identical regardless of host endianness or pointer width.
+#[derive(Default, Debug)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    bit: usize,
+}
+
+#[derive(Debug)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DecodeError {
+    UnexpectedEnd,
+    InvalidTag(u64),
+    InvalidUtf8,
+    /// A duration whose nanosecond part is a whole second or more.
+    InvalidDuration,
+    /// A custom response curve. Curves are function pointers, which can't be
+    /// encoded, so they have to be configured again after decoding.
+    CustomCurve,
+    /// Combo progress past the end of its combo.
+    InvalidComboProgress,
+}
+
+pub trait Encode {
+    fn encode(&self, writer: &mut BitWriter);
+}
+
+pub trait Decode: Sized {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError>;
+}
+
+impl BitWriter {
+    pub fn write_bits(&mut self, value: u64, count: u32) {
+        for i in 0..count {
+            if self.bit.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if (value >> i) & 1 == 1 {
+                let last = self.bytes.len() - 1;
+                self.bytes[last] |= 1 << (self.bit % 8);
+            }
+            self.bit += 1;
+        }
+    }
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        BitReader { bytes, bit: 0 }
+    }
+    pub fn read_bits(&mut self, count: u32) -> Result<u64, DecodeError> {
+        let mut value = 0;
+        for i in 0..count {
+            let byte = self
+                .bytes
+                .get(self.bit / 8)
+                .ok_or(DecodeError::UnexpectedEnd)?;
+            if (byte >> (self.bit % 8)) & 1 == 1 {
+                value |= 1 << i;
+            }
+            self.bit += 1;
+        }
+        Ok(value)
+    }
+}
+
+pub fn encode_to_vec<T: Encode>(value: &T) -> Vec<u8> {
+    let mut writer = BitWriter::default();
+    value.encode(&mut writer);
+    writer.into_bytes()
+}
+
+pub fn decode_from_slice<T: Decode>(bytes: &[u8]) -> Result<T, DecodeError> {
+    T::decode(&mut BitReader::new(bytes))
+}
+
+impl Encode for bool {
+    fn encode(&self, writer: &mut BitWriter) {
+        writer.write_bits(*self as u64, 1);
+    }
+}
+
+impl Decode for bool {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(reader.read_bits(1)? == 1)
+    }
+}
+
+macro_rules! encode_uint {
+    ($ty:ty, $bits:expr) => {
+        impl Encode for $ty {
+            fn encode(&self, writer: &mut BitWriter) {
+                writer.write_bits(u64::from(*self), $bits);
+            }
+        }
+        impl Decode for $ty {
+            fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+                Ok(reader.read_bits($bits)? as $ty)
+            }
+        }
+    };
+}
+
+encode_uint!(u8, 8);
+encode_uint!(u16, 16);
+encode_uint!(u32, 32);
+encode_uint!(u64, 64);
+
+impl Encode for i64 {
+    fn encode(&self, writer: &mut BitWriter) {
+        (*self as u64).encode(writer);
+    }
+}
+
+impl Decode for i64 {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(u64::decode(reader)? as i64)
+    }
+}
+
+impl Encode for f64 {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.to_bits().encode(writer);
+    }
+}
+
+impl Decode for f64 {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(f64::from_bits(u64::decode(reader)?))
+    }
+}
+
+impl Encode for Duration {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.as_secs().encode(writer);
+        writer.write_bits(u64::from(self.subsec_nanos()), 30);
+    }
+}
+
+impl Decode for Duration {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        let secs = u64::decode(reader)?;
+        let nanos = reader.read_bits(30)? as u32;
+        if nanos >= 1_000_000_000 {
+            return Err(DecodeError::InvalidDuration);
+        }
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.is_some().encode(writer);
+        if let Some(value) = self {
+            value.encode(writer);
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        if bool::decode(reader)? {
+            Ok(Some(T::decode(reader)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, writer: &mut BitWriter) {
+        (self.len() as u32).encode(writer);
+        for value in self {
+            value.encode(writer);
+        }
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        let len = u32::decode(reader)?;
+        (0..len).map(|_| T::decode(reader)).collect()
+    }
+}
+
+impl<T: Encode> Encode for VecDeque<T> {
+    fn encode(&self, writer: &mut BitWriter) {
+        (self.len() as u32).encode(writer);
+        for value in self {
+            value.encode(writer);
+        }
+    }
+}
+
+impl<T: Decode> Decode for VecDeque<T> {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        let len = u32::decode(reader)?;
+        (0..len).map(|_| T::decode(reader)).collect()
+    }
+}
+
+impl<T: Encode, U: Encode> Encode for (T, U) {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.0.encode(writer);
+        self.1.encode(writer);
+    }
+}
+
+impl<T: Decode, U: Decode> Decode for (T, U) {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok((T::decode(reader)?, U::decode(reader)?))
+    }
+}
+
+impl<T: Encode, U: Encode, V: Encode> Encode for (T, U, V) {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.0.encode(writer);
+        self.1.encode(writer);
+        self.2.encode(writer);
+    }
+}
+
+impl<T: Decode, U: Decode, V: Decode> Decode for (T, U, V) {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok((T::decode(reader)?, U::decode(reader)?, V::decode(reader)?))
+    }
+}
+
+impl Encode for String {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.as_bytes().to_vec().encode(writer);
+    }
+}
+
+impl Decode for String {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        String::from_utf8(Vec::decode(reader)?).map_err(|_| DecodeError::InvalidUtf8)
+    }
+}
+
+impl Encode for Direction {
+    fn encode(&self, writer: &mut BitWriter) {
+        (*self == Direction::Down).encode(writer);
+    }
+}
+
+impl Decode for Direction {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(if bool::decode(reader)? {
+            Direction::Down
+        } else {
+            Direction::Up
+        })
+    }
+}
+
+impl Encode for ButtonChange {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.0.encode(writer);
+    }
+}
+
+impl Decode for ButtonChange {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(ButtonChange(bool::decode(reader)?))
+    }
+}
+
+impl Encode for AxisChange {
+    fn encode(&self, writer: &mut BitWriter) {
+        match self {
+            AxisChange::Position(pos) => {
+                writer.write_bits(0, 2);
+                pos.encode(writer);
+            }
+            AxisChange::Velocity(dir) => {
+                writer.write_bits(1, 2);
+                dir.encode(writer);
+            }
+            AxisChange::Falling(dir) => {
+                writer.write_bits(2, 2);
+                dir.encode(writer);
+            }
+        }
+    }
+}
+
+impl Decode for AxisChange {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        match reader.read_bits(2)? {
+            0 => Ok(AxisChange::Position(f64::decode(reader)?)),
+            1 => Ok(AxisChange::Velocity(Direction::decode(reader)?)),
+            2 => Ok(AxisChange::Falling(Direction::decode(reader)?)),
+            tag => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+}
+
+impl Encode for ValueChange {
+    fn encode(&self, writer: &mut BitWriter) {
+        match self {
+            ValueChange::Set(value) => {
+                writer.write_bits(0, 1);
+                value.encode(writer);
+            }
+            ValueChange::Offset(offset) => {
+                writer.write_bits(1, 1);
+                offset.encode(writer);
+            }
+        }
+    }
+}
+
+impl Decode for ValueChange {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        match reader.read_bits(1)? {
+            0 => Ok(ValueChange::Set(f64::decode(reader)?)),
+            _ => Ok(ValueChange::Offset(f64::decode(reader)?)),
+        }
+    }
+}
+
+impl Encode for Change {
+    fn encode(&self, writer: &mut BitWriter) {
+        match self {
+            Change::Axis(change) => {
+                writer.write_bits(0, 3);
+                change.encode(writer);
+            }
+            Change::Button(change) => {
+                writer.write_bits(1, 3);
+                change.encode(writer);
+            }
+            Change::Value(change) => {
+                writer.write_bits(2, 3);
+                change.encode(writer);
+            }
+            Change::Pressure(change) => {
+                writer.write_bits(3, 3);
+                change.0.encode(writer);
+            }
+            Change::Trigger(change) => {
+                writer.write_bits(4, 3);
+                change.0.encode(writer);
+            }
+            Change::Axis2d(change) => {
+                writer.write_bits(5, 3);
+                change.x.encode(writer);
+                change.y.encode(writer);
+            }
+        }
+    }
+}
+
+impl Decode for Change {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        match reader.read_bits(3)? {
+            0 => Ok(Change::Axis(AxisChange::decode(reader)?)),
+            1 => Ok(Change::Button(ButtonChange::decode(reader)?)),
+            2 => Ok(Change::Value(ValueChange::decode(reader)?)),
+            3 => Ok(Change::Pressure(PressureChange(f64::decode(reader)?))),
+            4 => Ok(Change::Trigger(TriggerChange(f64::decode(reader)?))),
+            5 => Ok(Change::Axis2d(Axis2dChange {
+                x: f64::decode(reader)?,
+                y: f64::decode(reader)?,
+            })),
+            tag => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+}
+
+impl<A: Encode, B: Encode> Encode for Binding<A, B> {
+    fn encode(&self, writer: &mut BitWriter) {
+        match self {
+            Binding::Axis(axis) => {
+                writer.write_bits(0, 3);
+                axis.encode(writer);
+            }
+            Binding::Button(button) => {
+                writer.write_bits(1, 3);
+                button.encode(writer);
+            }
+            Binding::Value(value) => {
+                writer.write_bits(2, 3);
+                value.encode(writer);
+            }
+            Binding::Accumulator(accumulator) => {
+                writer.write_bits(3, 3);
+                accumulator.encode(writer);
+            }
+            Binding::Trigger(trigger) => {
+                writer.write_bits(4, 3);
+                trigger.encode(writer);
+            }
+            Binding::Axis2d(axis) => {
+                writer.write_bits(5, 3);
+                axis.encode(writer);
+            }
+        }
+    }
+}
+
+impl<A: Decode, B: Decode> Decode for Binding<A, B> {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        match reader.read_bits(3)? {
+            0 => Ok(Binding::Axis(A::decode(reader)?)),
+            1 => Ok(Binding::Button(B::decode(reader)?)),
+            2 => Ok(Binding::Value(A::decode(reader)?)),
+            3 => Ok(Binding::Accumulator(A::decode(reader)?)),
+            4 => Ok(Binding::Trigger(A::decode(reader)?)),
+            5 => Ok(Binding::Axis2d(A::decode(reader)?)),
+            tag => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+}
+
+impl<A: Encode, B: Encode> Encode for InputFrame<A, B> {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.changes.encode(writer);
+        self.delta.encode(writer);
+    }
+}
+
+impl<A: Decode, B: Decode> Decode for InputFrame<A, B> {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(InputFrame {
+            changes: Vec::decode(reader)?,
+            delta: Duration::decode(reader)?,
+        })
+    }
+}
+
+#[cfg(feature = "recording")]
+impl<A: Encode, B: Encode> Encode for MacroStep<A, B> {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.at.encode(writer);
+        self.binding.encode(writer);
+        self.change.encode(writer);
+    }
+}
+
+#[cfg(feature = "recording")]
+impl<A: Decode, B: Decode> Decode for MacroStep<A, B> {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(MacroStep {
+            at: Duration::decode(reader)?,
+            binding: Binding::decode(reader)?,
+            change: Change::decode(reader)?,
+        })
+    }
+}
+
+#[cfg(feature = "recording")]
+impl<A: Encode, B: Encode> Encode for InputMacro<A, B> {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.steps.encode(writer);
+    }
+}
+
+#[cfg(feature = "recording")]
+impl<A: Decode, B: Decode> Decode for InputMacro<A, B> {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(InputMacro {
+            steps: Vec::decode(reader)?,
+        })
+    }
+}
+
+impl Encode for SocdPolicy {
+    fn encode(&self, writer: &mut BitWriter) {
+        let tag = match self {
+            SocdPolicy::LastInput => 0,
+            SocdPolicy::FirstInput => 1,
+            SocdPolicy::Neutral => 2,
+        };
+        writer.write_bits(tag, 2);
+    }
+}
+
+impl Decode for SocdPolicy {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        match reader.read_bits(2)? {
+            0 => Ok(SocdPolicy::LastInput),
+            1 => Ok(SocdPolicy::FirstInput),
+            2 => Ok(SocdPolicy::Neutral),
+            tag => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+}
+
+/// Custom curves are written as a bare tag and fail to decode.
+impl Encode for ResponseCurve {
+    fn encode(&self, writer: &mut BitWriter) {
+        match self {
+            ResponseCurve::Linear => writer.write_bits(0, 2),
+            ResponseCurve::Power(exponent) => {
+                writer.write_bits(1, 2);
+                exponent.encode(writer);
+            }
+            ResponseCurve::Custom(_) => writer.write_bits(2, 2),
+        }
+    }
+}
+
+impl Decode for ResponseCurve {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        match reader.read_bits(2)? {
+            0 => Ok(ResponseCurve::Linear),
+            1 => Ok(ResponseCurve::Power(f64::decode(reader)?)),
+            2 => Err(DecodeError::CustomCurve),
+            tag => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+}
+
+impl Encode for AxisQuantization {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.steps.encode(writer);
+        self.hysteresis.encode(writer);
+    }
+}
+
+impl Decode for AxisQuantization {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(AxisQuantization {
+            steps: u32::decode(reader)?,
+            hysteresis: f64::decode(reader)?,
+        })
+    }
+}
+
+impl Encode for TriggerZone {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.name.encode(writer);
+        self.threshold.encode(writer);
+    }
+}
+
+impl Decode for TriggerZone {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(TriggerZone {
+            name: String::decode(reader)?,
+            threshold: f64::decode(reader)?,
+        })
+    }
+}
+
+impl Encode for AxisConfig {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.socd.encode(writer);
+        self.dead_zone.encode(writer);
+        self.zones.encode(writer);
+        self.max_speed.encode(writer);
+        self.gravity.encode(writer);
+        self.acceleration.encode(writer);
+        self.deceleration.encode(writer);
+        self.quantization.encode(writer);
+        self.response.encode(writer);
+    }
+}
+
+impl Decode for AxisConfig {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(AxisConfig {
+            socd: SocdPolicy::decode(reader)?,
+            dead_zone: f64::decode(reader)?,
+            zones: Vec::decode(reader)?,
+            max_speed: Option::decode(reader)?,
+            gravity: Option::decode(reader)?,
+            acceleration: Option::decode(reader)?,
+            deceleration: Option::decode(reader)?,
+            quantization: Option::decode(reader)?,
+            response: ResponseCurve::decode(reader)?,
+        })
+    }
+}
+
+impl Encode for ZoneEvent {
+    fn encode(&self, writer: &mut BitWriter) {
+        match self {
+            ZoneEvent::Enter(zone) => {
+                writer.write_bits(0, 1);
+                zone.encode(writer);
+            }
+            ZoneEvent::Exit(zone) => {
+                writer.write_bits(1, 1);
+                zone.encode(writer);
+            }
+        }
+    }
+}
+
+impl Decode for ZoneEvent {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        match reader.read_bits(1)? {
+            0 => Ok(ZoneEvent::Enter(String::decode(reader)?)),
+            _ => Ok(ZoneEvent::Exit(String::decode(reader)?)),
+        }
+    }
+}
+
+impl Encode for AxisActivity {
+    fn encode(&self, writer: &mut BitWriter) {
+        (*self == AxisActivity::Stopped).encode(writer);
+    }
+}
+
+impl Decode for AxisActivity {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(if bool::decode(reader)? {
+            AxisActivity::Stopped
+        } else {
+            AxisActivity::Started
+        })
+    }
+}
+
+impl Encode for DeadZoneShape {
+    fn encode(&self, writer: &mut BitWriter) {
+        let tag = match self {
+            DeadZoneShape::Radial => 0,
+            DeadZoneShape::Axial => 1,
+            DeadZoneShape::Cross => 2,
+            DeadZoneShape::Bowtie => 3,
+        };
+        writer.write_bits(tag, 2);
+    }
+}
+
+impl Decode for DeadZoneShape {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        match reader.read_bits(2)? {
+            0 => Ok(DeadZoneShape::Radial),
+            1 => Ok(DeadZoneShape::Axial),
+            2 => Ok(DeadZoneShape::Cross),
+            _ => Ok(DeadZoneShape::Bowtie),
+        }
+    }
+}
+
+impl Encode for InputAxisConfig {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.dead_zone.encode(writer);
+        self.saturation.encode(writer);
+        self.curve.encode(writer);
+        self.sensitivity.encode(writer);
+        self.anti_dead_zone.encode(writer);
+        self.output_min.encode(writer);
+        self.dead_zone_shape.encode(writer);
+    }
+}
+
+impl Decode for InputAxisConfig {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(InputAxisConfig {
+            dead_zone: f64::decode(reader)?,
+            saturation: f64::decode(reader)?,
+            curve: f64::decode(reader)?,
+            sensitivity: f64::decode(reader)?,
+            anti_dead_zone: f64::decode(reader)?,
+            output_min: f64::decode(reader)?,
+            dead_zone_shape: DeadZoneShape::decode(reader)?,
+        })
+    }
+}
+
+impl Encode for ButtonEdge {
+    fn encode(&self, writer: &mut BitWriter) {
+        (*self == ButtonEdge::Release).encode(writer);
+    }
+}
+
+impl Decode for ButtonEdge {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(if bool::decode(reader)? {
+            ButtonEdge::Release
+        } else {
+            ButtonEdge::Press
+        })
+    }
+}
+
+impl Encode for ButtonRepeat {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.delay.encode(writer);
+        self.interval.encode(writer);
+    }
+}
+
+impl Decode for ButtonRepeat {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(ButtonRepeat {
+            delay: Duration::decode(reader)?,
+            interval: Duration::decode(reader)?,
+        })
+    }
+}
+
+impl Encode for ButtonConfig {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.debounce.encode(writer);
+        self.slow_keys.encode(writer);
+        self.turbo.encode(writer);
+        self.edge.encode(writer);
+        self.cooldown.encode(writer);
+        self.max_rate.encode(writer);
+        self.repeat.encode(writer);
+        self.actuation.encode(writer);
+    }
+}
+
+impl Decode for ButtonConfig {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(ButtonConfig {
+            debounce: Option::decode(reader)?,
+            slow_keys: Option::decode(reader)?,
+            turbo: Option::decode(reader)?,
+            edge: ButtonEdge::decode(reader)?,
+            cooldown: Option::decode(reader)?,
+            max_rate: Option::decode(reader)?,
+            repeat: Option::decode(reader)?,
+            actuation: Option::decode(reader)?,
+        })
+    }
+}
+
+impl Encode for Accumulator {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.total.encode(writer);
+    }
+}
+
+impl Decode for Accumulator {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(Accumulator {
+            total: f64::decode(reader)?,
+        })
+    }
+}
+
+#[cfg(feature = "combo")]
+impl<A: Encode, B: Encode> Encode for ComboStep<A, B> {
+    fn encode(&self, writer: &mut BitWriter) {
+        match self {
+            ComboStep::Button(button) => {
+                writer.write_bits(0, 1);
+                button.encode(writer);
+            }
+            ComboStep::Axis(axis, direction) => {
+                writer.write_bits(1, 1);
+                axis.encode(writer);
+                direction.encode(writer);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "combo")]
+impl<A: Decode, B: Decode> Decode for ComboStep<A, B> {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        match reader.read_bits(1)? {
+            0 => Ok(ComboStep::Button(B::decode(reader)?)),
+            _ => Ok(ComboStep::Axis(
+                A::decode(reader)?,
+                Direction::decode(reader)?,
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "combo")]
+impl<A: Encode, B: Encode> Encode for Combo<A, B> {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.steps.encode(writer);
+        self.window.encode(writer);
+    }
+}
+
+#[cfg(feature = "combo")]
+impl<A: Decode, B: Decode> Decode for Combo<A, B> {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(Combo {
+            steps: Vec::decode(reader)?,
+            window: Duration::decode(reader)?,
+        })
+    }
+}
+
+#[cfg(feature = "audit")]
+impl Encode for AuditTrace {
+    fn encode(&self, writer: &mut BitWriter) {
+        (self.frames.len() as u32).encode(writer);
+        for frame in self.frames.iter() {
+            frame.changes.encode(writer);
+            frame.checksum.encode(writer);
+        }
+    }
+}
+
+#[cfg(feature = "audit")]
+impl Decode for AuditTrace {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        let len = u32::decode(reader)?;
+        let frames = (0..len)
+            .map(|_| {
+                Ok(AuditFrame {
+                    changes: Vec::decode(reader)?,
+                    checksum: u64::decode(reader)?,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(AuditTrace { frames })
+    }
+}
+
+/// The shared bindings followed by one named section of overrides per
+/// player, where a missing binding marks an input the player unbound.
+/// Bindings are written sorted by their encoded input so the same profiles
+/// always produce the same bytes.
+#[cfg(feature = "profile")]
+impl<A, B, C> Encode for PlayerProfiles<A, B, C>
+where
+    A: Hash + Eq + Clone + Encode,
+    B: Hash + Eq + Clone + Encode,
+    C: Hash + Eq + Clone + Encode,
+{
+    fn encode(&self, writer: &mut BitWriter) {
+        let mut shared: Vec<_> = self.shared.get_bindings().into_iter().collect();
+        shared.sort_by_cached_key(|(input, _)| encode_to_vec(input));
+        shared.encode(writer);
+        (self.players.len() as u32).encode(writer);
+        for (name, profile) in self.players.iter() {
+            name.encode(writer);
+            let mut overrides: Vec<_> = profile
+                .get_overrides()
+                .iter()
+                .map(|(input, binding)| (input.clone(), binding.clone()))
+                .collect();
+            overrides.sort_by_cached_key(|(input, _)| encode_to_vec(input));
+            overrides.encode(writer);
+        }
+    }
+}
+
+#[cfg(feature = "profile")]
+impl<A, B, C> Decode for PlayerProfiles<A, B, C>
+where
+    A: Hash + Eq + Clone + Decode,
+    B: Hash + Eq + Clone + Decode,
+    C: Hash + Eq + Clone + Decode,
+{
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        let shared: Vec<(C, Binding<A, B>)> = Vec::decode(reader)?;
+        let mut profiles =
+            PlayerProfiles::new(BindingProfile::from_bindings(shared.into_iter().collect()));
+        let len = u32::decode(reader)?;
+        for _ in 0..len {
+            let name = String::decode(reader)?;
+            let overrides: Vec<(C, Option<Binding<A, B>>)> = Vec::decode(reader)?;
+            let profile = profiles.player_mut(&name);
+            for (input, binding) in overrides {
+                match binding {
+                    Some(binding) => profile.add_binding(binding, input),
+                    None => profile.remove_binding(input),
+                }
+            }
+        }
+        Ok(profiles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn bits_are_packed_lsb_first() {
+        let mut writer = BitWriter::default();
+        true.encode(&mut writer);
+        Direction::Down.encode(&mut writer);
+        writer.write_bits(0b101, 3);
+        assert_eq!(writer.into_bytes(), vec![0b10111]);
+    }
+    #[test]
+    fn integers_are_little_endian() {
+        assert_eq!(encode_to_vec(&0x0102_0304u32), vec![4, 3, 2, 1]);
+    }
+    #[test]
+    fn frames_round_trip() {
+        let frames: Vec<InputFrame<u8, u8>> = vec![InputFrame {
+            changes: vec![
+                (Binding::Axis(1), AxisChange::Position(-0.25).into()),
+                (Binding::Axis(1), AxisChange::Falling(Direction::Up).into()),
+                (Binding::Button(7), ButtonChange(true).into()),
+                (Binding::Button(7), PressureChange(0.5).into()),
+                (Binding::Trigger(3), TriggerChange(0.25).into()),
+                (Binding::Axis2d(4), Axis2dChange { x: 0.5, y: -1.0 }.into()),
+                (Binding::Value(2), ValueChange::Offset(1.5).into()),
+            ],
+            delta: Duration::new(1, 16_000_000),
+        }];
+        let bytes = encode_to_vec(&frames);
+        assert_eq!(decode_from_slice(&bytes), Ok(frames));
+    }
+    #[test]
+    #[cfg(feature = "recording")]
+    fn macros_round_trip() {
+        let input_macro: InputMacro<u8, u16> = InputMacro {
+            steps: vec![MacroStep {
+                at: Duration::from_millis(120),
+                binding: Binding::Button(300),
+                change: ButtonChange(false).into(),
+            }],
+        };
+        let bytes = encode_to_vec(&input_macro);
+        let decoded: InputMacro<u8, u16> = decode_from_slice(&bytes).unwrap();
+        assert_eq!(decoded.steps[0].at, Duration::from_millis(120));
+        assert_eq!(decoded.steps[0].binding, Binding::Button(300));
+    }
+    #[test]
+    #[cfg(feature = "audit")]
+    fn audit_traces_round_trip() {
+        let trace = AuditTrace {
+            frames: vec![AuditFrame {
+                changes: vec![1, 2],
+                checksum: 0xdead_beef,
+            }],
+        };
+        assert_eq!(decode_from_slice(&encode_to_vec(&trace)), Ok(trace));
+    }
+    #[test]
+    fn truncated_input_errors() {
+        let bytes = encode_to_vec(&0xffffu16);
+        assert_eq!(
+            decode_from_slice::<u32>(&bytes),
+            Err(DecodeError::UnexpectedEnd)
+        );
+    }
+
+    #[test]
+    fn oversized_nanos_error() {
+        let mut writer = BitWriter::default();
+        1u64.encode(&mut writer);
+        writer.write_bits(1_000_000_000, 30);
+        assert_eq!(
+            decode_from_slice::<Duration>(&writer.into_bytes()),
+            Err(DecodeError::InvalidDuration)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "combo")]
+    fn combo_progress_past_the_end_errors() {
+        use combo::ComboRegistry;
+        let mut writer = BitWriter::default();
+        1u32.encode(&mut writer);
+        "Dash".to_string().encode(&mut writer);
+        Combo {
+            steps: vec![ComboStep::<u8, u8>::Button(1)],
+            window: Duration::from_millis(200),
+        }
+        .encode(&mut writer);
+        2u32.encode(&mut writer);
+        Duration::default().encode(&mut writer);
+        Vec::<String>::new().encode(&mut writer);
+        Vec::<String>::new().encode(&mut writer);
+        assert!(matches!(
+            decode_from_slice::<ComboRegistry<u8, u8>>(&writer.into_bytes()),
+            Err(DecodeError::InvalidComboProgress)
+        ));
+    }
+
+    #[test]
+    fn snapshots_round_trip() {
+        #[cfg(feature = "combo")]
+        use combo::ComboStep;
+        use manager::{IConverter, IManager, IRollback, IUpdater, Manager, Snapshot};
+        let mut manager: Manager<u8, u8, u8> = Manager::new();
+        manager.add_button_binding(1, 10);
+        manager.add_axis_binding(2, 11);
+        manager.configure_axis(
+            2,
+            AxisConfig {
+                response: ResponseCurve::Power(2.0),
+                ..AxisConfig::default()
+            },
+        );
+        #[cfg(feature = "combo")]
+        manager.add_combo(
+            "Dash".to_string(),
+            Combo {
+                steps: vec![ComboStep::Button(1), ComboStep::Button(1)],
+                window: Duration::from_millis(200),
+            },
+        );
+        manager.process_input(10, ButtonChange(true)).unwrap();
+        manager
+            .process_input(11, AxisChange::Position(0.5))
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        let snapshot = manager.snapshot();
+        let bytes = encode_to_vec(&snapshot);
+        let decoded: Snapshot<u8, u8> = decode_from_slice(&bytes).unwrap();
+        assert_eq!(encode_to_vec(&decoded), bytes);
+
+        manager.process_input(10, ButtonChange(false)).unwrap();
+        manager.tick(Duration::from_millis(16));
+        manager.rewind_to(&decoded);
+        assert!(manager.get_button(1).unwrap().pressed);
+        assert_eq!(manager.get_axis_output(2), 0.25);
+    }
+
+    #[test]
+    fn custom_curves_fail_to_decode() {
+        let config = AxisConfig {
+            response: ResponseCurve::Custom(|position| position),
+            ..AxisConfig::default()
+        };
+        assert_eq!(
+            decode_from_slice::<AxisConfig>(&encode_to_vec(&config)),
+            Err(DecodeError::CustomCurve)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "profile")]
+    fn player_profiles_round_trip() {
+        let mut shared: BindingProfile<u8, u8, u8> = BindingProfile::new();
+        shared.add_button_binding(1, 10);
+        shared.add_axis_binding(2, 11);
+        let mut profiles = PlayerProfiles::new(shared);
+        profiles.player_mut("p1").add_button_binding(1, 12);
+        profiles.player_mut("p1").remove_binding(10);
+        profiles.player_mut("p2").add_binding(Binding::Value(3), 13);
+        let bytes = encode_to_vec(&profiles);
+        let decoded: PlayerProfiles<u8, u8, u8> = decode_from_slice(&bytes).unwrap();
+        assert_eq!(decoded, profiles);
+        assert_eq!(encode_to_vec(&decoded), bytes);
+        assert_eq!(decoded.profile_for("p1").get_binding(&10), None);
+        assert_eq!(
+            decoded.profile_for("p3").get_binding(&10),
+            Some(&Binding::Button(1))
+        );
+    }
+}
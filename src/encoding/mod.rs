@@ -0,0 +1,2 @@
+mod encoding;
+pub use self::encoding::*;
@@ -0,0 +1,112 @@
+use manager::{Binding, Change};
+use std::fmt::Debug;
+use std::io::{self, Write};
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum LoggedEvent<'a, A, B> {
+    Change(&'a Binding<A, B>, &'a Change),
+    Fired(&'a B),
+    Combo(&'a str),
+    LatencyExceeded(&'a Binding<A, B>, Duration),
+}
+
+/// Receives every applied change and fired event as it happens.
+pub trait IEventLog<A, B> {
+    fn log(&mut self, frame: u64, event: LoggedEvent<A, B>);
+}
+
+/// Writes one JSON object per line. Actions and changes are written with
+/// their `Debug` representation, so this is meant for tools and bug reports
+/// rather than for replaying.
+pub struct JsonLinesExporter<W: Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> JsonLinesExporter<W> {
+    pub fn new(writer: W) -> Self {
+        JsonLinesExporter {
+            writer,
+            error: None,
+        }
+    }
+    /// The first write error, after which nothing more is written.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+impl<A: Debug, B: Debug, W: Write> IEventLog<A, B> for JsonLinesExporter<W> {
+    fn log(&mut self, frame: u64, event: LoggedEvent<A, B>) {
+        if self.error.is_some() {
+            return;
+        }
+        let line = match event {
+            LoggedEvent::Change(binding, change) => format!(
+                "{{\"frame\":{},\"type\":\"change\",\"action\":{},\"change\":{}}}",
+                frame,
+                json_string(&format!("{:?}", binding)),
+                json_string(&format!("{:?}", change))
+            ),
+            LoggedEvent::Fired(button) => format!(
+                "{{\"frame\":{},\"type\":\"fired\",\"action\":{}}}",
+                frame,
+                json_string(&format!("{:?}", button))
+            ),
+            LoggedEvent::Combo(name) => format!(
+                "{{\"frame\":{},\"type\":\"combo\",\"name\":{}}}",
+                frame,
+                json_string(name)
+            ),
+            LoggedEvent::LatencyExceeded(binding, latency) => format!(
+                "{{\"frame\":{},\"type\":\"latency\",\"action\":{},\"latency_ms\":{}}}",
+                frame,
+                json_string(&format!("{:?}", binding)),
+                latency.as_secs_f64() * 1000.0
+            ),
+        };
+        if let Err(error) = writeln!(self.writer, "{}", line) {
+            self.error = Some(error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use change::ButtonChange;
+    #[test]
+    fn writes_one_object_per_line() {
+        let mut exporter = JsonLinesExporter::new(Vec::new());
+        let binding: Binding<&str, &str> = Binding::Button("jump");
+        let change: Change = ButtonChange(true).into();
+        exporter.log(0, LoggedEvent::Change(&binding, &change));
+        IEventLog::<&str, &str>::log(&mut exporter, 1, LoggedEvent::Combo("say \"hi\""));
+        let written = String::from_utf8(exporter.into_inner()).unwrap();
+        assert_eq!(
+            written,
+            "{\"frame\":0,\"type\":\"change\",\"action\":\"Button(\\\"jump\\\")\",\"change\":\"Button(ButtonChange(true))\"}\n\
+             {\"frame\":1,\"type\":\"combo\",\"name\":\"say \\\"hi\\\"\"}\n"
+        );
+    }
+}
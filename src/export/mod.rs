@@ -0,0 +1,2 @@
+mod export;
+pub use self::export::*;
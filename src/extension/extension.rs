@@ -0,0 +1,145 @@
+use manager::Change;
+use polling::InputSource;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ExtensionInputKind {
+    Axis,
+    Button,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExtensionInput<C> {
+    pub input: C,
+    pub name: String,
+    pub kind: ExtensionInputKind,
+}
+
+/// A source for controller-specific inputs (touchpads, motion, paddles) that
+/// also describes the extra inputs it produces so they can be bound.
+pub trait ControllerExtension<C>: InputSource<C> {
+    fn name(&self) -> &str;
+    fn inputs(&self) -> Vec<ExtensionInput<C>>;
+}
+
+/// Collects extensions; it is itself an `InputSource` so it can be handed to
+/// the poller as a single source.
+pub struct ExtensionRegistry<C> {
+    extensions: Vec<Box<dyn ControllerExtension<C>>>,
+}
+
+pub trait IExtensionRegistry<C> {
+    fn new() -> Self
+    where
+        Self: Sized;
+    fn add_extension(&mut self, extension: Box<dyn ControllerExtension<C>>);
+    fn get_inputs(&self) -> Vec<ExtensionInput<C>>;
+    fn find_input(&self, extension: &str, name: &str) -> Option<ExtensionInput<C>>;
+}
+
+impl<C> IExtensionRegistry<C> for ExtensionRegistry<C> {
+    fn new() -> Self {
+        ExtensionRegistry {
+            extensions: Vec::new(),
+        }
+    }
+    fn add_extension(&mut self, extension: Box<dyn ControllerExtension<C>>) {
+        self.extensions.push(extension);
+    }
+    fn get_inputs(&self) -> Vec<ExtensionInput<C>> {
+        self.extensions
+            .iter()
+            .flat_map(|extension| extension.inputs())
+            .collect()
+    }
+    fn find_input(&self, extension: &str, name: &str) -> Option<ExtensionInput<C>> {
+        self.extensions
+            .iter()
+            .filter(|e| e.name() == extension)
+            .flat_map(|e| e.inputs())
+            .find(|input| input.name == name)
+    }
+}
+
+impl<C> InputSource<C> for ExtensionRegistry<C> {
+    fn poll(&mut self) -> Vec<(C, Change)> {
+        self.extensions
+            .iter_mut()
+            .flat_map(|extension| extension.poll())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use change::{AxisChange, ButtonChange};
+
+    #[derive(Debug, PartialEq, Clone)]
+    enum Input {
+        TouchX,
+        Paddle,
+    }
+
+    struct Touchpad;
+    impl InputSource<Input> for Touchpad {
+        fn poll(&mut self) -> Vec<(Input, Change)> {
+            vec![(Input::TouchX, AxisChange::Position(0.5).into())]
+        }
+    }
+    impl ControllerExtension<Input> for Touchpad {
+        fn name(&self) -> &str {
+            "touchpad"
+        }
+        fn inputs(&self) -> Vec<ExtensionInput<Input>> {
+            vec![ExtensionInput {
+                input: Input::TouchX,
+                name: "x".to_string(),
+                kind: ExtensionInputKind::Axis,
+            }]
+        }
+    }
+
+    struct Paddles;
+    impl InputSource<Input> for Paddles {
+        fn poll(&mut self) -> Vec<(Input, Change)> {
+            vec![(Input::Paddle, ButtonChange(true).into())]
+        }
+    }
+    impl ControllerExtension<Input> for Paddles {
+        fn name(&self) -> &str {
+            "paddles"
+        }
+        fn inputs(&self) -> Vec<ExtensionInput<Input>> {
+            vec![ExtensionInput {
+                input: Input::Paddle,
+                name: "p1".to_string(),
+                kind: ExtensionInputKind::Button,
+            }]
+        }
+    }
+
+    #[test]
+    fn lists_and_finds_extension_inputs() {
+        let mut registry = ExtensionRegistry::new();
+        registry.add_extension(Box::new(Touchpad));
+        registry.add_extension(Box::new(Paddles));
+        assert_eq!(registry.get_inputs().len(), 2);
+        let paddle = registry.find_input("paddles", "p1").unwrap();
+        assert_eq!(paddle.input, Input::Paddle);
+        assert_eq!(paddle.kind, ExtensionInputKind::Button);
+        assert_eq!(registry.find_input("touchpad", "p1"), None);
+    }
+
+    #[test]
+    fn polls_every_extension() {
+        let mut registry = ExtensionRegistry::new();
+        registry.add_extension(Box::new(Touchpad));
+        registry.add_extension(Box::new(Paddles));
+        let polled: Vec<Input> = registry
+            .poll()
+            .into_iter()
+            .map(|(input, _)| input)
+            .collect();
+        assert_eq!(polled, vec![Input::TouchX, Input::Paddle]);
+    }
+}
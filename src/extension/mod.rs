@@ -0,0 +1,2 @@
+mod extension;
+pub use self::extension::*;
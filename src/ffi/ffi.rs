@@ -0,0 +1,236 @@
+use change::{AxisChange, ButtonChange, Direction};
+use manager::*;
+use std::time::Duration;
+
+/// Manager with integer ids for axes, buttons and raw inputs, as exposed to C.
+/// Mirrored by `include/input_manager.h`. Build the C library with
+/// `cargo rustc --release --features ffi --crate-type staticlib` (or `cdylib`).
+pub type FfiManager = Manager<u32, u32, u32>;
+
+#[no_mangle]
+pub extern "C" fn input_manager_new() -> *mut FfiManager {
+    Box::into_raw(Box::new(FfiManager::new()))
+}
+
+/// # Safety
+/// `manager` must come from `input_manager_new` and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn input_manager_free(manager: *mut FfiManager) {
+    if !manager.is_null() {
+        drop(Box::from_raw(manager));
+    }
+}
+
+/// # Safety
+/// `manager` must be a live pointer from `input_manager_new`.
+#[no_mangle]
+pub unsafe extern "C" fn input_manager_add_axis_binding(
+    manager: *mut FfiManager,
+    axis: u32,
+    input: u32,
+) {
+    if let Some(manager) = manager.as_mut() {
+        manager.add_axis_binding(axis, input);
+    }
+}
+
+/// # Safety
+/// `manager` must be a live pointer from `input_manager_new`.
+#[no_mangle]
+pub unsafe extern "C" fn input_manager_add_button_binding(
+    manager: *mut FfiManager,
+    button: u32,
+    input: u32,
+) {
+    if let Some(manager) = manager.as_mut() {
+        manager.add_button_binding(button, input);
+    }
+}
+
+/// # Safety
+/// `manager` must be a live pointer from `input_manager_new`.
+#[no_mangle]
+pub unsafe extern "C" fn input_manager_apply_button(
+    manager: *mut FfiManager,
+    button: u32,
+    pressed: bool,
+) {
+    if let Some(manager) = manager.as_mut() {
+        let _ = manager.apply_change(&Binding::Button(button), ButtonChange(pressed).into());
+    }
+}
+
+/// # Safety
+/// `manager` must be a live pointer from `input_manager_new`.
+#[no_mangle]
+pub unsafe extern "C" fn input_manager_apply_axis_position(
+    manager: *mut FfiManager,
+    axis: u32,
+    position: f64,
+) {
+    if let Some(manager) = manager.as_mut() {
+        let _ = manager.apply_change(&Binding::Axis(axis), AxisChange::Position(position).into());
+    }
+}
+
+/// Routes a raw button input through its binding. Returns false when the
+/// input is unbound or bound to a non-button action.
+///
+/// # Safety
+/// `manager` must be a live pointer from `input_manager_new`.
+#[no_mangle]
+pub unsafe extern "C" fn input_manager_process_button(
+    manager: *mut FfiManager,
+    input: u32,
+    pressed: bool,
+) -> bool {
+    manager.as_mut().is_some_and(|manager| {
+        manager
+            .process_input(input, ButtonChange(pressed))
+            .unwrap_or(false)
+    })
+}
+
+/// Routes a raw analog input through its binding. Returns false when the
+/// input is unbound.
+///
+/// # Safety
+/// `manager` must be a live pointer from `input_manager_new`.
+#[no_mangle]
+pub unsafe extern "C" fn input_manager_process_axis(
+    manager: *mut FfiManager,
+    input: u32,
+    position: f64,
+) -> bool {
+    manager.as_mut().is_some_and(|manager| {
+        manager
+            .process_input(input, AxisChange::Position(position))
+            .unwrap_or(false)
+    })
+}
+
+/// `direction` is positive for up and negative for down; `held` false
+/// releases that direction.
+///
+/// # Safety
+/// `manager` must be a live pointer from `input_manager_new`.
+#[no_mangle]
+pub unsafe extern "C" fn input_manager_apply_axis_direction(
+    manager: *mut FfiManager,
+    axis: u32,
+    direction: i32,
+    held: bool,
+) {
+    let direction = if direction >= 0 {
+        Direction::Up
+    } else {
+        Direction::Down
+    };
+    let change = if held {
+        AxisChange::Velocity(direction)
+    } else {
+        AxisChange::Falling(direction)
+    };
+    if let Some(manager) = manager.as_mut() {
+        let _ = manager.apply_change(&Binding::Axis(axis), change.into());
+    }
+}
+
+/// Negative, non-finite or out-of-range deltas tick by zero.
+///
+/// # Safety
+/// `manager` must be a live pointer from `input_manager_new`.
+#[no_mangle]
+pub unsafe extern "C" fn input_manager_tick(manager: *mut FfiManager, delta_seconds: f64) {
+    if let Some(manager) = manager.as_mut() {
+        manager.tick(Duration::try_from_secs_f64(delta_seconds).unwrap_or_default());
+    }
+}
+
+/// Returns 0 for unknown axes.
+///
+/// # Safety
+/// `manager` must be a live pointer from `input_manager_new`.
+#[no_mangle]
+pub unsafe extern "C" fn input_manager_get_axis(manager: *const FfiManager, axis: u32) -> f64 {
+    manager
+        .as_ref()
+        .and_then(|manager| manager.get_axis(axis))
+        .map_or(0.0, |axis| axis.position)
+}
+
+/// # Safety
+/// `manager` must be a live pointer from `input_manager_new`.
+#[no_mangle]
+pub unsafe extern "C" fn input_manager_get_button(manager: *const FfiManager, button: u32) -> bool {
+    manager
+        .as_ref()
+        .and_then(|manager| manager.get_button(button))
+        .is_some_and(|button| button.pressed)
+}
+
+/// True only on the frame the button fired.
+///
+/// # Safety
+/// `manager` must be a live pointer from `input_manager_new`.
+#[no_mangle]
+pub unsafe extern "C" fn input_manager_get_button_pressed(
+    manager: *const FfiManager,
+    button: u32,
+) -> bool {
+    manager
+        .as_ref()
+        .is_some_and(|manager| manager.get_button_pressed(button))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drives_manager_through_c_api() {
+        unsafe {
+            let manager = input_manager_new();
+            input_manager_add_button_binding(manager, 1, 100);
+            input_manager_add_axis_binding(manager, 2, 200);
+            assert!(input_manager_process_button(manager, 100, true));
+            assert!(!input_manager_process_button(manager, 101, true));
+            assert!(input_manager_get_button_pressed(manager, 1));
+            assert!(input_manager_process_axis(manager, 200, 0.25));
+            input_manager_tick(manager, 0.016);
+            assert!(input_manager_get_button(manager, 1));
+            assert!(!input_manager_get_button_pressed(manager, 1));
+            assert_eq!(input_manager_get_axis(manager, 2), 0.25);
+            assert_eq!(input_manager_get_axis(manager, 3), 0.0);
+            input_manager_free(manager);
+        }
+    }
+
+    #[test]
+    fn applies_actions_directly() {
+        unsafe {
+            let manager = input_manager_new();
+            input_manager_add_button_binding(manager, 1, 100);
+            input_manager_add_axis_binding(manager, 2, 200);
+            input_manager_apply_button(manager, 1, true);
+            assert!(input_manager_get_button_pressed(manager, 1));
+            input_manager_apply_axis_position(manager, 2, 0.25);
+            assert_eq!(input_manager_get_axis(manager, 2), 0.25);
+            input_manager_free(manager);
+        }
+    }
+
+    #[test]
+    fn non_finite_tick_is_ignored() {
+        unsafe {
+            let manager = input_manager_new();
+            input_manager_add_button_binding(manager, 1, 100);
+            input_manager_process_button(manager, 100, true);
+            input_manager_tick(manager, f64::INFINITY);
+            input_manager_tick(manager, f64::NAN);
+            input_manager_tick(manager, -1.0);
+            assert!(input_manager_get_button(manager, 1));
+            input_manager_free(manager);
+        }
+    }
+}
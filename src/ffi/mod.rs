@@ -0,0 +1,2 @@
+mod ffi;
+pub use self::ffi::*;
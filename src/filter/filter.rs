@@ -0,0 +1,80 @@
+use manager::{Binding, Change, State};
+
+/// Sees each change on its way to an action and returns what to apply in its
+/// place: return it unchanged to pass it on, a modified pair to rewrite it or
+/// `None` to drop it. Anti-cheat checks, accessibility transforms and mods
+/// plug in by implementing this.
+pub trait ChangeFilter<A, B> {
+    fn filter(&mut self, binding: Binding<A, B>, change: Change)
+        -> Option<(Binding<A, B>, Change)>;
+}
+
+impl<A, B, F: FnMut(Binding<A, B>, Change) -> Option<(Binding<A, B>, Change)>> ChangeFilter<A, B>
+    for F
+{
+    fn filter(
+        &mut self,
+        binding: Binding<A, B>,
+        change: Change,
+    ) -> Option<(Binding<A, B>, Change)> {
+        self(binding, change)
+    }
+}
+
+/// Adjusts an action's state after each tick, for gameplay rules such as
+/// limiting look pitch or zeroing movement while stunned.
+pub trait StateProcessor {
+    fn process(&mut self, state: &mut State);
+}
+
+impl<F: FnMut(&mut State)> StateProcessor for F {
+    fn process(&mut self, state: &mut State) {
+        self(state)
+    }
+}
+
+pub fn run_change_filters<A, B>(
+    filters: &mut [Box<dyn ChangeFilter<A, B>>],
+    binding: Binding<A, B>,
+    change: Change,
+) -> Option<(Binding<A, B>, Change)> {
+    filters
+        .iter_mut()
+        .try_fold((binding, change), |(binding, change), filter| {
+            filter.filter(binding, change)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use change::{ButtonChange, ValueChange};
+
+    #[test]
+    fn filters_chain_until_one_drops() {
+        let mut filters: Vec<Box<dyn ChangeFilter<u8, u8>>> = vec![
+            Box::new(|binding, change| match change {
+                Change::Value(ValueChange::Set(value)) => {
+                    Some((binding, ValueChange::Set(value.min(1.0)).into()))
+                }
+                change => Some((binding, change)),
+            }),
+            Box::new(|binding: Binding<u8, u8>, change: Change| match binding {
+                Binding::Button(0) => None,
+                binding => Some((binding, change)),
+            }),
+        ];
+        assert_eq!(
+            run_change_filters(
+                &mut filters,
+                Binding::Value(1),
+                ValueChange::Set(3.0).into()
+            ),
+            Some((Binding::Value(1), ValueChange::Set(1.0).into()))
+        );
+        assert_eq!(
+            run_change_filters(&mut filters, Binding::Button(0), ButtonChange(true).into()),
+            None
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+/// Turns millisecond timestamps, such as the one `requestAnimationFrame`
+/// passes its callback or `performance.now()`, into tick deltas. Deltas are
+/// clamped to `max_delta` so a tab resuming from suspension does not produce
+/// one enormous tick.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FrameClock {
+    last: Option<f64>,
+    max_delta: Duration,
+}
+
+impl Default for FrameClock {
+    fn default() -> Self {
+        FrameClock::new(Duration::from_millis(100))
+    }
+}
+
+pub trait IFrameClock {
+    fn new(max_delta: Duration) -> Self
+    where
+        Self: Sized;
+    fn advance(&mut self, now_ms: f64) -> Duration;
+    fn set_max_delta(&mut self, max_delta: Duration);
+    fn reset(&mut self);
+}
+
+impl IFrameClock for FrameClock {
+    fn new(max_delta: Duration) -> Self {
+        FrameClock {
+            last: None,
+            max_delta,
+        }
+    }
+    fn advance(&mut self, now_ms: f64) -> Duration {
+        let elapsed = match self.last {
+            Some(last) if now_ms > last => Duration::from_secs_f64((now_ms - last) / 1000.0),
+            _ => Duration::default(),
+        };
+        self.last = Some(now_ms);
+        elapsed.min(self.max_delta)
+    }
+    fn set_max_delta(&mut self, max_delta: Duration) {
+        self.max_delta = max_delta;
+    }
+    fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn first_frame_has_no_delta() {
+        let mut clock = FrameClock::default();
+        assert_eq!(clock.advance(1234.0), Duration::default());
+        assert_eq!(clock.advance(1250.0), Duration::from_millis(16));
+    }
+    #[test]
+    fn clamps_after_suspension() {
+        let mut clock = FrameClock::default();
+        clock.advance(0.0);
+        assert_eq!(clock.advance(60_000.0), Duration::from_millis(100));
+        assert_eq!(clock.advance(59_000.0), Duration::default());
+    }
+}
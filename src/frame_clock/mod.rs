@@ -0,0 +1,2 @@
+mod frame_clock;
+pub use self::frame_clock::*;
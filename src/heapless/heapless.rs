@@ -0,0 +1,296 @@
+use change::{AxisChange, ButtonChange, Direction};
+use manager::{Binding, Change};
+use std::time::Duration;
+
+/// Returned when a fixed-capacity container is already full.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CapacityError;
+
+/// A vector with inline storage for at most `N` items.
+#[derive(Debug, Clone)]
+pub struct FixedVec<T, const N: usize> {
+    items: [Option<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> Default for FixedVec<T, N> {
+    fn default() -> Self {
+        FixedVec {
+            items: std::array::from_fn(|_| None),
+            len: 0,
+        }
+    }
+}
+
+impl<T, const N: usize> FixedVec<T, N> {
+    pub fn push(&mut self, item: T) -> Result<(), CapacityError> {
+        if self.len == N {
+            return Err(CapacityError);
+        }
+        self.items[self.len] = Some(item);
+        self.len += 1;
+        Ok(())
+    }
+    pub fn swap_remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        self.len -= 1;
+        self.items.swap(index, self.len);
+        self.items[self.len].take()
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items[..self.len]
+            .iter()
+            .filter_map(|item| item.as_ref())
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.items[..self.len]
+            .iter_mut()
+            .filter_map(|item| item.as_mut())
+    }
+}
+
+/// A linear-probe map with inline storage for at most `N` entries. Lookups
+/// are O(N), which is the right trade for the handful of bindings a
+/// microcontroller-class device has.
+#[derive(Debug, Clone)]
+pub struct FixedMap<K, V, const N: usize> {
+    entries: FixedVec<(K, V), N>,
+}
+
+impl<K, V, const N: usize> Default for FixedMap<K, V, N> {
+    fn default() -> Self {
+        FixedMap {
+            entries: FixedVec::default(),
+        }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> FixedMap<K, V, N> {
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError> {
+        if let Some(existing) = self.get_mut(&key) {
+            return Ok(Some(std::mem::replace(existing, value)));
+        }
+        self.entries.push((key, value)).map(|_| None)
+    }
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.entries
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, value)| value)
+    }
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.entries
+            .iter_mut()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, value)| value)
+    }
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self
+            .entries
+            .iter()
+            .position(|(existing, _)| existing == key)?;
+        self.entries.swap_remove(index).map(|(_, value)| value)
+    }
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &(K, V)> {
+        self.entries.iter()
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut (K, V)> {
+        self.entries.iter_mut()
+    }
+}
+
+/// An axis without SOCD tracking, so applying changes never allocates.
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct FixedAxis {
+    pub position: f64,
+    pub velocity: Option<Direction>,
+    falling: bool,
+}
+
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct FixedButton {
+    pub pressed: bool,
+    pub new_event: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum FixedState {
+    Axis(FixedAxis),
+    Button(FixedButton),
+}
+
+impl FixedAxis {
+    fn apply(&mut self, change: AxisChange) {
+        match change {
+            AxisChange::Position(position) => {
+                self.falling = false;
+                self.position = position;
+            }
+            AxisChange::Velocity(direction) => {
+                self.falling = false;
+                self.velocity = Some(direction);
+            }
+            AxisChange::Falling(direction) => {
+                if self.velocity.as_ref() == Some(&direction) {
+                    self.velocity = None;
+                    self.falling = true;
+                }
+            }
+        }
+    }
+    fn tick(&mut self, delta: Duration) {
+        let dx = match (self.falling, self.velocity.clone()) {
+            (true, _) if self.position > 0.0 => -1.0,
+            (true, _) => 1.0,
+            (false, Some(direction)) => direction.into(),
+            (false, None) => 0.0,
+        };
+        self.position += dx * delta.as_millis() as f64 / 500.0;
+        if self.falling && dx * self.position > 0.0 {
+            self.falling = false;
+            self.position = 0.0;
+        } else if !self.falling {
+            self.position = self.position.clamp(-1.0, 1.0);
+        }
+    }
+}
+
+impl FixedButton {
+    fn apply(&mut self, change: ButtonChange) {
+        if self.pressed != change.0 {
+            self.pressed = change.0;
+            self.new_event = true;
+        }
+    }
+}
+
+/// The core binding and state machine with all storage inline, for targets
+/// without an allocator. `N` bounds both the number of bound inputs and the
+/// number of actions. Macros, combos, polling and the other std-backed
+/// subsystems are only available on `Manager`.
+#[derive(Debug, Clone)]
+pub struct HeaplessManager<A, B, C, const N: usize> {
+    bindings: FixedMap<C, Binding<A, B>, N>,
+    states: FixedMap<Binding<A, B>, FixedState, N>,
+}
+
+pub trait IHeaplessManager<A, B, C> {
+    fn new() -> Self
+    where
+        Self: Sized;
+    fn add_axis_binding(&mut self, axis: A, input: C) -> Result<(), CapacityError>;
+    fn add_button_binding(&mut self, button: B, input: C) -> Result<(), CapacityError>;
+    fn apply_input(&mut self, input: &C, change: Change);
+    fn tick(&mut self, delta: Duration);
+    fn get_axis(&self, axis: A) -> Option<&FixedAxis>;
+    fn get_button(&self, button: B) -> Option<&FixedButton>;
+    fn get_button_pressed(&self, button: B) -> bool;
+}
+
+impl<A: PartialEq + Clone, B: PartialEq + Clone, C: PartialEq, const N: usize>
+    HeaplessManager<A, B, C, N>
+{
+    fn bind(
+        &mut self,
+        input: C,
+        binding: Binding<A, B>,
+        state: FixedState,
+    ) -> Result<(), CapacityError> {
+        if self.states.get(&binding).is_none() {
+            self.states.insert(binding.clone(), state)?;
+        }
+        self.bindings.insert(input, binding).map(|_| ())
+    }
+}
+
+impl<A: PartialEq + Clone, B: PartialEq + Clone, C: PartialEq, const N: usize>
+    IHeaplessManager<A, B, C> for HeaplessManager<A, B, C, N>
+{
+    fn new() -> Self {
+        HeaplessManager {
+            bindings: FixedMap::default(),
+            states: FixedMap::default(),
+        }
+    }
+    fn add_axis_binding(&mut self, axis: A, input: C) -> Result<(), CapacityError> {
+        self.bind(
+            input,
+            Binding::Axis(axis),
+            FixedState::Axis(FixedAxis::default()),
+        )
+    }
+    fn add_button_binding(&mut self, button: B, input: C) -> Result<(), CapacityError> {
+        self.bind(
+            input,
+            Binding::Button(button),
+            FixedState::Button(FixedButton::default()),
+        )
+    }
+    fn apply_input(&mut self, input: &C, change: Change) {
+        let state = match self.bindings.get(input) {
+            Some(binding) => self.states.get_mut(binding),
+            None => None,
+        };
+        match (state, change) {
+            (Some(FixedState::Axis(axis)), Change::Axis(change)) => axis.apply(change),
+            (Some(FixedState::Button(button)), Change::Button(change)) => button.apply(change),
+            _ => {}
+        }
+    }
+    fn tick(&mut self, delta: Duration) {
+        for (_, state) in self.states.iter_mut() {
+            match state {
+                FixedState::Axis(axis) => axis.tick(delta),
+                FixedState::Button(button) => button.new_event = false,
+            }
+        }
+    }
+    fn get_axis(&self, axis: A) -> Option<&FixedAxis> {
+        match self.states.get(&Binding::Axis(axis)) {
+            Some(FixedState::Axis(axis)) => Some(axis),
+            _ => None,
+        }
+    }
+    fn get_button(&self, button: B) -> Option<&FixedButton> {
+        match self.states.get(&Binding::Button(button)) {
+            Some(FixedState::Button(button)) => Some(button),
+            _ => None,
+        }
+    }
+    fn get_button_pressed(&self, button: B) -> bool {
+        self.get_button(button)
+            .is_some_and(|button| button.pressed && button.new_event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn runs_within_fixed_capacity() {
+        let mut manager: HeaplessManager<u8, u8, char, 2> = HeaplessManager::new();
+        assert_eq!(manager.add_button_binding(0, 'a'), Ok(()));
+        assert_eq!(manager.add_axis_binding(1, 'w'), Ok(()));
+        assert_eq!(manager.add_button_binding(2, 'b'), Err(CapacityError));
+        manager.apply_input(&'a', ButtonChange(true).into());
+        manager.apply_input(&'w', AxisChange::Velocity(Direction::Up).into());
+        assert!(manager.get_button_pressed(0));
+        manager.tick(Duration::from_millis(250));
+        assert!(!manager.get_button_pressed(0));
+        assert_eq!(manager.get_axis(1).unwrap().position, 0.5);
+    }
+}
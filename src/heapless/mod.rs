@@ -0,0 +1,2 @@
+mod heapless;
+pub use self::heapless::*;
@@ -0,0 +1,85 @@
+use manager::Binding;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::Duration;
+
+/// Activation counts per action, one map per `bucket` of elapsed time, and
+/// pointer samples counted into a `columns` by `rows` grid over normalized
+/// screen coordinates.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Heatmap<A: Hash + Eq, B: Hash + Eq> {
+    pub bucket: Duration,
+    pub counts: Vec<HashMap<Binding<A, B>, u32>>,
+    pub columns: usize,
+    pub rows: usize,
+    pub regions: Vec<u32>,
+    elapsed: Duration,
+}
+
+pub trait IHeatmap<A: Hash + Eq, B: Hash + Eq> {
+    fn new(bucket: Duration, columns: usize, rows: usize) -> Self
+    where
+        Self: Sized;
+    fn record_activation(&mut self, action: &Binding<A, B>);
+    /// `x` and `y` run from 0 to 1; points outside are clamped to the edge.
+    fn record_point(&mut self, x: f64, y: f64);
+    fn tick(&mut self, delta: Duration);
+    fn region_count(&self, column: usize, row: usize) -> u32;
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone> IHeatmap<A, B> for Heatmap<A, B> {
+    fn new(bucket: Duration, columns: usize, rows: usize) -> Self {
+        Heatmap {
+            bucket,
+            counts: vec![HashMap::new()],
+            columns,
+            rows,
+            regions: vec![0; columns * rows],
+            elapsed: Duration::default(),
+        }
+    }
+    fn record_activation(&mut self, action: &Binding<A, B>) {
+        if let Some(bucket) = self.counts.last_mut() {
+            *bucket.entry(action.clone()).or_insert(0) += 1;
+        }
+    }
+    fn record_point(&mut self, x: f64, y: f64) {
+        if self.regions.is_empty() {
+            return;
+        }
+        let cell = |position: f64, cells: usize| {
+            ((position.clamp(0.0, 1.0) * cells as f64) as usize).min(cells - 1)
+        };
+        let index = cell(y, self.rows) * self.columns + cell(x, self.columns);
+        self.regions[index] += 1;
+    }
+    fn tick(&mut self, delta: Duration) {
+        self.elapsed += delta;
+        while self.bucket > Duration::default() && self.elapsed >= self.bucket {
+            self.elapsed -= self.bucket;
+            self.counts.push(HashMap::new());
+        }
+    }
+    fn region_count(&self, column: usize, row: usize) -> u32 {
+        self.regions[row * self.columns + column]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn buckets_activations_and_points() {
+        let mut heatmap: Heatmap<u8, u8> = Heatmap::new(Duration::from_secs(1), 2, 2);
+        heatmap.record_activation(&Binding::Button(1));
+        heatmap.tick(Duration::from_millis(1500));
+        heatmap.record_activation(&Binding::Button(1));
+        heatmap.record_activation(&Binding::Button(1));
+        assert_eq!(heatmap.counts.len(), 2);
+        assert_eq!(heatmap.counts[1][&Binding::Button(1)], 2);
+        heatmap.record_point(0.75, 0.25);
+        heatmap.record_point(1.5, 0.0);
+        assert_eq!(heatmap.region_count(1, 0), 2);
+        assert_eq!(heatmap.region_count(0, 1), 0);
+    }
+}
@@ -0,0 +1,2 @@
+mod heatmap;
+pub use self::heatmap::*;
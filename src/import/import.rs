@@ -0,0 +1,296 @@
+use axis::InputAxisConfig;
+use profile::{BindingProfile, IBindingProfile};
+use std::collections::HashMap;
+
+/// Bindings and per-input tuning read from another engine's config. Actions
+/// and inputs keep the names used in the source file.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportedBindings {
+    pub profile: BindingProfile<String, String, String>,
+    pub input_configs: HashMap<String, InputAxisConfig>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ImportError {
+    MissingField { line: usize, field: String },
+    InvalidNumber { line: usize, value: String },
+}
+
+fn parse_number(line: usize, value: &str) -> Result<f64, ImportError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| ImportError::InvalidNumber {
+            line,
+            value: value.trim().to_string(),
+        })
+}
+
+fn new_import() -> ImportedBindings {
+    ImportedBindings {
+        profile: BindingProfile::new(),
+        input_configs: HashMap::new(),
+    }
+}
+
+#[derive(Default)]
+struct UnityAxis {
+    line: usize,
+    fields: HashMap<String, (usize, String)>,
+}
+
+impl UnityAxis {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .get(name)
+            .map(|(_, value)| value.as_str())
+            .filter(|value| !value.is_empty())
+    }
+    fn number(&self, name: &str) -> Result<Option<f64>, ImportError> {
+        match self.fields.get(name) {
+            Some((line, value)) if !value.is_empty() => parse_number(*line, value).map(Some),
+            _ => Ok(None),
+        }
+    }
+    fn import(&self, imported: &mut ImportedBindings) -> Result<(), ImportError> {
+        let name = self
+            .field("m_Name")
+            .ok_or_else(|| ImportError::MissingField {
+                line: self.line,
+                field: "m_Name".to_string(),
+            })?
+            .to_string();
+        if self.field("type") == Some("2") {
+            let axis = self.number("axis")?.unwrap_or(0.0) as u32;
+            let input = format!("joystick axis {}", axis + 1);
+            let config = InputAxisConfig {
+                dead_zone: self.number("dead")?.unwrap_or(0.0),
+                sensitivity: self.number("sensitivity")?.unwrap_or(1.0),
+                ..InputAxisConfig::default()
+            };
+            imported.input_configs.insert(input.clone(), config);
+            imported.profile.add_axis_binding(name, input);
+            return Ok(());
+        }
+        let negative = ["negativeButton", "altNegativeButton"]
+            .iter()
+            .filter_map(|field| self.field(field))
+            .collect::<Vec<_>>();
+        let positive = ["positiveButton", "altPositiveButton"]
+            .iter()
+            .filter_map(|field| self.field(field));
+        for input in negative.iter().cloned().chain(positive) {
+            if negative.is_empty() {
+                imported
+                    .profile
+                    .add_button_binding(name.clone(), input.to_string());
+            } else {
+                imported
+                    .profile
+                    .add_axis_binding(name.clone(), input.to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the axes list of Unity's legacy `InputManager.asset`. Axes with
+/// only positive buttons become buttons, joystick axes are bound to
+/// `joystick axis N` with their dead zone and sensitivity kept.
+pub fn import_unity_input_manager(asset: &str) -> Result<ImportedBindings, ImportError> {
+    let mut imported = new_import();
+    let mut current: Option<UnityAxis> = None;
+    for (index, line) in asset.lines().enumerate() {
+        let line_number = index + 1;
+        let trimmed = line.trim();
+        let entry = match trimmed.strip_prefix("- ") {
+            Some(entry) => {
+                if let Some(axis) = current.take() {
+                    axis.import(&mut imported)?;
+                }
+                current = Some(UnityAxis {
+                    line: line_number,
+                    ..UnityAxis::default()
+                });
+                entry
+            }
+            None => trimmed,
+        };
+        if let (Some(axis), Some((key, value))) = (current.as_mut(), entry.split_once(':')) {
+            axis.fields.insert(
+                key.trim().to_string(),
+                (line_number, value.trim().to_string()),
+            );
+        }
+    }
+    if let Some(axis) = current {
+        axis.import(&mut imported)?;
+    }
+    Ok(imported)
+}
+
+fn unreal_fields(line: usize, body: &str) -> Result<HashMap<String, String>, ImportError> {
+    let inner = body
+        .trim()
+        .strip_prefix('(')
+        .and_then(|body| body.strip_suffix(')'))
+        .unwrap_or(body);
+    let mut fields = HashMap::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, c) in inner.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                insert_unreal_field(line, &inner[start..index], &mut fields)?;
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    insert_unreal_field(line, &inner[start..], &mut fields)?;
+    Ok(fields)
+}
+
+fn insert_unreal_field(
+    line: usize,
+    field: &str,
+    fields: &mut HashMap<String, String>,
+) -> Result<(), ImportError> {
+    if field.trim().is_empty() {
+        return Ok(());
+    }
+    let (key, value) = field
+        .split_once('=')
+        .ok_or_else(|| ImportError::MissingField {
+            line,
+            field: field.trim().to_string(),
+        })?;
+    let value = value.trim().trim_matches('"');
+    let value = match value.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+        Some(nested) => {
+            for (key, value) in unreal_fields(line, nested)? {
+                fields.insert(key, value);
+            }
+            return Ok(());
+        }
+        None => value,
+    };
+    fields.insert(key.trim().to_string(), value.to_string());
+    Ok(())
+}
+
+fn unreal_field<'a>(
+    line: usize,
+    fields: &'a HashMap<String, String>,
+    name: &str,
+) -> Result<&'a str, ImportError> {
+    fields
+        .get(name)
+        .map(|value| value.as_str())
+        .ok_or_else(|| ImportError::MissingField {
+            line,
+            field: name.to_string(),
+        })
+}
+
+/// Reads `ActionMappings`, `AxisMappings` and `AxisConfig` entries from an
+/// Unreal `DefaultInput.ini`. Axis configs keep their dead zone, exponent
+/// and sensitivity; other lines are ignored.
+pub fn import_unreal_input_ini(ini: &str) -> Result<ImportedBindings, ImportError> {
+    let mut imported = new_import();
+    for (index, line) in ini.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim().trim_start_matches(['+', '-', '.', '!']);
+        let (key, body) = match line.split_once('=') {
+            Some(entry) => entry,
+            None => continue,
+        };
+        match key.trim() {
+            "ActionMappings" => {
+                let fields = unreal_fields(line_number, body)?;
+                let action = unreal_field(line_number, &fields, "ActionName")?;
+                let key = unreal_field(line_number, &fields, "Key")?;
+                imported
+                    .profile
+                    .add_button_binding(action.to_string(), key.to_string());
+            }
+            "AxisMappings" => {
+                let fields = unreal_fields(line_number, body)?;
+                let axis = unreal_field(line_number, &fields, "AxisName")?;
+                let key = unreal_field(line_number, &fields, "Key")?;
+                imported
+                    .profile
+                    .add_axis_binding(axis.to_string(), key.to_string());
+            }
+            "AxisConfig" => {
+                let fields = unreal_fields(line_number, body)?;
+                let key = unreal_field(line_number, &fields, "AxisKeyName")?;
+                let number = |name: &str| match fields.get(name) {
+                    Some(value) => parse_number(line_number, value).map(Some),
+                    None => Ok(None),
+                };
+                let config = InputAxisConfig {
+                    dead_zone: number("DeadZone")?.unwrap_or(0.0),
+                    curve: number("Exponent")?.unwrap_or(1.0),
+                    sensitivity: number("Sensitivity")?.unwrap_or(1.0),
+                    ..InputAxisConfig::default()
+                };
+                imported.input_configs.insert(key.to_string(), config);
+            }
+            _ => {}
+        }
+    }
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use manager::Binding;
+
+    #[test]
+    fn imports_unity_axes() {
+        let asset = "InputManager:\n  m_Axes:\n  - serializedVersion: 3\n    m_Name: Horizontal\n    negativeButton: left\n    positiveButton: right\n    altNegativeButton: a\n    altPositiveButton: d\n    dead: 0.001\n    type: 0\n  - serializedVersion: 3\n    m_Name: Fire1\n    negativeButton: \n    positiveButton: left ctrl\n    type: 0\n  - serializedVersion: 3\n    m_Name: Vertical\n    dead: 0.25\n    sensitivity: 2\n    type: 2\n    axis: 1\n";
+        let imported = import_unity_input_manager(asset).unwrap();
+        let profile = &imported.profile;
+        assert_eq!(
+            profile.get_binding(&"a".to_string()),
+            Some(&Binding::Axis("Horizontal".to_string()))
+        );
+        assert_eq!(
+            profile.get_binding(&"left ctrl".to_string()),
+            Some(&Binding::Button("Fire1".to_string()))
+        );
+        assert_eq!(
+            profile.get_binding(&"joystick axis 2".to_string()),
+            Some(&Binding::Axis("Vertical".to_string()))
+        );
+        let config = &imported.input_configs["joystick axis 2"];
+        assert_eq!((config.dead_zone, config.sensitivity), (0.25, 2.0));
+    }
+
+    #[test]
+    fn imports_unreal_ini() {
+        let ini = "[/Script/Engine.InputSettings]\n+ActionMappings=(ActionName=\"Jump\",bShift=False,Key=SpaceBar)\n+AxisMappings=(AxisName=\"MoveForward\",Scale=1.000000,Key=Gamepad_LeftY)\n+AxisConfig=(AxisKeyName=\"Gamepad_LeftY\",AxisProperties=(DeadZone=0.250000,Exponent=2.000000,Sensitivity=1.000000))\n";
+        let imported = import_unreal_input_ini(ini).unwrap();
+        assert_eq!(
+            imported.profile.get_binding(&"SpaceBar".to_string()),
+            Some(&Binding::Button("Jump".to_string()))
+        );
+        assert_eq!(
+            imported.profile.get_binding(&"Gamepad_LeftY".to_string()),
+            Some(&Binding::Axis("MoveForward".to_string()))
+        );
+        let config = &imported.input_configs["Gamepad_LeftY"];
+        assert_eq!((config.dead_zone, config.curve), (0.25, 2.0));
+        assert_eq!(
+            import_unreal_input_ini("+AxisConfig=(AxisKeyName=X,AxisProperties=(DeadZone=abc))"),
+            Err(ImportError::InvalidNumber {
+                line: 1,
+                value: "abc".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,2 @@
+mod import;
+pub use self::import::*;
@@ -0,0 +1,159 @@
+use manager::{Binding, Change};
+use std::time::Duration;
+
+#[derive(Clone)]
+pub struct MacroStep<A, B> {
+    pub at: Duration,
+    pub binding: Binding<A, B>,
+    pub change: Change,
+}
+
+#[derive(Clone)]
+pub struct InputMacro<A, B> {
+    pub steps: Vec<MacroStep<A, B>>,
+}
+
+#[derive(Clone)]
+pub struct MacroRecorder<A, B> {
+    elapsed: Duration,
+    steps: Vec<MacroStep<A, B>>,
+}
+
+#[derive(Clone)]
+struct Playback<A, B> {
+    input_macro: InputMacro<A, B>,
+    speed: f64,
+    elapsed: Duration,
+    next: usize,
+}
+
+#[derive(Clone)]
+pub struct MacroPlayer<A, B> {
+    playing: Vec<Playback<A, B>>,
+}
+
+pub trait IMacroRecorder<A, B> {
+    fn record(&mut self, binding: &Binding<A, B>, change: &Change);
+    fn tick(&mut self, delta: Duration);
+    fn finish(self) -> InputMacro<A, B>;
+}
+
+pub trait IMacroPlayer<A, B> {
+    /// Starts playing `input_macro`. Returns false, playing nothing, when
+    /// `speed` is negative or not finite.
+    fn play(&mut self, input_macro: InputMacro<A, B>, speed: f64) -> bool;
+    fn tick(&mut self, delta: Duration) -> Vec<(Binding<A, B>, Change)>;
+    fn is_playing(&self) -> bool;
+}
+
+impl<A, B> Default for MacroRecorder<A, B> {
+    fn default() -> Self {
+        MacroRecorder {
+            elapsed: Duration::default(),
+            steps: Vec::new(),
+        }
+    }
+}
+
+impl<A: Clone, B: Clone> IMacroRecorder<A, B> for MacroRecorder<A, B> {
+    fn record(&mut self, binding: &Binding<A, B>, change: &Change) {
+        self.steps.push(MacroStep {
+            at: self.elapsed,
+            binding: binding.clone(),
+            change: change.clone(),
+        });
+    }
+    fn tick(&mut self, delta: Duration) {
+        self.elapsed += delta;
+    }
+    fn finish(self) -> InputMacro<A, B> {
+        InputMacro { steps: self.steps }
+    }
+}
+
+impl<A, B> Default for MacroPlayer<A, B> {
+    fn default() -> Self {
+        MacroPlayer {
+            playing: Vec::new(),
+        }
+    }
+}
+
+impl<A: Clone, B: Clone> IMacroPlayer<A, B> for MacroPlayer<A, B> {
+    fn play(&mut self, input_macro: InputMacro<A, B>, speed: f64) -> bool {
+        if !speed.is_finite() || speed < 0.0 {
+            return false;
+        }
+        self.playing.push(Playback {
+            input_macro,
+            speed,
+            elapsed: Duration::default(),
+            next: 0,
+        });
+        true
+    }
+    fn tick(&mut self, delta: Duration) -> Vec<(Binding<A, B>, Change)> {
+        let mut due = Vec::new();
+        for playback in self.playing.iter_mut() {
+            playback.elapsed += delta.mul_f64(playback.speed);
+            while let Some(step) = playback.input_macro.steps.get(playback.next) {
+                if step.at > playback.elapsed {
+                    break;
+                }
+                due.push((step.binding.clone(), step.change.clone()));
+                playback.next += 1;
+            }
+        }
+        self.playing
+            .retain(|playback| playback.next < playback.input_macro.steps.len());
+        due
+    }
+    fn is_playing(&self) -> bool {
+        !self.playing.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use change::ButtonChange;
+    fn recorded() -> InputMacro<(), &'static str> {
+        let mut recorder = MacroRecorder::default();
+        recorder.record(&Binding::Button("Jump"), &ButtonChange(true).into());
+        recorder.tick(Duration::from_millis(100));
+        recorder.record(&Binding::Button("Jump"), &ButtonChange(false).into());
+        recorder.finish()
+    }
+    #[test]
+    fn records_offsets() {
+        let input_macro = recorded();
+        assert_eq!(input_macro.steps.len(), 2);
+        assert_eq!(input_macro.steps[1].at, Duration::from_millis(100));
+    }
+    #[test]
+    fn plays_back_over_ticks() {
+        let mut player = MacroPlayer::default();
+        player.play(recorded(), 1.0);
+        assert_eq!(player.tick(Duration::from_millis(16)).len(), 1);
+        assert_eq!(player.tick(Duration::from_millis(16)).len(), 0);
+        assert_eq!(player.tick(Duration::from_millis(100)).len(), 1);
+        assert!(!player.is_playing());
+    }
+    #[test]
+    fn speed_scales_playback() {
+        let mut player = MacroPlayer::default();
+        player.play(recorded(), 2.0);
+        player.tick(Duration::from_millis(16));
+        assert_eq!(player.tick(Duration::from_millis(50)).len(), 1);
+    }
+
+    #[test]
+    fn rejects_invalid_speed() {
+        let mut player = MacroPlayer::default();
+        assert!(!player.play(recorded(), -1.0));
+        assert!(!player.play(recorded(), f64::NAN));
+        assert!(!player.play(recorded(), f64::INFINITY));
+        assert!(!player.is_playing());
+        assert!(player.tick(Duration::from_millis(16)).is_empty());
+    }
+}
@@ -0,0 +1,2 @@
+mod input_macro;
+pub use self::input_macro::*;
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+/// Maps names to small integer handles so lookups after registration hash a
+/// `u32` instead of a string. Handles are assigned in registration order.
+#[derive(Default, Debug, Clone)]
+pub struct Interner {
+    handles: HashMap<String, u32>,
+    names: Vec<String>,
+}
+
+pub trait IInterner {
+    fn new() -> Self
+    where
+        Self: Sized;
+    fn intern(&mut self, name: &str) -> u32;
+    fn get(&self, name: &str) -> Option<u32>;
+    fn name(&self, handle: u32) -> Option<&str>;
+}
+
+impl IInterner for Interner {
+    fn new() -> Self {
+        Interner::default()
+    }
+    fn intern(&mut self, name: &str) -> u32 {
+        if let Some(&handle) = self.handles.get(name) {
+            return handle;
+        }
+        let handle = self.names.len() as u32;
+        self.names.push(name.to_string());
+        self.handles.insert(name.to_string(), handle);
+        handle
+    }
+    fn get(&self, name: &str) -> Option<u32> {
+        self.handles.get(name).cloned()
+    }
+    fn name(&self, handle: u32) -> Option<&str> {
+        self.names.get(handle as usize).map(|name| name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn interns_each_name_once() {
+        let mut interner = Interner::new();
+        assert_eq!(interner.intern("jump"), 0);
+        assert_eq!(interner.intern("fire"), 1);
+        assert_eq!(interner.intern("jump"), 0);
+        assert_eq!(interner.get("fire"), Some(1));
+        assert_eq!(interner.name(1), Some("fire"));
+        assert_eq!(interner.get("crouch"), None);
+    }
+}
@@ -0,0 +1,2 @@
+mod interner;
+pub use self::interner::*;
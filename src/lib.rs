@@ -1,9 +1,151 @@
+#![allow(clippy::module_inception)]
+#![allow(anonymous_parameters)]
+#![allow(clippy::from_over_into, clippy::manual_clamp)]
+#![cfg_attr(
+    test,
+    allow(clippy::bool_assert_comparison, clippy::field_reassign_with_default)
+)]
+
+mod accumulator;
+#[cfg(feature = "analog_keyboard")]
+mod analog_keyboard;
+#[cfg(feature = "animation")]
+mod animation;
+#[cfg(feature = "audit")]
+mod audit;
 mod axis;
+mod axis2d;
+mod binding_edit;
 mod button;
 mod change;
+#[cfg(feature = "combo")]
+mod combo;
+#[cfg(feature = "custom_state")]
+mod custom_state;
+#[cfg(feature = "device")]
+mod device;
+#[cfg(feature = "drift")]
+mod drift;
+#[cfg(feature = "encoding")]
+mod encoding;
+#[cfg(feature = "export")]
+mod export;
+#[cfg(feature = "extension")]
+mod extension;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(feature = "filter")]
+mod filter;
+#[cfg(feature = "frame_clock")]
+mod frame_clock;
+#[cfg(feature = "heapless")]
+mod heapless;
+#[cfg(feature = "heatmap")]
+mod heatmap;
+#[cfg(feature = "import")]
+mod import;
+#[cfg(feature = "recording")]
+mod input_macro;
+#[cfg(feature = "interner")]
+mod interner;
 mod manager;
+#[cfg(feature = "midi")]
+mod midi;
+#[cfg(feature = "net")]
+mod net;
+#[cfg(feature = "osc")]
+mod osc;
+#[cfg(feature = "output")]
+mod output;
+#[cfg(feature = "polling")]
+mod polling;
+#[cfg(feature = "profile")]
+mod profile;
+#[cfg(feature = "remote")]
+mod remote;
+#[cfg(feature = "serial")]
+mod serial;
+#[cfg(feature = "tilt")]
+mod tilt;
+#[cfg(feature = "timing")]
+mod timing;
+#[cfg(feature = "touch")]
+mod touch;
+mod trigger;
+mod value;
+#[cfg(feature = "virtual_device")]
+mod virtual_device;
+#[cfg(feature = "virtual_keyboard")]
+mod virtual_keyboard;
 
+pub use accumulator::*;
+#[cfg(feature = "analog_keyboard")]
+pub use analog_keyboard::*;
+#[cfg(feature = "animation")]
+pub use animation::*;
+#[cfg(feature = "audit")]
+pub use audit::*;
 pub use axis::*;
+pub use axis2d::*;
+pub use binding_edit::*;
 pub use button::*;
 pub use change::*;
+#[cfg(feature = "combo")]
+pub use combo::*;
+#[cfg(feature = "custom_state")]
+pub use custom_state::*;
+#[cfg(feature = "device")]
+pub use device::*;
+#[cfg(feature = "drift")]
+pub use drift::*;
+#[cfg(feature = "encoding")]
+pub use encoding::*;
+#[cfg(feature = "export")]
+pub use export::*;
+#[cfg(feature = "extension")]
+pub use extension::*;
+#[cfg(feature = "ffi")]
+pub use ffi::*;
+#[cfg(feature = "filter")]
+pub use filter::*;
+#[cfg(feature = "frame_clock")]
+pub use frame_clock::*;
+#[cfg(feature = "heapless")]
+pub use heapless::*;
+#[cfg(feature = "heatmap")]
+pub use heatmap::*;
+#[cfg(feature = "import")]
+pub use import::*;
+#[cfg(feature = "recording")]
+pub use input_macro::*;
+#[cfg(feature = "interner")]
+pub use interner::*;
 pub use manager::*;
+#[cfg(feature = "midi")]
+pub use midi::*;
+#[cfg(feature = "net")]
+pub use net::*;
+#[cfg(feature = "osc")]
+pub use osc::*;
+#[cfg(feature = "output")]
+pub use output::*;
+#[cfg(feature = "polling")]
+pub use polling::*;
+#[cfg(feature = "profile")]
+pub use profile::*;
+#[cfg(feature = "remote")]
+pub use remote::*;
+#[cfg(feature = "serial")]
+pub use serial::*;
+#[cfg(feature = "tilt")]
+pub use tilt::*;
+#[cfg(feature = "timing")]
+pub use timing::*;
+#[cfg(feature = "touch")]
+pub use touch::*;
+pub use trigger::*;
+pub use value::*;
+#[cfg(feature = "virtual_device")]
+pub use virtual_device::*;
+#[cfg(feature = "virtual_keyboard")]
+pub use virtual_keyboard::*;
@@ -0,0 +1,177 @@
+#[cfg(feature = "encoding")]
+use encoding::{BitReader, BitWriter, Decode, DecodeError, Encode};
+use manager::manager::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A stable reference to a slot in a `StateArena`. Handles are checked
+/// against the slot's generation, so a handle to a state that was dropped by
+/// a rewind resolves to nothing instead of to whatever replaced it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct StateHandle {
+    index: usize,
+    generation: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct AxisHandle(pub StateHandle);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ButtonHandle(pub StateHandle);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ValueHandle(pub StateHandle);
+
+/// Action states stored densely in insertion order, with a binding index for
+/// lookups by action. States are never removed individually.
+#[derive(Debug, Clone)]
+pub struct StateArena<A, B> {
+    slots: Vec<(Binding<A, B>, State, u64)>,
+    index: HashMap<Binding<A, B>, usize>,
+    next_generation: u64,
+}
+
+impl<A: Hash + Eq, B: Hash + Eq> Default for StateArena<A, B> {
+    fn default() -> Self {
+        StateArena {
+            slots: Vec::new(),
+            index: HashMap::new(),
+            next_generation: 0,
+        }
+    }
+}
+
+impl<A: Hash + Eq, B: Hash + Eq> PartialEq for StateArena<A, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(binding, state)| other.get(binding) == Some(state))
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl<A: Encode, B: Encode> Encode for StateArena<A, B> {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.slots.encode(writer);
+        self.next_generation.encode(writer);
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl<A: Hash + Eq + Clone + Decode, B: Hash + Eq + Clone + Decode> Decode for StateArena<A, B> {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        let slots: Vec<(Binding<A, B>, State, u64)> = Vec::decode(reader)?;
+        let index = slots
+            .iter()
+            .enumerate()
+            .map(|(index, (binding, _, _))| (binding.clone(), index))
+            .collect();
+        Ok(StateArena {
+            slots,
+            index,
+            next_generation: u64::decode(reader)?,
+        })
+    }
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone> StateArena<A, B> {
+    pub fn get_or_insert_with<F: FnOnce() -> State>(
+        &mut self,
+        binding: Binding<A, B>,
+        state: F,
+    ) -> &mut State {
+        let index = match self.index.get(&binding) {
+            Some(&index) => index,
+            None => {
+                let index = self.slots.len();
+                self.slots
+                    .push((binding.clone(), state(), self.next_generation));
+                self.next_generation += 1;
+                self.index.insert(binding, index);
+                index
+            }
+        };
+        &mut self.slots[index].1
+    }
+    /// Keeps generations counting up across a rewind so handles to states
+    /// created after the snapshot don't match anything created later.
+    pub fn restore_from(&mut self, snapshot: &StateArena<A, B>) {
+        let next_generation = self.next_generation.max(snapshot.next_generation);
+        *self = snapshot.clone();
+        self.next_generation = next_generation;
+    }
+}
+
+impl<A: Hash + Eq, B: Hash + Eq> StateArena<A, B> {
+    pub fn get(&self, binding: &Binding<A, B>) -> Option<&State> {
+        self.index.get(binding).map(|&index| &self.slots[index].1)
+    }
+    pub fn get_mut(&mut self, binding: &Binding<A, B>) -> Option<&mut State> {
+        match self.index.get(binding) {
+            Some(&index) => Some(&mut self.slots[index].1),
+            None => None,
+        }
+    }
+    pub fn handle(&self, binding: &Binding<A, B>) -> Option<StateHandle> {
+        self.index.get(binding).map(|&index| StateHandle {
+            index,
+            generation: self.slots[index].2,
+        })
+    }
+    pub fn get_by_handle(&self, handle: StateHandle) -> Option<&State> {
+        match self.slots.get(handle.index) {
+            Some((_, state, generation)) if *generation == handle.generation => Some(state),
+            _ => None,
+        }
+    }
+    pub fn contains_key(&self, binding: &Binding<A, B>) -> bool {
+        self.index.contains_key(binding)
+    }
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&Binding<A, B>, &State)> {
+        self.slots
+            .iter()
+            .map(|(binding, state, _)| (binding, state))
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&Binding<A, B>, &mut State)> {
+        self.slots
+            .iter_mut()
+            .map(|(binding, state, _)| (&*binding, state))
+    }
+    pub fn keys(&self) -> impl Iterator<Item = &Binding<A, B>> {
+        self.slots.iter().map(|(binding, _, _)| binding)
+    }
+    pub fn values(&self) -> impl Iterator<Item = &State> {
+        self.slots.iter().map(|(_, state, _)| state)
+    }
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut State> {
+        self.slots.iter_mut().map(|(_, state, _)| state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axis::Axis;
+    use button::Button;
+
+    #[test]
+    fn handles_go_stale_after_rewind() {
+        let mut arena: StateArena<u8, u8> = StateArena::default();
+        arena.get_or_insert_with(Binding::Axis(0), || State::Axis(Axis::default()));
+        let snapshot = arena.clone();
+        arena.get_or_insert_with(Binding::Button(1), || State::Button(Button::default()));
+        let axis = arena.handle(&Binding::Axis(0)).unwrap();
+        let button = arena.handle(&Binding::Button(1)).unwrap();
+        arena.restore_from(&snapshot);
+        arena.get_or_insert_with(Binding::Button(2), || State::Button(Button::default()));
+        assert!(arena.get_by_handle(axis).is_some());
+        assert_eq!(arena.get_by_handle(button), None);
+    }
+}
@@ -0,0 +1,243 @@
+use axis::InputAxisConfig;
+use change::{AxisChange, ButtonChange};
+use manager::manager::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Resolves raw inputs to the actions they drive. The manager composes one of
+/// these, so other resolution strategies only need to produce a map rather
+/// than reimplement state storage.
+#[derive(Debug, Clone)]
+pub struct BindingMap<A, B, C> {
+    bindings: HashMap<C, Binding<A, B>>,
+    default_changes: HashMap<C, Change>,
+    input_configs: HashMap<C, InputAxisConfig>,
+    input_thresholds: HashMap<C, f64>,
+    input_index: Option<fn(&C) -> usize>,
+    indexed: Vec<Option<Binding<A, B>>>,
+}
+
+/// Indices at or above this bound are resolved through the `HashMap` instead
+/// of the table, so a sparse index can't force a huge allocation.
+pub const MAX_INDEXED_INPUTS: usize = 1024;
+
+/// Inputs that map to a dense index, letting `resolve` use a table lookup
+/// instead of hashing. Usually implemented with `indexed_input!`.
+pub trait IndexedInput {
+    fn input_index(&self) -> usize;
+}
+
+/// Declares a fieldless input enum and a match-based `IndexedInput` impl for
+/// it.
+#[macro_export]
+macro_rules! indexed_input {
+    ($(#[$meta:meta])* $vis:vis enum $name:ident { $($variant:ident),* $(,)? }) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variant),*
+        }
+
+        impl $crate::IndexedInput for $name {
+            fn input_index(&self) -> usize {
+                match self {
+                    $($name::$variant => $name::$variant as usize),*
+                }
+            }
+        }
+    };
+}
+
+pub trait IBindingMap<A, B, C> {
+    fn new() -> Self
+    where
+        Self: Sized;
+    fn from_bindings(bindings: HashMap<C, Binding<A, B>>) -> Self
+    where
+        Self: Sized;
+    fn resolve(&self, input: &C) -> Option<&Binding<A, B>>;
+    fn set_binding(&mut self, input: C, binding: Option<Binding<A, B>>) -> Option<Binding<A, B>>;
+    fn inputs_for(&self, binding: &Binding<A, B>) -> Vec<&C>;
+    fn get_bindings(&self) -> &HashMap<C, Binding<A, B>>;
+    fn add_default_change(&mut self, input: C, change: Change);
+    fn get_default_change(&self, input: &C) -> Option<&Change>;
+    fn set_input_index(&mut self, index: fn(&C) -> usize);
+    fn set_input_config(&mut self, input: C, config: InputAxisConfig);
+    fn get_input_config(&self, input: &C) -> Option<&InputAxisConfig>;
+    fn set_input_threshold(&mut self, input: C, threshold: f64);
+    fn get_input_threshold(&self, input: &C) -> Option<f64>;
+    fn shape_position(&self, input: &C, position: f64) -> f64;
+    fn convert_change(&self, input: &C, change: Change) -> Change;
+}
+
+impl<A: PartialEq + Clone, B: PartialEq + Clone, C: Hash + Eq> IBindingMap<A, B, C>
+    for BindingMap<A, B, C>
+{
+    fn new() -> Self {
+        BindingMap::from_bindings(HashMap::new())
+    }
+    fn from_bindings(bindings: HashMap<C, Binding<A, B>>) -> Self {
+        BindingMap {
+            bindings,
+            default_changes: HashMap::new(),
+            input_configs: HashMap::new(),
+            input_thresholds: HashMap::new(),
+            input_index: None,
+            indexed: Vec::new(),
+        }
+    }
+    fn resolve(&self, input: &C) -> Option<&Binding<A, B>> {
+        match self.input_index.map(|index| index(input)) {
+            Some(index) if index < MAX_INDEXED_INPUTS => {
+                self.indexed.get(index).and_then(|b| b.as_ref())
+            }
+            _ => self.bindings.get(input),
+        }
+    }
+    fn set_binding(&mut self, input: C, binding: Option<Binding<A, B>>) -> Option<Binding<A, B>> {
+        if let Some(index) = self.input_index {
+            self.set_indexed(index(&input), binding.clone());
+        }
+        match binding {
+            Some(binding) => self.bindings.insert(input, binding),
+            None => self.bindings.remove(&input),
+        }
+    }
+    fn inputs_for(&self, binding: &Binding<A, B>) -> Vec<&C> {
+        self.bindings
+            .iter()
+            .filter(|&(_, bound)| bound == binding)
+            .map(|(input, _)| input)
+            .collect()
+    }
+    fn get_bindings(&self) -> &HashMap<C, Binding<A, B>> {
+        &self.bindings
+    }
+    fn add_default_change(&mut self, input: C, change: Change) {
+        self.default_changes.insert(input, change);
+    }
+    fn get_default_change(&self, input: &C) -> Option<&Change> {
+        self.default_changes.get(input)
+    }
+    fn set_input_config(&mut self, input: C, config: InputAxisConfig) {
+        self.input_configs.insert(input, config);
+    }
+    fn get_input_config(&self, input: &C) -> Option<&InputAxisConfig> {
+        self.input_configs.get(input)
+    }
+    /// A negative threshold holds the button while the position is at or
+    /// below it, for binding the low end of an axis.
+    fn set_input_threshold(&mut self, input: C, threshold: f64) {
+        self.input_thresholds.insert(input, threshold);
+    }
+    fn get_input_threshold(&self, input: &C) -> Option<f64> {
+        self.input_thresholds.get(input).cloned()
+    }
+    fn shape_position(&self, input: &C, position: f64) -> f64 {
+        match self.input_configs.get(input) {
+            Some(config) => config.shape(position),
+            None => position,
+        }
+    }
+    fn convert_change(&self, input: &C, change: Change) -> Change {
+        let position = match change {
+            Change::Axis(AxisChange::Position(position)) => self.shape_position(input, position),
+            change => return change,
+        };
+        match self.input_thresholds.get(input) {
+            Some(&threshold) if threshold < 0.0 => ButtonChange(position <= threshold).into(),
+            Some(&threshold) => ButtonChange(position >= threshold).into(),
+            None => AxisChange::Position(position).into(),
+        }
+    }
+    fn set_input_index(&mut self, index: fn(&C) -> usize) {
+        self.input_index = Some(index);
+        self.indexed.clear();
+        let bound: Vec<_> = self
+            .bindings
+            .iter()
+            .map(|(input, binding)| (index(input), binding.clone()))
+            .collect();
+        for (index, binding) in bound {
+            self.set_indexed(index, Some(binding));
+        }
+    }
+}
+
+impl<A: Clone, B: Clone, C> BindingMap<A, B, C> {
+    fn set_indexed(&mut self, index: usize, binding: Option<Binding<A, B>>) {
+        if index >= MAX_INDEXED_INPUTS {
+            return;
+        }
+        if index >= self.indexed.len() {
+            self.indexed.resize(index + 1, None);
+        }
+        self.indexed[index] = binding;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_and_reverse_looks_up() {
+        let mut map: BindingMap<u8, u8, char> = BindingMap::new();
+        assert_eq!(map.set_binding('a', Some(Binding::Button(1))), None);
+        map.set_binding('b', Some(Binding::Button(1)));
+        map.set_binding('c', Some(Binding::Axis(2)));
+        assert_eq!(map.resolve(&'c'), Some(&Binding::Axis(2)));
+        let mut inputs = map.inputs_for(&Binding::Button(1));
+        inputs.sort();
+        assert_eq!(inputs, vec![&'a', &'b']);
+        assert_eq!(map.set_binding('c', None), Some(Binding::Axis(2)));
+        assert_eq!(map.resolve(&'c'), None);
+    }
+
+    indexed_input! {
+        #[derive(Hash, Eq, PartialEq, Clone, Copy, Debug)]
+        enum Pad {
+            North,
+            South,
+            East,
+        }
+    }
+
+    #[test]
+    fn resolves_through_input_index() {
+        let mut map: BindingMap<u8, u8, Pad> = BindingMap::new();
+        map.set_binding(Pad::South, Some(Binding::Button(1)));
+        map.set_input_index(Pad::input_index);
+        map.set_binding(Pad::East, Some(Binding::Axis(2)));
+        assert_eq!(map.resolve(&Pad::South), Some(&Binding::Button(1)));
+        assert_eq!(map.resolve(&Pad::East), Some(&Binding::Axis(2)));
+        assert_eq!(map.resolve(&Pad::North), None);
+        map.set_binding(Pad::South, None);
+        assert_eq!(map.resolve(&Pad::South), None);
+    }
+
+    #[test]
+    fn sparse_indices_fall_back_to_hashing() {
+        let mut map: BindingMap<u8, u8, u32> = BindingMap::new();
+        map.set_input_index(|&input| input as usize);
+        map.set_binding(u32::MAX, Some(Binding::Button(1)));
+        map.set_binding(3, Some(Binding::Axis(2)));
+        assert_eq!(map.resolve(&u32::MAX), Some(&Binding::Button(1)));
+        assert_eq!(map.resolve(&3), Some(&Binding::Axis(2)));
+        assert!(map.indexed.len() <= MAX_INDEXED_INPUTS);
+        map.set_binding(u32::MAX, None);
+        assert_eq!(map.resolve(&u32::MAX), None);
+    }
+
+    #[test]
+    fn converts_positions_past_threshold_to_presses() {
+        let mut map: BindingMap<u8, u8, char> = BindingMap::new();
+        map.set_input_threshold('d', -0.5);
+        let convert = |position| map.convert_change(&'d', AxisChange::Position(position).into());
+        assert_eq!(convert(-0.75), ButtonChange(true).into());
+        assert_eq!(convert(-0.25), ButtonChange(false).into());
+        assert_eq!(
+            map.convert_change(&'x', AxisChange::Position(0.25).into()),
+            AxisChange::Position(0.25).into()
+        );
+    }
+}
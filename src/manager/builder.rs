@@ -1,3 +1,9 @@
+use axis::{AxisConfig, InputAxisConfig};
+use button::ButtonConfig;
+#[cfg(feature = "combo")]
+use combo::{Combo, HoldConfirm};
+#[cfg(feature = "recording")]
+use input_macro::InputMacro;
 use manager::manager::*;
 use std::hash::Hash;
 pub struct ManagerBuilder<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> {
@@ -6,14 +12,33 @@ pub struct ManagerBuilder<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> {
 
 pub trait IManagerBuilder<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> {
     type Product;
-    fn new() -> Self;
+    fn new() -> Self
+    where
+        Self: Sized;
     fn build(self) -> Self::Product;
     fn add_axis_binding(self, axis: A, input: C) -> Self;
     fn add_button_binding(self, button: B, input: C) -> Self;
-    fn add_default_change(self, impl Into<Change>, C) -> Self;
+    fn add_axis_button_binding(self, button: B, input: C, threshold: f64) -> Self;
+    fn add_value_binding(self, value: A, input: C) -> Self;
+    fn add_accumulator_binding(self, accumulator: A, input: C) -> Self;
+    fn add_trigger_binding(self, trigger: A, input: C) -> Self;
+    fn add_axis2d_binding(self, axis: A, input: C) -> Self;
+    fn add_default_change(self, change: Change, input: C) -> Self;
+    fn configure_button(self, button: B, config: ButtonConfig) -> Self;
+    fn configure_axis(self, axis: A, config: AxisConfig) -> Self;
+    fn configure_axis2d(self, axis: A, config: InputAxisConfig) -> Self;
+    #[cfg(feature = "combo")]
+    fn add_combo(self, name: String, combo: Combo<A, B>) -> Self;
+    #[cfg(feature = "combo")]
+    fn add_hold_confirm(self, action: B, confirm: HoldConfirm<B>) -> Self;
+    fn register_action(self, action: Binding<A, B>) -> Self;
+    #[cfg(feature = "recording")]
+    fn add_macro_binding(self, input: C, input_macro: InputMacro<A, B>, speed: f64) -> Self;
+    fn set_sub_tick_ordering(self, ordering: SubTickOrdering) -> Self;
+    fn set_input_delay(self, frames: usize) -> Self;
 }
 
-impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> IManagerBuilder<A, B, C>
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq + Clone> IManagerBuilder<A, B, C>
     for ManagerBuilder<A, B, C>
 {
     type Product = Manager<A, B, C>;
@@ -33,9 +58,82 @@ impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> IManagerBuilder<A
         self.manager.add_button_binding(button, input);
         self
     }
+    fn add_axis_button_binding(mut self, button: B, input: C, threshold: f64) -> Self {
+        self.manager
+            .add_axis_button_binding(button, input, threshold);
+        self
+    }
+
+    fn add_value_binding(mut self, value: A, input: C) -> Self {
+        self.manager.add_value_binding(value, input);
+        self
+    }
 
-    fn add_default_change(mut self, change: impl Into<Change>, input: C) -> Self {
+    fn add_accumulator_binding(mut self, accumulator: A, input: C) -> Self {
+        self.manager.add_accumulator_binding(accumulator, input);
+        self
+    }
+
+    fn add_trigger_binding(mut self, trigger: A, input: C) -> Self {
+        self.manager.add_trigger_binding(trigger, input);
+        self
+    }
+
+    fn add_axis2d_binding(mut self, axis: A, input: C) -> Self {
+        self.manager.add_axis2d_binding(axis, input);
+        self
+    }
+
+    fn add_default_change(mut self, change: Change, input: C) -> Self {
         self.manager.add_default_change(change, input);
         self
     }
+
+    fn configure_button(mut self, button: B, config: ButtonConfig) -> Self {
+        self.manager.configure_button(button, config);
+        self
+    }
+
+    fn configure_axis(mut self, axis: A, config: AxisConfig) -> Self {
+        self.manager.configure_axis(axis, config);
+        self
+    }
+
+    fn configure_axis2d(mut self, axis: A, config: InputAxisConfig) -> Self {
+        self.manager.configure_axis2d(axis, config);
+        self
+    }
+
+    #[cfg(feature = "combo")]
+    fn add_combo(mut self, name: String, combo: Combo<A, B>) -> Self {
+        self.manager.add_combo(name, combo);
+        self
+    }
+
+    #[cfg(feature = "combo")]
+    fn add_hold_confirm(mut self, action: B, confirm: HoldConfirm<B>) -> Self {
+        self.manager.add_hold_confirm(action, confirm);
+        self
+    }
+
+    fn register_action(mut self, action: Binding<A, B>) -> Self {
+        self.manager.register_action(action);
+        self
+    }
+
+    #[cfg(feature = "recording")]
+    fn add_macro_binding(mut self, input: C, input_macro: InputMacro<A, B>, speed: f64) -> Self {
+        self.manager.add_macro_binding(input, input_macro, speed);
+        self
+    }
+
+    fn set_sub_tick_ordering(mut self, ordering: SubTickOrdering) -> Self {
+        self.manager.set_sub_tick_ordering(ordering);
+        self
+    }
+
+    fn set_input_delay(mut self, frames: usize) -> Self {
+        self.manager.set_input_delay(frames);
+        self
+    }
 }
@@ -0,0 +1,212 @@
+use accumulator::*;
+use axis::*;
+use axis2d::*;
+use button::*;
+use manager::manager::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::Duration;
+use trigger::*;
+use value::*;
+
+/// State storage for engines that already resolve physical inputs to
+/// actions. States are created the first time an action receives a change.
+#[derive(Debug, Clone)]
+pub struct DirectManager<A: Hash + Eq, B: Hash + Eq> {
+    states: HashMap<Binding<A, B>, State>,
+}
+
+pub trait IDirectManager<A, B> {
+    fn new() -> Self
+    where
+        Self: Sized;
+    fn apply_change(&mut self, binding: &Binding<A, B>, change: Change);
+    fn tick(&mut self, delta: Duration);
+    fn configure_button(&mut self, button: B, config: ButtonConfig);
+    fn configure_axis(&mut self, axis: A, config: AxisConfig);
+    fn get_axis(&self, axis: A) -> Option<&Axis>;
+    fn get_button(&self, button: B) -> Option<&Button>;
+    fn get_value(&self, value: A) -> Option<&Value>;
+    fn get_trigger(&self, trigger: A) -> Option<&Trigger>;
+    fn get_axis2d(&self, axis: A) -> Option<&Axis2d>;
+    fn get_button_pressed(&self, button: B) -> bool;
+    fn get_button_released(&self, button: B) -> bool;
+    fn get_states(&self) -> &HashMap<Binding<A, B>, State>;
+    fn axes(&self) -> StateIter<'_, A, Axis>;
+    fn buttons(&self) -> StateIter<'_, B, Button>;
+    fn values(&self) -> StateIter<'_, A, Value>;
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone> IDirectManager<A, B> for DirectManager<A, B> {
+    fn new() -> Self {
+        DirectManager {
+            states: HashMap::new(),
+        }
+    }
+    fn apply_change(&mut self, binding: &Binding<A, B>, change: Change) {
+        match (self.states.get_mut(binding), change) {
+            (Some(State::Axis(axis)), Change::Axis(change)) => axis.apply(change),
+            (Some(State::Button(button)), Change::Button(change)) => button.apply(change),
+            (Some(State::Button(button)), Change::Pressure(change)) => {
+                button.apply_pressure(change)
+            }
+            (Some(State::Value(value)), Change::Value(change)) => value.apply(change),
+            (Some(State::Accumulator(accumulator)), Change::Value(change)) => {
+                accumulator.apply(change)
+            }
+            (None, Change::Axis(change)) => {
+                let mut axis = Axis::default();
+                axis.apply(change);
+                self.states.insert(binding.clone(), State::Axis(axis));
+            }
+            (None, Change::Button(change)) => {
+                let mut button = Button::default();
+                button.apply(change);
+                self.states.insert(binding.clone(), State::Button(button));
+            }
+            (Some(State::Trigger(trigger)), Change::Trigger(change)) => trigger.apply(change),
+            (Some(State::Axis2d(axis2d)), Change::Axis2d(change)) => axis2d.apply(change),
+            (None, Change::Axis2d(change)) => {
+                let mut axis2d = Axis2d::default();
+                axis2d.apply(change);
+                self.states.insert(binding.clone(), State::Axis2d(axis2d));
+            }
+            (None, Change::Trigger(change)) => {
+                let mut trigger = Trigger::default();
+                trigger.apply(change);
+                self.states.insert(binding.clone(), State::Trigger(trigger));
+            }
+            (None, Change::Pressure(change)) => {
+                let mut button = Button::default();
+                button.apply_pressure(change);
+                self.states.insert(binding.clone(), State::Button(button));
+            }
+            (None, Change::Value(change)) => {
+                let state = match binding {
+                    Binding::Accumulator(_) => {
+                        let mut accumulator = Accumulator::default();
+                        accumulator.apply(change);
+                        State::Accumulator(accumulator)
+                    }
+                    _ => {
+                        let mut value = Value::default();
+                        value.apply(change);
+                        State::Value(value)
+                    }
+                };
+                self.states.insert(binding.clone(), state);
+            }
+            _ => {}
+        }
+    }
+    fn tick(&mut self, delta: Duration) {
+        for state in self.states.values_mut() {
+            match state {
+                State::Axis(axis) => axis.tick(delta),
+                State::Button(button) => button.tick(delta),
+                State::Value(value) => value.tick(delta),
+                State::Accumulator(accumulator) => accumulator.tick(delta),
+                State::Trigger(trigger) => trigger.tick(delta),
+                State::Axis2d(axis2d) => axis2d.tick(delta),
+            }
+        }
+    }
+    fn configure_button(&mut self, button: B, config: ButtonConfig) {
+        if let State::Button(button) = self
+            .states
+            .entry(Binding::Button(button))
+            .or_insert_with(|| State::Button(Button::default()))
+        {
+            button.configure(config);
+        }
+    }
+    fn configure_axis(&mut self, axis: A, config: AxisConfig) {
+        if let State::Axis(axis) = self
+            .states
+            .entry(Binding::Axis(axis))
+            .or_insert_with(|| State::Axis(Axis::default()))
+        {
+            axis.configure(config);
+        }
+    }
+    fn get_axis(&self, axis: A) -> Option<&Axis> {
+        match self.states.get(&Binding::Axis(axis)) {
+            Some(State::Axis(axis)) => Some(axis),
+            _ => None,
+        }
+    }
+    fn get_button(&self, button: B) -> Option<&Button> {
+        match self.states.get(&Binding::Button(button)) {
+            Some(State::Button(button)) => Some(button),
+            _ => None,
+        }
+    }
+    fn get_value(&self, value: A) -> Option<&Value> {
+        match self.states.get(&Binding::Value(value)) {
+            Some(State::Value(value)) => Some(value),
+            _ => None,
+        }
+    }
+    fn get_trigger(&self, trigger: A) -> Option<&Trigger> {
+        match self.states.get(&Binding::Trigger(trigger)) {
+            Some(State::Trigger(trigger)) => Some(trigger),
+            _ => None,
+        }
+    }
+    fn get_axis2d(&self, axis: A) -> Option<&Axis2d> {
+        match self.states.get(&Binding::Axis2d(axis)) {
+            Some(State::Axis2d(axis2d)) => Some(axis2d),
+            _ => None,
+        }
+    }
+    fn get_button_pressed(&self, button: B) -> bool {
+        self.get_button(button).is_some_and(|button| button.fired())
+    }
+    fn get_button_released(&self, button: B) -> bool {
+        self.get_button(button)
+            .is_some_and(|button| button.just_released)
+    }
+    fn get_states(&self) -> &HashMap<Binding<A, B>, State> {
+        &self.states
+    }
+    fn axes(&self) -> StateIter<'_, A, Axis> {
+        Box::new(self.states.iter().filter_map(|entry| match entry {
+            (Binding::Axis(key), State::Axis(axis)) => Some((key, axis)),
+            _ => None,
+        }))
+    }
+    fn buttons(&self) -> StateIter<'_, B, Button> {
+        Box::new(self.states.iter().filter_map(|entry| match entry {
+            (Binding::Button(key), State::Button(button)) => Some((key, button)),
+            _ => None,
+        }))
+    }
+    fn values(&self) -> StateIter<'_, A, Value> {
+        Box::new(self.states.iter().filter_map(|entry| match entry {
+            (Binding::Value(key), State::Value(value)) => Some((key, value)),
+            _ => None,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use change::{AxisChange, ButtonChange, Direction};
+
+    #[test]
+    fn creates_states_on_first_change() {
+        let mut manager: DirectManager<&str, &str> = DirectManager::new();
+        manager.apply_change(&Binding::Button("jump"), ButtonChange(true).into());
+        manager.apply_change(
+            &Binding::Axis("move"),
+            AxisChange::Velocity(Direction::Up).into(),
+        );
+        assert!(manager.get_button_pressed("jump"));
+        manager.tick(Duration::from_millis(100));
+        assert!(!manager.get_button_pressed("jump"));
+        assert!(manager.get_button("jump").unwrap().pressed);
+        assert!(manager.get_axis("move").unwrap().position > 0.0);
+        assert_eq!(manager.get_axis("jump"), None);
+    }
+}
@@ -0,0 +1,94 @@
+use axis::*;
+use button::*;
+use interner::*;
+use manager::manager::*;
+
+/// A manager keyed by names chosen at runtime, e.g. from a config file or a
+/// scripting layer. Names are interned on registration and the returned
+/// handles should be kept for per-frame calls.
+pub struct DynamicManager {
+    manager: Manager<u32, u32, u32>,
+    actions: Interner,
+    inputs: Interner,
+}
+
+pub trait IDynamicManager {
+    fn new() -> Self
+    where
+        Self: Sized;
+    fn add_axis_binding(&mut self, axis: &str, input: &str) -> u32;
+    fn add_button_binding(&mut self, button: &str, input: &str) -> u32;
+    fn action_handle(&self, name: &str) -> Option<u32>;
+    fn input_handle(&self, name: &str) -> Option<u32>;
+    fn action_name(&self, handle: u32) -> Option<&str>;
+    fn apply_input(&mut self, input: u32, change: Change) -> Result<(), InputError>;
+    fn get_axis(&self, axis: u32) -> Option<&Axis>;
+    fn get_button(&self, button: u32) -> Option<&Button>;
+    fn get_manager(&self) -> &Manager<u32, u32, u32>;
+    fn get_manager_mut(&mut self) -> &mut Manager<u32, u32, u32>;
+}
+
+impl IDynamicManager for DynamicManager {
+    fn new() -> Self {
+        DynamicManager {
+            manager: Manager::new(),
+            actions: Interner::new(),
+            inputs: Interner::new(),
+        }
+    }
+    fn add_axis_binding(&mut self, axis: &str, input: &str) -> u32 {
+        let axis = self.actions.intern(axis);
+        let input = self.inputs.intern(input);
+        self.manager.add_axis_binding(axis, input);
+        axis
+    }
+    fn add_button_binding(&mut self, button: &str, input: &str) -> u32 {
+        let button = self.actions.intern(button);
+        let input = self.inputs.intern(input);
+        self.manager.add_button_binding(button, input);
+        button
+    }
+    fn action_handle(&self, name: &str) -> Option<u32> {
+        self.actions.get(name)
+    }
+    fn input_handle(&self, name: &str) -> Option<u32> {
+        self.inputs.get(name)
+    }
+    fn action_name(&self, handle: u32) -> Option<&str> {
+        self.actions.name(handle)
+    }
+    fn apply_input(&mut self, input: u32, change: Change) -> Result<(), InputError> {
+        self.manager.process_input(input, change).map(|_| ())
+    }
+    fn get_axis(&self, axis: u32) -> Option<&Axis> {
+        self.manager.get_axis(axis)
+    }
+    fn get_button(&self, button: u32) -> Option<&Button> {
+        self.manager.get_button(button)
+    }
+    fn get_manager(&self) -> &Manager<u32, u32, u32> {
+        &self.manager
+    }
+    fn get_manager_mut(&mut self) -> &mut Manager<u32, u32, u32> {
+        &mut self.manager
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use change::ButtonChange;
+
+    #[test]
+    fn routes_interned_inputs() {
+        let mut manager = DynamicManager::new();
+        let jump = manager.add_button_binding("jump", "space");
+        let space = manager.input_handle("space").unwrap();
+        manager
+            .apply_input(space, ButtonChange(true).into())
+            .unwrap();
+        assert!(manager.get_button(jump).unwrap().pressed);
+        assert_eq!(manager.action_handle("jump"), Some(jump));
+        assert_eq!(manager.action_name(jump), Some("jump"));
+    }
+}
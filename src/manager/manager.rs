@@ -1,14 +1,58 @@
+use accumulator::*;
+#[cfg(feature = "animation")]
+use animation::*;
+#[cfg(feature = "audit")]
+use audit::*;
 use axis::*;
+use axis2d::*;
+use binding_edit::*;
 use button::*;
-use change::{AxisChange, ButtonChange, Direction};
-use std::collections::HashMap;
+#[cfg(feature = "contexts")]
+use change::Direction;
+use change::{Axis2dChange, AxisChange, ButtonChange, PressureChange, TriggerChange, ValueChange};
+#[cfg(feature = "combo")]
+use combo::*;
+#[cfg(feature = "custom_state")]
+use custom_state::*;
+#[cfg(feature = "device")]
+use device::*;
+#[cfg(feature = "drift")]
+use drift::*;
+#[cfg(feature = "encoding")]
+use encoding::{BitReader, BitWriter, Decode, DecodeError, Encode};
+#[cfg(feature = "export")]
+use export::*;
+#[cfg(feature = "filter")]
+use filter::*;
+#[cfg(feature = "frame_clock")]
+use frame_clock::*;
+#[cfg(feature = "heatmap")]
+use heatmap::*;
+#[cfg(feature = "recording")]
+use input_macro::*;
+use manager::arena::*;
+use manager::binding_map::*;
+#[cfg(feature = "polling")]
+use polling::*;
+#[cfg(feature = "profile")]
+use profile::*;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
+use std::mem::discriminant;
 use std::time::Duration;
+#[cfg(feature = "timing")]
+use timing::*;
+use trigger::*;
+use value::*;
 
-#[derive(Clone)]
+#[derive(Debug, PartialEq, Clone, Hash)]
 pub enum Change {
     Axis(AxisChange),
     Button(ButtonChange),
+    Value(ValueChange),
+    Pressure(PressureChange),
+    Trigger(TriggerChange),
+    Axis2d(Axis2dChange),
 }
 
 impl Into<Change> for AxisChange {
@@ -23,264 +67,4116 @@ impl Into<Change> for ButtonChange {
     }
 }
 
+impl From<ValueChange> for Change {
+    fn from(change: ValueChange) -> Change {
+        Change::Value(change)
+    }
+}
+
+impl From<PressureChange> for Change {
+    fn from(change: PressureChange) -> Change {
+        Change::Pressure(change)
+    }
+}
+
+impl From<TriggerChange> for Change {
+    fn from(change: TriggerChange) -> Change {
+        Change::Trigger(change)
+    }
+}
+
+impl From<Axis2dChange> for Change {
+    fn from(change: Axis2dChange) -> Change {
+        Change::Axis2d(change)
+    }
+}
+
+impl Change {
+    /// How much activity the change represents, 1 for a press or a started
+    /// movement and 0 for releases.
+    pub fn magnitude(&self) -> f64 {
+        match *self {
+            Change::Axis(AxisChange::Position(position)) => position.abs(),
+            Change::Axis(AxisChange::Velocity(_)) => 1.0,
+            Change::Axis(AxisChange::Falling(_)) => 0.0,
+            Change::Button(ButtonChange(pressed)) => {
+                if pressed {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Change::Value(ValueChange::Set(value)) | Change::Value(ValueChange::Offset(value)) => {
+                value.abs()
+            }
+            Change::Pressure(PressureChange(pressure))
+            | Change::Trigger(TriggerChange(pressure)) => pressure,
+            Change::Axis2d(Axis2dChange { x, y }) => x.hypot(y).min(1.0),
+        }
+    }
+}
+
 #[derive(Hash, Eq, PartialEq, Clone, Debug)]
 pub enum Binding<A, B> {
     Axis(A),
     Button(B),
+    Value(A),
+    Accumulator(A),
+    Trigger(A),
+    Axis2d(A),
+}
+
+impl<A: PartialEq, B> Binding<A, B> {
+    pub fn accepts(&self, change: &Change) -> bool {
+        matches!(
+            (self, change),
+            (Binding::Axis(_), Change::Axis(_))
+                | (Binding::Button(_), Change::Button(_) | Change::Pressure(_))
+                | (Binding::Value(_), Change::Value(_))
+                | (Binding::Accumulator(_), Change::Value(_))
+                | (Binding::Trigger(_), Change::Trigger(_))
+                | (Binding::Axis2d(_), Change::Axis2d(_))
+        )
+    }
+    /// Axes and values share the `A` id space, so one id can't be both.
+    pub fn conflicts_with(&self, other: &Binding<A, B>) -> bool {
+        match (self.axis_id(), other.axis_id()) {
+            (Some(a), Some(b)) => a == b && discriminant(self) != discriminant(other),
+            _ => false,
+        }
+    }
+    fn axis_id(&self) -> Option<&A> {
+        match self {
+            Binding::Axis(id)
+            | Binding::Value(id)
+            | Binding::Accumulator(id)
+            | Binding::Trigger(id)
+            | Binding::Axis2d(id) => Some(id),
+            Binding::Button(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InputError {
+    /// The change is for a different kind of action than the binding, such
+    /// as an axis change applied to a button.
+    ChangeKindMismatch,
+}
+
+/// A registration the manager refused because it would give an action or an
+/// input two incompatible kinds.
+#[derive(Debug, PartialEq, Clone)]
+pub enum RegistrationConflict<A, B, C> {
+    ActionKind {
+        existing: Binding<A, B>,
+        conflicting: Binding<A, B>,
+    },
+    DefaultChange {
+        input: C,
+        binding: Binding<A, B>,
+        change: Change,
+    },
 }
 
+#[derive(Debug, PartialEq, Clone, Hash)]
 pub enum State {
     Axis(Axis),
     Button(Button),
+    Value(Value),
+    Accumulator(Accumulator),
+    Trigger(Trigger),
+    Axis2d(Axis2d),
+}
+
+impl State {
+    /// The state as a single float: the axis output, the button or trigger
+    /// pressure, the stick magnitude or the stored value.
+    pub fn level(&self) -> f64 {
+        match self {
+            State::Axis(axis) => axis.output(),
+            State::Button(button) => button.value,
+            State::Value(value) => value.value,
+            State::Accumulator(accumulator) => accumulator.total,
+            State::Trigger(trigger) => trigger.value,
+            State::Axis2d(axis2d) => axis2d.magnitude(),
+        }
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl Encode for State {
+    fn encode(&self, writer: &mut BitWriter) {
+        match self {
+            State::Axis(axis) => {
+                writer.write_bits(0, 3);
+                axis.encode(writer);
+            }
+            State::Button(button) => {
+                writer.write_bits(1, 3);
+                button.encode(writer);
+            }
+            State::Value(value) => {
+                writer.write_bits(2, 3);
+                value.encode(writer);
+            }
+            State::Accumulator(accumulator) => {
+                writer.write_bits(3, 3);
+                accumulator.encode(writer);
+            }
+            State::Trigger(trigger) => {
+                writer.write_bits(4, 3);
+                trigger.encode(writer);
+            }
+            State::Axis2d(axis2d) => {
+                writer.write_bits(5, 3);
+                axis2d.encode(writer);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl Decode for State {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        match reader.read_bits(3)? {
+            0 => Ok(State::Axis(Axis::decode(reader)?)),
+            1 => Ok(State::Button(Button::decode(reader)?)),
+            2 => Ok(State::Value(Value::decode(reader)?)),
+            3 => Ok(State::Accumulator(Accumulator::decode(reader)?)),
+            4 => Ok(State::Trigger(Trigger::decode(reader)?)),
+            5 => Ok(State::Axis2d(Axis2d::decode(reader)?)),
+            tag => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+}
+
+/// Which delta an action's state advances by. `Gameplay` states use the
+/// delta scaled by the manager's time scale, `Realtime` states the raw delta
+/// passed to `tick`, so menus keep working during slow motion or pause.
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+pub enum ClockDomain {
+    #[default]
+    Gameplay,
+    Realtime,
+}
+
+/// How timestamped changes are applied. `Ordered` queues them until `tick`
+/// and applies them in timestamp order, `Integrated` additionally advances
+/// each axis by the time between its changes.
+#[derive(Default, Debug, PartialEq, Clone)]
+pub enum SubTickOrdering {
+    #[default]
+    Immediate,
+    Ordered,
+    Integrated,
+}
+
+/// Everything needed to restore the manager to an earlier frame.
+#[derive(Clone)]
+pub struct Snapshot<A: Hash + Eq, B: Hash + Eq> {
+    states: StateArena<A, B>,
+    #[cfg(feature = "combo")]
+    combos: ComboRegistry<A, B>,
+    queued_changes: Vec<(Binding<A, B>, Change, Duration)>,
+    delayed_changes: VecDeque<DelayedFrame<A, B>>,
+    press_order: Vec<B>,
+}
+
+/// Combo definitions are included, but custom response curves aren't and
+/// make decoding fail.
+#[cfg(feature = "encoding")]
+impl<A: Hash + Eq + Encode, B: Hash + Eq + Encode> Encode for Snapshot<A, B> {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.states.encode(writer);
+        #[cfg(feature = "combo")]
+        self.combos.encode(writer);
+        self.queued_changes.encode(writer);
+        self.delayed_changes.encode(writer);
+        self.press_order.encode(writer);
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl<A: Hash + Eq + Clone + Decode, B: Hash + Eq + Clone + Decode> Decode for Snapshot<A, B> {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(Snapshot {
+            states: StateArena::decode(reader)?,
+            #[cfg(feature = "combo")]
+            combos: ComboRegistry::decode(reader)?,
+            queued_changes: Vec::decode(reader)?,
+            delayed_changes: VecDeque::decode(reader)?,
+            press_order: Vec::decode(reader)?,
+        })
+    }
+}
+
+/// A complete save of the manager's input state for emulator-style save
+/// states: the rollback snapshot plus macro recording and playback progress,
+/// the current frame's changes and the frame clock.
+#[derive(Clone)]
+pub struct SaveState<A: Hash + Eq, B: Hash + Eq> {
+    snapshot: Snapshot<A, B>,
+    #[cfg(feature = "recording")]
+    macro_recorder: Option<MacroRecorder<A, B>>,
+    #[cfg(feature = "recording")]
+    macro_player: MacroPlayer<A, B>,
+    frame_changes: Vec<(Binding<A, B>, Change)>,
+    last_frame: InputFrame<A, B>,
+    #[cfg(feature = "frame_clock")]
+    frame_clock: FrameClock,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InputFrame<A, B> {
+    pub changes: Vec<(Binding<A, B>, Change)>,
+    pub delta: Duration,
+}
+
+/// How far one consumer has read the manager's changes. Each consumer keeps
+/// its own, so readers don't depend on running before `tick`.
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+pub struct ChangeCursor {
+    seen: u64,
+}
+
+/// What a scope does with its held inputs across a focus change. `Release`
+/// releases them when focus is lost, `CarryOver` leaves them held but still
+/// lets releases through while unfocused, and `Reevaluate` releases them and
+/// replays whatever is still held when focus returns.
+#[cfg(feature = "contexts")]
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+pub enum TransitionPolicy {
+    #[default]
+    Release,
+    CarryOver,
+    Reevaluate,
+}
+
+#[cfg(feature = "contexts")]
+#[derive(Debug, PartialEq, Clone)]
+pub enum ScopeTransition {
+    Enter(String),
+    Exit(String),
+}
+
+#[cfg(feature = "contexts")]
+#[derive(Debug)]
+struct FocusScope<A, B> {
+    focused: bool,
+    actions: Vec<Binding<A, B>>,
+    policy: TransitionPolicy,
+}
+
+/// The physical input, and its device class when a classifier is set, that
+/// last changed an action.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ChangeSource<C> {
+    pub input: C,
+    #[cfg(feature = "device")]
+    pub device: Option<DeviceClass>,
+}
+
+/// How quickly an analog button travelled from rest to its press threshold,
+/// `Strong` when within the configured window.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum PressStrength {
+    Strong,
+    Weak,
+}
+
+/// An applied change together with the input that caused it. `source` is
+/// `None` for changes applied directly to an action.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ActionEvent<A, B, C> {
+    pub action: Binding<A, B>,
+    pub change: Change,
+    pub source: Option<ChangeSource<C>>,
+}
+
+/// A timestamped change to a budgeted action that reached a tick later
+/// than the action's budget allows.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LatencyWarning<A, B> {
+    pub action: Binding<A, B>,
+    pub latency: Duration,
+    pub budget: Duration,
 }
 
 pub struct Manager<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> {
-    states: HashMap<Binding<A, B>, State>,
-    bindings: HashMap<C, Binding<A, B>>,
-    default_changes: HashMap<C, Change>,
+    states: StateArena<A, B>,
+    bindings: BindingMap<A, B, C>,
+    #[cfg(feature = "combo")]
+    combos: ComboRegistry<A, B>,
+    #[cfg(feature = "recording")]
+    macro_recorder: Option<MacroRecorder<A, B>>,
+    #[cfg(feature = "recording")]
+    macro_bindings: HashMap<C, (InputMacro<A, B>, f64)>,
+    #[cfg(feature = "recording")]
+    macro_player: MacroPlayer<A, B>,
+    #[cfg(feature = "recording")]
+    held_macro_inputs: HashSet<C>,
+    binding_undo: Vec<BindingMutation<A, B, C>>,
+    binding_redo: Vec<BindingMutation<A, B, C>>,
+    sub_tick_ordering: SubTickOrdering,
+    queued_changes: Vec<(Binding<A, B>, Change, Duration)>,
+    #[cfg(feature = "polling")]
+    poller: Option<Poller<C>>,
+    #[cfg(feature = "polling")]
+    poll_errors: Vec<(C, InputError)>,
+    input_delay: usize,
+    delayed_changes: VecDeque<DelayedFrame<A, B>>,
+    #[cfg(feature = "audit")]
+    audit: Option<AuditTrace>,
+    #[cfg(feature = "heatmap")]
+    heatmap: Option<Heatmap<A, B>>,
+    frame_changes: Vec<(Binding<A, B>, Change)>,
+    last_frame: InputFrame<A, B>,
+    #[cfg(feature = "drift")]
+    drift_detectors: HashMap<A, DriftDetector>,
+    #[cfg(feature = "timing")]
+    button_timings: HashMap<B, ButtonTimings>,
+    #[cfg(feature = "combo")]
+    hold_confirms: Vec<(B, HoldConfirm<B>)>,
+    #[cfg(feature = "export")]
+    event_log: Option<Box<dyn IEventLog<A, B>>>,
+    frame: u64,
+    paused: bool,
+    stepping: bool,
+    paused_changes: DelayedFrame<A, B>,
+    action_remap: HashMap<Binding<A, B>, Binding<A, B>>,
+    clock: Duration,
+    press_rises: HashMap<C, Option<Duration>>,
+    threshold_held: HashSet<C>,
+    press_speeds: HashMap<B, Duration>,
+    strong_press_window: Duration,
+    #[cfg(feature = "combo")]
+    confirmed: Vec<B>,
+    #[cfg(feature = "device")]
+    device_classifier: Option<fn(&C) -> DeviceClass>,
+    #[cfg(feature = "device")]
+    device_tracker: ActiveDeviceTracker,
+    #[cfg(feature = "contexts")]
+    focus_scopes: HashMap<String, FocusScope<A, B>>,
+    #[cfg(feature = "contexts")]
+    suppressed_changes: Vec<(Binding<A, B>, Change)>,
+    #[cfg(feature = "contexts")]
+    pending_scope_transitions: Vec<ScopeTransition>,
+    #[cfg(feature = "contexts")]
+    scope_transitions: Vec<ScopeTransition>,
+    #[cfg(feature = "frame_clock")]
+    frame_clock: FrameClock,
+    #[cfg(feature = "filter")]
+    change_filters: Vec<Box<dyn ChangeFilter<A, B>>>,
+    #[cfg(feature = "filter")]
+    state_processors: Vec<(Binding<A, B>, Box<dyn StateProcessor>)>,
+    #[cfg(feature = "custom_state")]
+    custom_states: HashMap<String, Box<dyn CustomState>>,
+    #[cfg(feature = "custom_state")]
+    custom_bindings: HashMap<C, (String, usize)>,
+    registered_actions: Vec<Binding<A, B>>,
+    registration_conflicts: Vec<RegistrationConflict<A, B, C>>,
+    change_seq: u64,
+    last_changed: HashMap<Binding<A, B>, u64>,
+    press_order: Vec<B>,
+    manual_frames: bool,
+    last_sources: HashMap<Binding<A, B>, ChangeSource<C>>,
+    pending_source: Option<ChangeSource<C>>,
+    action_events: Option<Vec<ActionEvent<A, B, C>>>,
+    clock_domains: HashMap<Binding<A, B>, ClockDomain>,
+    time_scale: f64,
+    activity_weights: HashMap<Binding<A, B>, f64>,
+    latency_budgets: HashMap<Binding<A, B>, Duration>,
+    pending_latencies: Vec<(Binding<A, B>, Duration)>,
+    latency_warnings: Vec<LatencyWarning<A, B>>,
+    #[cfg(feature = "animation")]
+    animation_parameters: HashMap<String, (Binding<A, B>, AnimationParameter)>,
 }
 
+/// Boxed so the manager traits stay usable as trait objects.
+pub type StateIter<'a, K, S> = Box<dyn Iterator<Item = (&'a K, &'a S)> + 'a>;
+
+type DelayedFrame<A, B> = Vec<(Binding<A, B>, Change, Option<Duration>)>;
+
+type BindingMutation<A, B, C> = Vec<(C, Option<Binding<A, B>>, Option<Binding<A, B>>)>;
+
 pub trait IManager<A, B, C> {
-    fn new() -> Self;
+    fn new() -> Self
+    where
+        Self: Sized;
     fn get_axis(&self, binding: A) -> Option<&Axis>;
     fn get_button(&self, binding: B) -> Option<&Button>;
-    fn get_states(&self) -> &HashMap<Binding<A, B>, State>;
+    fn get_value(&self, binding: A) -> Option<&Value>;
+    fn get_accumulator(&self, binding: A) -> Option<&Accumulator>;
+    fn get_trigger(&self, binding: A) -> Option<&Trigger>;
+    fn get_axis2d(&self, binding: A) -> Option<&Axis2d>;
+    fn get_states(&self) -> &StateArena<A, B>;
+    fn axis_handle(&self, axis: A) -> Option<AxisHandle>;
+    fn button_handle(&self, button: B) -> Option<ButtonHandle>;
+    fn value_handle(&self, value: A) -> Option<ValueHandle>;
+    fn get_axis_by_handle(&self, handle: AxisHandle) -> Option<&Axis>;
+    fn get_button_by_handle(&self, handle: ButtonHandle) -> Option<&Button>;
+    fn get_value_by_handle(&self, handle: ValueHandle) -> Option<&Value>;
+    fn axes(&self) -> StateIter<'_, A, Axis>;
+    fn buttons(&self) -> StateIter<'_, B, Button>;
+    fn values(&self) -> StateIter<'_, A, Value>;
     fn get_changed_buttons(&self) -> HashMap<&B, &Button>;
+    fn held_buttons_ordered(&self) -> &[B];
     fn get_button_pressed(&self, B) -> bool;
+    fn get_button_released(&self, button: B) -> bool;
+    fn get_button_held_duration(&self, button: B) -> Duration;
+    fn get_button_value(&self, button: B) -> f64;
+    #[cfg(feature = "combo")]
+    fn get_completed_combos(&self) -> &[String];
+    fn get_button_edges(&self, button: B) -> &[ButtonEdge];
+    fn get_cooldown_remaining(&self, button: B) -> Duration;
+    fn get_axis_activity(&self, axis: A) -> Option<AxisActivity>;
+    fn get_zone_events(&self, axis: A) -> &[ZoneEvent];
+    fn get_axis_in_zone(&self, axis: A, zone: &str) -> bool;
+    /// The axis position through its response curve.
+    fn get_axis_output(&self, axis: A) -> f64;
+    #[cfg(feature = "drift")]
+    fn get_drift_report(&self, axis: A) -> Option<DriftReport>;
+    #[cfg(feature = "timing")]
+    fn get_button_timing(&self, button: B) -> Option<&ButtonTimings>;
+    /// Time the last analog press took from rest to its threshold.
+    fn get_press_speed(&self, button: B) -> Option<Duration>;
+    fn get_press_strength(&self, button: B) -> Option<PressStrength>;
 }
 
 pub trait IConverter<A, B, C> {
     fn add_axis_binding(&mut self, axis: A, input: C);
     fn get_binding(&self, C) -> Option<Binding<A, B>>;
+    /// Resolves the input's binding and applies the change to it, returning
+    /// whether the input was bound.
+    fn process_input<T: Into<Change>>(&mut self, input: C, change: T) -> Result<bool, InputError>
+    where
+        Self: Sized;
     fn add_button_binding(&mut self, button: B, input: C);
+    fn add_axis_button_binding(&mut self, button: B, input: C, threshold: f64);
+    fn add_value_binding(&mut self, value: A, input: C);
+    fn add_accumulator_binding(&mut self, accumulator: A, input: C);
+    fn add_trigger_binding(&mut self, trigger: A, input: C);
+    fn add_axis2d_binding(&mut self, axis: A, input: C);
     fn get_default_change(&self, C) -> Option<Change>;
-    fn add_default_change(&mut self, impl Into<Change>, C);
+    fn add_default_change(&mut self, change: Change, input: C);
+    fn configure_button(&mut self, button: B, config: ButtonConfig);
+    /// Returns false if the axis rejected the config.
+    fn configure_axis(&mut self, axis: A, config: AxisConfig) -> bool;
+    fn configure_axis2d(&mut self, axis: A, config: InputAxisConfig);
+    #[cfg(feature = "combo")]
+    fn add_combo(&mut self, name: String, combo: Combo<A, B>);
+    #[cfg(feature = "combo")]
+    fn add_hold_confirm(&mut self, action: B, confirm: HoldConfirm<B>);
+    #[cfg(feature = "drift")]
+    fn watch_axis_drift(&mut self, axis: A, config: DriftConfig);
+    #[cfg(feature = "timing")]
+    fn watch_button_timing(&mut self, button: B, window: usize);
+    fn get_binding_map(&self) -> &BindingMap<A, B, C>;
+    fn set_binding_map(&mut self, binding_map: BindingMap<A, B, C>);
+    fn get_registration_conflicts(&self) -> &[RegistrationConflict<A, B, C>];
+    fn set_input_index(&mut self, index: fn(&C) -> usize);
+    fn configure_input_axis(&mut self, input: C, config: InputAxisConfig);
+    /// Sends everything bound to `from` to `to` instead, without touching the
+    /// physical bindings. Refused when the actions are different kinds.
+    fn remap_action(&mut self, from: Binding<A, B>, to: Binding<A, B>) -> bool;
+    fn remove_action_remap(&mut self, from: &Binding<A, B>);
+    fn set_strong_press_window(&mut self, window: Duration);
+}
+
+pub trait IBindingEdits<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> {
+    fn begin_binding_edit(&self) -> BindingEdit<A, B, C>;
+    fn commit_binding_edit(&mut self, edit: BindingEdit<A, B, C>);
+    fn undo_binding_change(&mut self) -> bool;
+    fn redo_binding_change(&mut self) -> bool;
+    #[cfg(feature = "profile")]
+    fn get_binding_profile(&self) -> BindingProfile<A, B, C>;
+    #[cfg(feature = "profile")]
+    fn load_binding_profile(&mut self, profile: &BindingProfile<A, B, C>);
+}
+
+#[cfg(feature = "recording")]
+pub trait IMacros<A, B, C> {
+    fn start_macro_recording(&mut self);
+    fn stop_macro_recording(&mut self) -> Option<InputMacro<A, B>>;
+    /// Plays `input_macro` whenever `input` is pressed. Returns false, leaving
+    /// the bindings unchanged, when `speed` is negative or not finite.
+    fn add_macro_binding(&mut self, input: C, input_macro: InputMacro<A, B>, speed: f64) -> bool;
+    fn play_macro(&mut self, input: C) -> bool;
+}
+
+#[cfg(feature = "polling")]
+pub trait IPolling<C> {
+    fn start_polling(&mut self, sources: Vec<Box<dyn InputSource<C>>>, interval: Duration);
+    fn stop_polling(&mut self);
+    /// Polled inputs the last tick couldn't apply, with the reason.
+    fn get_poll_errors(&self) -> &[(C, InputError)];
+}
+
+#[cfg(feature = "device")]
+pub trait IDeviceTracking<A, B, C> {
+    fn set_device_classifier(&mut self, classifier: fn(&C) -> DeviceClass);
+    fn observe_input(&mut self, input: &C, change: &Change);
+    fn get_active_device(&self) -> Option<DeviceClass>;
+    fn get_device_switch(&self) -> Option<&DeviceSwitch>;
+    fn prompt_for(&self, action: &Binding<A, B>) -> Option<&C>;
+}
+
+#[cfg(feature = "contexts")]
+/// Scopes start focused. Changes to an action in any unfocused scope are
+/// dropped, and its held inputs are released when the scope loses focus.
+/// Scopes double as action groups for game modes, which `focus_only`
+/// switches between in one call.
+pub trait IFocusScopes<A, B> {
+    fn add_to_scope(&mut self, scope: String, action: Binding<A, B>);
+    fn add_actions_to_scope(&mut self, scope: String, actions: Vec<Binding<A, B>>);
+    fn set_scope_focus(&mut self, scope: &str, focused: bool);
+    fn focus_only(&mut self, scopes: &[&str]);
+    fn is_scope_focused(&self, scope: &str) -> bool;
+    fn set_scope_policy(&mut self, scope: &str, policy: TransitionPolicy);
+    fn get_scope_transitions(&self) -> &[ScopeTransition];
+}
+
+#[cfg(feature = "filter")]
+pub trait IChangeFilters<A, B> {
+    fn add_filter(&mut self, filter: Box<dyn ChangeFilter<A, B>>);
+    fn clear_filters(&mut self);
+}
+
+/// Processors run in the order they were added, after everything else in
+/// `tick`, so they see the final state for the frame.
+#[cfg(feature = "filter")]
+pub trait IStateProcessors<A, B> {
+    fn add_state_processor(&mut self, action: Binding<A, B>, processor: Box<dyn StateProcessor>);
+    fn clear_state_processors(&mut self);
+}
+
+#[cfg(feature = "custom_state")]
+pub trait ICustomStates<C> {
+    fn add_custom_state(&mut self, name: String, state: Box<dyn CustomState>);
+    fn add_custom_binding(&mut self, name: String, input: C, slot: usize);
+    fn apply_custom_change(&mut self, name: &str, slot: usize, change: Change);
+    fn get_custom_state(&self, name: &str) -> Option<&dyn CustomState>;
+}
+
+/// Actions the game expects to exist, so missing bindings can be reported
+/// before a player hits them.
+pub trait IActions<A, B> {
+    fn register_action(&mut self, action: Binding<A, B>);
+    fn get_registered_actions(&self) -> &[Binding<A, B>];
+    fn unbound_actions(&self) -> Vec<&Binding<A, B>>;
+}
+
+pub trait IChangeCursors<A, B> {
+    fn new_cursor(&self) -> ChangeCursor;
+    fn changes_since(&self, cursor: &mut ChangeCursor) -> Vec<&Binding<A, B>>;
+}
+
+/// Events are only collected while enabled, so an undrained queue can't
+/// grow without bound.
+pub trait IActionEvents<A, B, C> {
+    fn set_action_events_enabled(&mut self, enabled: bool);
+    fn drain_action_events(&mut self) -> Vec<ActionEvent<A, B, C>>;
+    fn last_source_for(&self, action: &Binding<A, B>) -> Option<&ChangeSource<C>>;
+    #[cfg(feature = "export")]
+    fn set_event_log(&mut self, log: Option<Box<dyn IEventLog<A, B>>>);
+}
+
+/// Measures how long timestamped changes to an action wait before the tick
+/// that consumes them, using the same clock as the timestamps. Only actions
+/// with a budget are measured, and warnings are also sent to the event log.
+pub trait ILatencyBudgets<A, B> {
+    fn set_latency_budget(&mut self, action: Binding<A, B>, budget: Option<Duration>);
+    /// The warnings raised by the last tick.
+    fn get_latency_warnings(&self) -> &[LatencyWarning<A, B>];
+}
+
+#[cfg(feature = "animation")]
+/// Named floats for animation blend trees, each following an action's
+/// `State::level` and updated at the end of every tick.
+pub trait IAnimationParameters<A, B> {
+    fn add_animation_parameter(&mut self, name: String, action: Binding<A, B>, smoothing: Duration);
+    fn remove_animation_parameter(&mut self, name: &str);
+    fn get_animation_parameter(&self, name: &str) -> Option<f64>;
+    fn animation_parameters(&self) -> Vec<(&str, f64)>;
+}
+
+pub trait IClockDomains<A, B> {
+    fn set_clock_domain(&mut self, action: Binding<A, B>, domain: ClockDomain);
+    /// Negative scales clamp to 0. Returns false and keeps the current scale
+    /// if `scale` isn't finite.
+    fn set_time_scale(&mut self, scale: f64) -> bool;
+    fn get_time_scale(&self) -> f64;
+}
+
+/// A single measure of how much input the last frame carried, for effects
+/// that react to the player being active or idle. Each change counts its
+/// `magnitude` times the action's weight, which defaults to 1.
+pub trait IActivity<A, B> {
+    fn set_activity_weight(&mut self, action: Binding<A, B>, weight: f64);
+    fn get_activity(&self) -> f64;
+}
+
+#[cfg(feature = "audit")]
+pub trait IAudit {
+    fn start_audit(&mut self);
+    fn stop_audit(&mut self) -> Option<AuditTrace>;
+    fn get_state_checksum(&self) -> u64;
+}
+
+#[cfg(feature = "heatmap")]
+pub trait IHeatmaps<A: Hash + Eq, B: Hash + Eq> {
+    fn start_heatmap(&mut self, bucket: Duration, columns: usize, rows: usize);
+    fn record_pointer(&mut self, x: f64, y: f64);
+    fn stop_heatmap(&mut self) -> Option<Heatmap<A, B>>;
+}
+
+pub trait IRollback<A: Hash + Eq, B: Hash + Eq> {
+    fn snapshot(&self) -> Snapshot<A, B>;
+    fn rewind_to(&mut self, snapshot: &Snapshot<A, B>);
+    fn replay_changes(&mut self, frames: &[InputFrame<A, B>]);
+    fn get_last_frame(&self) -> &InputFrame<A, B>;
+}
+
+pub trait ISaveState<A: Hash + Eq, B: Hash + Eq> {
+    fn save(&self) -> SaveState<A, B>;
+    fn load(&mut self, state: &SaveState<A, B>);
+}
+
+/// Everything a game loop needs, usable as `Box<dyn InputManager<A, B, C>>`.
+pub trait InputManager<A, B, C>: IManager<A, B, C> + IConverter<A, B, C> + IUpdater<A, B> {}
+
+impl<A, B, C, T: IManager<A, B, C> + IConverter<A, B, C> + IUpdater<A, B>> InputManager<A, B, C>
+    for T
+{
 }
 
 pub trait IUpdater<A, B> {
     fn tick(&mut self, Duration);
-    fn apply_change(&mut self, &Binding<A, B>, impl Into<Change>);
+    #[cfg(feature = "frame_clock")]
+    fn tick_at_timestamp(&mut self, now_ms: f64);
+    #[cfg(feature = "frame_clock")]
+    fn set_max_tick_delta(&mut self, max_delta: Duration);
+    fn apply_change(&mut self, binding: &Binding<A, B>, change: Change) -> Result<(), InputError>;
+    fn apply_change_at(
+        &mut self,
+        binding: &Binding<A, B>,
+        change: Change,
+        at: Duration,
+    ) -> Result<(), InputError>;
+    fn set_sub_tick_ordering(&mut self, ordering: SubTickOrdering);
+    fn set_input_delay(&mut self, frames: usize);
+    /// With manual frames, `tick` only advances time and edge flags such as
+    /// `new_event` stay set until `end_frame` is called.
+    fn set_manual_frames(&mut self, manual: bool);
+    fn end_frame(&mut self);
+    fn take_accumulated(&mut self, accumulator: A) -> f64;
+    /// Returns false if the axis has not received a position to center on.
+    fn recenter_axis(&mut self, axis: A) -> bool;
+    /// While paused, `tick` does nothing and applied changes are held.
+    /// `step_frame` runs a single tick and then applies the held changes, as
+    /// if they had arrived during that frame.
+    fn set_paused(&mut self, paused: bool);
+    fn step_frame(&mut self, delta: Duration);
+    fn get_paused_changes(&self) -> &[(Binding<A, B>, Change, Option<Duration>)];
 }
 
-impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> IUpdater<A, B> for Manager<A, B, C> {
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq + Clone> IUpdater<A, B>
+    for Manager<A, B, C>
+{
     fn tick(&mut self, delta: Duration) {
-        for state in self.states.values_mut() {
-            match state {
-                State::Axis(axis) => axis.tick(delta),
-                State::Button(button) => button.tick(),
+        if self.paused && !self.stepping {
+            return;
+        }
+        self.stepping = false;
+        self.last_frame = InputFrame {
+            changes: self.frame_changes.drain(..).collect(),
+            delta,
+        };
+        #[cfg(feature = "polling")]
+        {
+            let polled = self
+                .poller
+                .as_ref()
+                .map(|poller| poller.drain())
+                .unwrap_or_default();
+            self.poll_errors.clear();
+            for (input, change, at) in polled {
+                if let Err(error) = self.apply_input(input.clone(), change, Some(at)) {
+                    self.poll_errors.push((input, error));
+                }
             }
         }
-    }
-    fn apply_change(&mut self, binding: &Binding<A, B>, change: impl Into<Change>) {
-        if let Some(ref mut state) = self.states.get_mut(binding) {
-            match (state, change.into()) {
-                (&mut State::Axis(ref mut a), Change::Axis(ref c)) => a.apply(c.clone()),
-                (&mut State::Button(ref mut b), Change::Button(ref c)) => b.apply(c.clone()),
-                _ => unreachable!(),
+        #[cfg(feature = "device")]
+        self.device_tracker.tick();
+        #[cfg(feature = "contexts")]
+        {
+            self.scope_transitions = self.pending_scope_transitions.drain(..).collect();
+        }
+        let mut queued: Vec<_> = self.queued_changes.drain(..).collect();
+        queued.sort_by_key(|&(_, _, at)| at);
+        let frame_start = self.clock;
+        let mut integrated: HashMap<Binding<A, B>, (Duration, Duration)> = HashMap::new();
+        if self.sub_tick_ordering == SubTickOrdering::Integrated {
+            for (binding, _, _) in queued.iter() {
+                if let Binding::Axis(_) = binding {
+                    integrated
+                        .entry(binding.clone())
+                        .or_insert((frame_start, Duration::default()));
+                }
+            }
+        }
+        let scaled = self.scaled_delta(delta);
+        for (binding, state) in self.states.iter_mut() {
+            let delta = match self.clock_domains.get(binding) {
+                Some(ClockDomain::Realtime) => delta,
+                _ => scaled,
+            };
+            match (binding, state) {
+                (_, State::Axis(_)) if integrated.contains_key(binding) => {}
+                (_, State::Axis(axis)) => axis.tick(delta),
+                (Binding::Button(key), State::Button(button)) => {
+                    let was_pressed = button.pressed;
+                    if self.manual_frames {
+                        button.advance(delta);
+                    } else {
+                        button.tick(delta);
+                    }
+                    if button.fired_since(was_pressed) {
+                        #[cfg(feature = "combo")]
+                        self.combos.feed(ComboStep::Button(key.clone()));
+                        #[cfg(feature = "export")]
+                        if let Some(ref mut log) = self.event_log {
+                            log.log(self.frame, LoggedEvent::Fired(key));
+                        }
+                        #[cfg(feature = "heatmap")]
+                        if let Some(ref mut heatmap) = self.heatmap {
+                            heatmap.record_activation(binding);
+                        }
+                    }
+                    Self::track_press_order(&mut self.press_order, key, button.is_held());
+                    if button.new_event {
+                        self.change_seq += 1;
+                        self.last_changed.insert(binding.clone(), self.change_seq);
+                    }
+                }
+                (_, State::Button(button)) if self.manual_frames => button.advance(delta),
+                (_, State::Button(button)) => button.tick(delta),
+                (_, State::Value(_)) if self.manual_frames => {}
+                (_, State::Value(value)) => value.tick(delta),
+                (_, State::Accumulator(accumulator)) => accumulator.tick(delta),
+                (_, State::Trigger(_)) if self.manual_frames => {}
+                (_, State::Trigger(trigger)) => trigger.tick(delta),
+                (_, State::Axis2d(_)) if self.manual_frames => {}
+                (_, State::Axis2d(axis2d)) => axis2d.tick(delta),
+            }
+        }
+        for (binding, change, at) in queued {
+            if let Some(&mut (ref mut last_at, ref mut elapsed)) = integrated.get_mut(&binding) {
+                let until = at.min(frame_start + delta).max(*last_at);
+                let gap = until - *last_at;
+                let scaled_gap = self.domain_delta(&binding, gap);
+                if let Some(State::Axis(axis)) = self.states.get_mut(&binding) {
+                    axis.tick(scaled_gap);
+                }
+                *last_at = until;
+                *elapsed += gap;
+            }
+            self.dispatch_change(&binding, change, Some(at));
+        }
+        for (binding, (_, elapsed)) in integrated {
+            let rest = self.domain_delta(&binding, delta.saturating_sub(elapsed));
+            if let Some(State::Axis(axis)) = self.states.get_mut(&binding) {
+                axis.tick(rest);
+            }
+        }
+        self.delayed_changes.push_back(Vec::new());
+        while self.delayed_changes.len() > self.input_delay {
+            for (binding, change, at) in self.delayed_changes.pop_front().unwrap_or_default() {
+                self.route_change(&binding, change, at);
+            }
+        }
+        #[cfg(feature = "custom_state")]
+        for state in self.custom_states.values_mut() {
+            state.tick(delta);
+        }
+        #[cfg(feature = "drift")]
+        for (axis, detector) in self.drift_detectors.iter_mut() {
+            if let Some(State::Axis(a)) = self.states.get(&Binding::Axis(axis.clone())) {
+                detector.sample(a.position, delta);
+            }
+        }
+        #[cfg(feature = "timing")]
+        for (button, timings) in self.button_timings.iter_mut() {
+            if let Some(State::Button(b)) = self.states.get(&Binding::Button(button.clone())) {
+                timings.sample(b.pressed, delta);
             }
         }
+        #[cfg(feature = "combo")]
+        {
+            for action in self.confirmed.drain(..).collect::<Vec<_>>() {
+                self.enqueue_change(&Binding::Button(action), ButtonChange(false).into(), None);
+            }
+            let held = |states: &StateArena<A, B>, button: &B| match states
+                .get(&Binding::Button(button.clone()))
+            {
+                Some(State::Button(b)) => b.is_held(),
+                _ => false,
+            };
+            for (action, confirm) in self.hold_confirms.iter_mut() {
+                let both_held =
+                    held(&self.states, &confirm.first) && held(&self.states, &confirm.second);
+                if confirm.advance(both_held, delta) {
+                    self.confirmed.push(action.clone());
+                }
+            }
+            for action in self.confirmed.clone() {
+                self.enqueue_change(&Binding::Button(action), ButtonChange(true).into(), None);
+            }
+        }
+        #[cfg(feature = "recording")]
+        {
+            if let Some(ref mut recorder) = self.macro_recorder {
+                recorder.tick(delta);
+            }
+            for (binding, change) in self.macro_player.tick(delta) {
+                let _ = self.apply_change(&binding, change);
+            }
+        }
+        #[cfg(feature = "combo")]
+        self.combos.tick(delta);
+        #[cfg(feature = "heatmap")]
+        if let Some(ref mut heatmap) = self.heatmap {
+            heatmap.tick(delta);
+        }
+        #[cfg(feature = "filter")]
+        for (action, processor) in self.state_processors.iter_mut() {
+            if let Some(state) = self.states.get_mut(action) {
+                processor.process(state);
+            }
+        }
+        #[cfg(feature = "animation")]
+        for (action, parameter) in self.animation_parameters.values_mut() {
+            let target = self
+                .states
+                .get(action)
+                .map(State::level)
+                .unwrap_or_default();
+            parameter.sample(target, delta);
+        }
+        self.clock += delta;
+        self.check_latencies();
+        #[cfg(all(feature = "export", feature = "combo"))]
+        if let Some(ref mut log) = self.event_log {
+            for name in self.combos.get_completed() {
+                log.log(self.frame, LoggedEvent::Combo(name));
+            }
+        }
+        self.frame += 1;
+        #[cfg(feature = "strict-invariants")]
+        self.assert_invariants(true);
+        #[cfg(feature = "audit")]
+        if self.audit.is_some() {
+            let checksum = self.get_state_checksum();
+            if let Some(ref mut audit) = self.audit {
+                audit.end_frame(checksum);
+            }
+        }
+        for (binding, change, at) in ::std::mem::take(&mut self.paused_changes) {
+            self.frame_changes.push((binding.clone(), change.clone()));
+            self.delay_change(&binding, change, at);
+        }
     }
-}
-
-impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> IConverter<A, B, C>
-    for Manager<A, B, C>
-{
-    fn add_axis_binding(&mut self, axis: A, input: C) {
-        let binding = Binding::Axis(axis);
-        self.bindings.insert(input, binding.clone());
-        if !self.states.contains_key(&binding) {
-            self.states.insert(binding, State::Axis(Axis::default()));
+    #[cfg(feature = "frame_clock")]
+    fn tick_at_timestamp(&mut self, now_ms: f64) {
+        let delta = self.frame_clock.advance(now_ms);
+        self.tick(delta);
+    }
+    #[cfg(feature = "frame_clock")]
+    fn set_max_tick_delta(&mut self, max_delta: Duration) {
+        self.frame_clock.set_max_delta(max_delta);
+    }
+    fn apply_change(&mut self, binding: &Binding<A, B>, change: Change) -> Result<(), InputError> {
+        if !binding.accepts(&change) {
+            self.pending_source = None;
+            return Err(InputError::ChangeKindMismatch);
         }
+        self.enqueue_change(binding, change, None);
+        Ok(())
     }
-
-    fn add_default_change(&mut self, change: impl Into<Change>, input: C) {
-        self.default_changes.insert(input, change.into());
+    fn apply_change_at(
+        &mut self,
+        binding: &Binding<A, B>,
+        change: Change,
+        at: Duration,
+    ) -> Result<(), InputError> {
+        if !binding.accepts(&change) {
+            self.pending_source = None;
+            return Err(InputError::ChangeKindMismatch);
+        }
+        self.enqueue_change(binding, change, Some(at));
+        Ok(())
     }
-
-    fn get_binding(&self, input: C) -> Option<Binding<A, B>> {
-        self.bindings.get(&input).cloned()
+    fn set_sub_tick_ordering(&mut self, ordering: SubTickOrdering) {
+        self.sub_tick_ordering = ordering;
     }
-    fn get_default_change(&self, input: C) -> Option<Change> {
-        self.default_changes.get(&input).cloned()
+    fn set_input_delay(&mut self, frames: usize) {
+        self.input_delay = frames;
     }
-
-    fn add_button_binding(&mut self, button: B, input: C) {
-        let binding = Binding::Button(button);
-        self.bindings.insert(input, binding.clone());
-        if !self.states.contains_key(&binding) {
-            self.states
-                .insert(binding, State::Button(Button::default()));
+    fn set_manual_frames(&mut self, manual: bool) {
+        self.manual_frames = manual;
+    }
+    fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+    fn step_frame(&mut self, delta: Duration) {
+        self.stepping = true;
+        self.tick(delta);
+    }
+    fn get_paused_changes(&self) -> &[(Binding<A, B>, Change, Option<Duration>)] {
+        &self.paused_changes
+    }
+    fn recenter_axis(&mut self, axis: A) -> bool {
+        match self.states.get_mut(&Binding::Axis(axis)) {
+            Some(State::Axis(a)) => a.recenter(),
+            _ => false,
+        }
+    }
+    fn take_accumulated(&mut self, accumulator: A) -> f64 {
+        match self.states.get_mut(&Binding::Accumulator(accumulator)) {
+            Some(State::Accumulator(a)) => a.take(),
+            _ => 0.0,
+        }
+    }
+    fn end_frame(&mut self) {
+        for state in self.states.values_mut() {
+            match state {
+                State::Button(button) => button.end_frame(),
+                State::Value(value) => value.end_frame(),
+                State::Trigger(trigger) => trigger.end_frame(),
+                State::Axis2d(axis2d) => axis2d.end_frame(),
+                State::Axis(_) | State::Accumulator(_) => {}
+            }
         }
     }
 }
 
-impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> IManager<A, B, C>
-    for Manager<A, B, C>
-{
-    fn new() -> Self {
-        Manager {
-            states: HashMap::new(),
-            bindings: HashMap::new(),
-            default_changes: HashMap::new(),
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> Manager<A, B, C> {
+    fn default_state(binding: &Binding<A, B>) -> State {
+        match binding {
+            Binding::Axis(_) => State::Axis(Axis::default()),
+            Binding::Button(_) => State::Button(Button::default()),
+            Binding::Value(_) => State::Value(Value::default()),
+            Binding::Accumulator(_) => State::Accumulator(Accumulator::default()),
+            Binding::Trigger(_) => State::Trigger(Trigger::default()),
+            Binding::Axis2d(_) => State::Axis2d(Axis2d::default()),
         }
     }
-
-    fn get_axis(&self, binding: A) -> Option<&Axis> {
-        self.states.get(&Binding::Axis(binding)).and_then(|val| {
-            if let State::Axis(a) = val {
-                Some(a)
-            } else {
-                None
+    fn resolve_action(&self, input: &C) -> Option<Binding<A, B>> {
+        let binding = self.bindings.resolve(input)?;
+        Some(self.action_remap.get(binding).unwrap_or(binding).clone())
+    }
+    fn track_press_order(press_order: &mut Vec<B>, button: &B, held: bool) {
+        let position = press_order.iter().position(|b| b == button);
+        match (position, held) {
+            (None, true) => press_order.push(button.clone()),
+            (Some(index), false) => {
+                press_order.remove(index);
             }
-        })
+            _ => {}
+        }
     }
-    fn get_states(&self) -> &HashMap<Binding<A, B>, State> {
-        &self.states
+    fn record_action_event(
+        &mut self,
+        binding: &Binding<A, B>,
+        change: &Change,
+        source: Option<ChangeSource<C>>,
+    ) {
+        if let Some(ref mut events) = self.action_events {
+            events.push(ActionEvent {
+                action: binding.clone(),
+                change: change.clone(),
+                source,
+            });
+        }
     }
-
-    fn get_button(&self, binding: B) -> Option<&Button> {
-        self.states.get(&Binding::Button(binding)).and_then(|val| {
-            if let State::Button(b) = val {
-                Some(b)
-            } else {
+    fn check_latencies(&mut self) {
+        self.latency_warnings.clear();
+        for (action, at) in self.pending_latencies.drain(..) {
+            let budget = match self.latency_budgets.get(&action) {
+                Some(&budget) => budget,
+                None => continue,
+            };
+            let latency = self.clock.saturating_sub(at);
+            if latency > budget {
+                #[cfg(feature = "export")]
+                if let Some(ref mut log) = self.event_log {
+                    log.log(self.frame, LoggedEvent::LatencyExceeded(&action, latency));
+                }
+                self.latency_warnings.push(LatencyWarning {
+                    action,
+                    latency,
+                    budget,
+                });
+            }
+        }
+    }
+    /// Returns whether the change was applied, or `None` if it was deferred
+    /// to a later frame.
+    fn delay_change(
+        &mut self,
+        binding: &Binding<A, B>,
+        change: Change,
+        at: Option<Duration>,
+    ) -> Option<bool> {
+        if self.input_delay == 0 {
+            return self.route_change(binding, change, at);
+        }
+        if self.delayed_changes.is_empty() {
+            self.delayed_changes.push_back(Vec::new());
+        }
+        if let Some(frame) = self.delayed_changes.back_mut() {
+            frame.push((binding.clone(), change, at));
+        }
+        None
+    }
+    fn route_change(
+        &mut self,
+        binding: &Binding<A, B>,
+        change: Change,
+        at: Option<Duration>,
+    ) -> Option<bool> {
+        match at {
+            Some(at) if self.sub_tick_ordering != SubTickOrdering::Immediate => {
+                self.queued_changes.push((binding.clone(), change, at));
                 None
             }
-        })
+            _ => Some(self.dispatch_change(binding, change, at)),
+        }
     }
-    fn get_changed_buttons(&self) -> HashMap<&B, &Button> {
-        self.states
-            .iter()
-            .filter_map(|(key, val)| {
-                if let State::Button(b) = val {
-                    if b.new_event {
-                        if let Binding::Button(bind) = key {
-                            Some((bind, b))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
+    /// Returns false if the action has no state for the change or its state
+    /// dropped it, such as a debounced press.
+    fn dispatch_change(
+        &mut self,
+        binding: &Binding<A, B>,
+        change: Change,
+        at: Option<Duration>,
+    ) -> bool {
+        #[cfg(feature = "recording")]
+        if let Some(ref mut recorder) = self.macro_recorder {
+            recorder.record(binding, &change);
+        }
+        #[cfg(feature = "audit")]
+        if let Some(ref mut audit) = self.audit {
+            audit.record_change(stable_hash(&(binding, &change)));
+        }
+        #[cfg(feature = "export")]
+        if let Some(ref mut log) = self.event_log {
+            log.log(self.frame, LoggedEvent::Change(binding, &change));
+        }
+        if let (Some(at), true) = (at, self.latency_budgets.contains_key(binding)) {
+            self.pending_latencies.push((binding.clone(), at));
+        }
+        let state = match self.states.get_mut(binding) {
+            Some(state) => state,
+            None => return false,
+        };
+        self.change_seq += 1;
+        self.last_changed.insert(binding.clone(), self.change_seq);
+        #[cfg_attr(not(feature = "combo"), allow(unused_variables))]
+        let (applied, fired, turned) = match (binding, state, change) {
+            (Binding::Axis(_), &mut State::Axis(ref mut a), Change::Axis(c)) => {
+                let velocity = a.velocity.clone();
+                match at {
+                    Some(at) => a.apply_at(c, at),
+                    None => a.apply(c),
+                }
+                match a.velocity {
+                    Some(ref direction) if velocity.as_ref() != Some(direction) => {
+                        (true, false, Some(direction.clone()))
                     }
-                } else {
-                    None
+                    _ => (true, false, None),
                 }
-            })
-            .collect()
-    }
-
-    fn get_button_pressed(&self, button: B) -> bool {
-        self.get_button(button)
-            .map(|button| button.pressed && button.new_event)
-            .unwrap_or(false)
-    }
-}
-
+            }
+            (
+                Binding::Button(key),
+                &mut State::Button(ref mut b),
+                c @ (Change::Button(_) | Change::Pressure(_)),
+            ) => {
+                let was_pressed = b.pressed;
+                let before = (b.pressed, b.is_held(), b.value);
+                match (c, at) {
+                    (Change::Pressure(c), Some(at)) => b.apply_pressure_at(c, at),
+                    (Change::Pressure(c), None) => b.apply_pressure(c),
+                    (Change::Button(c), Some(at)) => b.apply_at(c, at),
+                    (Change::Button(c), None) => b.apply(c),
+                    _ => {}
+                }
+                Self::track_press_order(&mut self.press_order, key, b.is_held());
+                let applied = before != (b.pressed, b.is_held(), b.value);
+                (applied, b.fired_since(was_pressed), None)
+            }
+            (_, &mut State::Accumulator(ref mut a), Change::Value(c)) => {
+                a.apply(c);
+                (true, false, None)
+            }
+            (_, &mut State::Value(ref mut v), Change::Value(c)) => {
+                match at {
+                    Some(at) => v.apply_at(c, at),
+                    None => v.apply(c),
+                }
+                (true, false, None)
+            }
+            (_, &mut State::Trigger(ref mut t), Change::Trigger(c)) => {
+                match at {
+                    Some(at) => t.apply_at(c, at),
+                    None => t.apply(c),
+                }
+                (true, false, None)
+            }
+            (_, &mut State::Axis2d(ref mut s), Change::Axis2d(c)) => {
+                match at {
+                    Some(at) => s.apply_at(c, at),
+                    None => s.apply(c),
+                }
+                (true, false, None)
+            }
+            _ => (false, false, None),
+        };
+        #[cfg(feature = "heatmap")]
+        if let Some(ref mut heatmap) = self.heatmap {
+            let activated = match binding {
+                Binding::Button(_) => fired,
+                _ => true,
+            };
+            if activated {
+                heatmap.record_activation(binding);
+            }
+        }
+        #[cfg(feature = "export")]
+        if let (true, Binding::Button(button), Some(log)) = (fired, binding, &mut self.event_log) {
+            log.log(self.frame, LoggedEvent::Fired(button));
+        }
+        #[cfg(feature = "combo")]
+        match (binding, turned) {
+            (Binding::Button(button), _) if fired => {
+                self.combos.feed(ComboStep::Button(button.clone()))
+            }
+            (Binding::Axis(axis), Some(direction)) => {
+                self.combos.feed(ComboStep::Axis(axis.clone(), direction))
+            }
+            _ => {}
+        }
+        #[cfg(feature = "strict-invariants")]
+        self.assert_invariants(false);
+        applied
+    }
+    /// After a tick every axis with a velocity has been clamped.
+    #[cfg(feature = "strict-invariants")]
+    fn assert_invariants(&self, ticked: bool) {
+        for state in self.states.values() {
+            match state {
+                State::Axis(axis) => {
+                    axis.assert_invariants();
+                    debug_assert!(
+                        !ticked || axis.velocity.is_none() || axis.position.abs() <= 1.0,
+                        "moving axis outside its clamp after tick"
+                    );
+                }
+                State::Button(button) => button.assert_invariants(),
+                State::Value(value) => value.assert_invariants(),
+                State::Trigger(trigger) => trigger.assert_invariants(),
+                State::Axis2d(axis2d) => axis2d.assert_invariants(),
+                State::Accumulator(_) => {}
+            }
+        }
+    }
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq + Clone> IBindingEdits<A, B, C>
+    for Manager<A, B, C>
+{
+    fn begin_binding_edit(&self) -> BindingEdit<A, B, C> {
+        BindingEdit::new(self.bindings.get_bindings().clone())
+    }
+    fn commit_binding_edit(&mut self, edit: BindingEdit<A, B, C>) {
+        let bindings = edit.into_bindings();
+        let mut mutation: BindingMutation<A, B, C> = self
+            .bindings
+            .get_bindings()
+            .iter()
+            .filter(|&(input, _)| !bindings.contains_key(input))
+            .map(|(input, previous)| (input.clone(), Some(previous.clone()), None))
+            .collect();
+        for (input, binding) in bindings {
+            let previous = self.bindings.resolve(&input).cloned();
+            if previous.as_ref() != Some(&binding) {
+                mutation.push((input, previous, Some(binding)));
+            }
+        }
+        for (input, _, binding) in mutation.iter() {
+            self.set_binding(input.clone(), binding.clone());
+        }
+        self.record_binding_mutation(mutation);
+    }
+    #[cfg(feature = "profile")]
+    fn get_binding_profile(&self) -> BindingProfile<A, B, C> {
+        BindingProfile::from_bindings(self.bindings.get_bindings().clone())
+    }
+    #[cfg(feature = "profile")]
+    fn load_binding_profile(&mut self, profile: &BindingProfile<A, B, C>) {
+        self.commit_binding_edit(BindingEdit::new(profile.get_bindings()));
+    }
+    fn undo_binding_change(&mut self) -> bool {
+        if let Some(mutation) = self.binding_undo.pop() {
+            for (input, previous, _) in mutation.iter().rev() {
+                self.set_binding(input.clone(), previous.clone());
+            }
+            self.binding_redo.push(mutation);
+            true
+        } else {
+            false
+        }
+    }
+    fn redo_binding_change(&mut self) -> bool {
+        if let Some(mutation) = self.binding_redo.pop() {
+            for (input, _, binding) in mutation.iter() {
+                self.set_binding(input.clone(), binding.clone());
+            }
+            self.binding_undo.push(mutation);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq + Clone> Manager<A, B, C> {
+    /// Everything applied to an action goes through here once its change
+    /// kind has been checked.
+    fn enqueue_change(&mut self, binding: &Binding<A, B>, change: Change, at: Option<Duration>) {
+        let source = self.pending_source.take();
+        #[cfg(feature = "filter")]
+        let (binding, change) =
+            match run_change_filters(&mut self.change_filters, binding.clone(), change) {
+                Some((binding, change)) if binding.accepts(&change) => (binding, change),
+                _ => return,
+            };
+        #[cfg(not(feature = "filter"))]
+        let binding = binding.clone();
+        let binding = &binding;
+        #[cfg(feature = "contexts")]
+        if !self.admit_change(binding, &change) {
+            return;
+        }
+        self.record_action_event(binding, &change, source.clone());
+        let applied = if self.paused || !self.paused_changes.is_empty() {
+            self.paused_changes.push((binding.clone(), change, at));
+            None
+        } else {
+            self.frame_changes.push((binding.clone(), change.clone()));
+            self.delay_change(binding, change, at)
+        };
+        // Held and delayed changes don't carry their source, so it is kept
+        // once they are admitted.
+        if let (Some(source), true) = (source, applied.unwrap_or(true)) {
+            self.last_sources.insert(binding.clone(), source);
+        }
+    }
+    /// Tracks the active device, then feeds the input to custom states,
+    /// macros and bound actions.
+    fn apply_input(
+        &mut self,
+        input: C,
+        change: Change,
+        at: Option<Duration>,
+    ) -> Result<bool, InputError> {
+        #[cfg(feature = "device")]
+        self.observe_input(&input, &change);
+        #[cfg(feature = "custom_state")]
+        let custom = match self.custom_bindings.get(&input).cloned() {
+            Some((name, slot)) => {
+                self.apply_custom_change(&name, slot, change.clone());
+                true
+            }
+            None => false,
+        };
+        #[cfg(not(feature = "custom_state"))]
+        let custom = false;
+        Ok(self.apply_bound_input(input, change, at)? || custom)
+    }
+    fn apply_bound_input(
+        &mut self,
+        input: C,
+        change: Change,
+        at: Option<Duration>,
+    ) -> Result<bool, InputError> {
+        #[cfg(feature = "recording")]
+        let played = self.trigger_macro(&input, &change);
+        #[cfg(not(feature = "recording"))]
+        let played = false;
+        let binding = match self.resolve_action(&input) {
+            Some(binding) => binding,
+            None => return Ok(played),
+        };
+        if let (Binding::Button(button), Change::Axis(AxisChange::Position(position))) =
+            (&binding, &change)
+        {
+            self.track_press_speed(&input, button, *position, at.unwrap_or(self.clock));
+        }
+        let change = self.bindings.convert_change(&input, change);
+        if let (Some(_), Change::Button(ButtonChange(held))) =
+            (self.bindings.get_input_threshold(&input), &change)
+        {
+            if *held {
+                self.threshold_held.insert(input.clone());
+            } else {
+                self.threshold_held.remove(&input);
+            }
+        }
+        let source = ChangeSource {
+            #[cfg(feature = "device")]
+            device: self.device_classifier.map(|classify| classify(&input)),
+            input,
+        };
+        self.pending_source = Some(source);
+        match at {
+            Some(at) => self.apply_change_at(&binding, change, at)?,
+            None => self.apply_change(&binding, change)?,
+        }
+        Ok(true)
+    }
+    /// Starts the macro bound to `input` on its press edge. Held repeats
+    /// don't restart it.
+    #[cfg(feature = "recording")]
+    fn trigger_macro(&mut self, input: &C, change: &Change) -> bool {
+        if !self.macro_bindings.contains_key(input) {
+            return false;
+        }
+        if let Change::Button(ButtonChange(pressed)) = *change {
+            if !pressed {
+                self.held_macro_inputs.remove(input);
+            } else if self.held_macro_inputs.insert(input.clone()) {
+                self.play_macro(input.clone());
+            }
+        }
+        true
+    }
+    /// Scales `delta` by the time scale unless the action runs on the
+    /// realtime clock.
+    fn domain_delta(&self, binding: &Binding<A, B>, delta: Duration) -> Duration {
+        match self.clock_domains.get(binding) {
+            Some(ClockDomain::Realtime) => delta,
+            _ => self.scaled_delta(delta),
+        }
+    }
+    /// `delta` times the time scale, saturating at `Duration::MAX` when a
+    /// large scale would overflow.
+    fn scaled_delta(&self, delta: Duration) -> Duration {
+        Duration::try_from_secs_f64(delta.as_secs_f64() * self.time_scale).unwrap_or(Duration::MAX)
+    }
+    /// Times analog threshold presses from when the input leaves rest until
+    /// it crosses the threshold.
+    fn track_press_speed(&mut self, input: &C, button: &B, position: f64, now: Duration) {
+        let threshold = match self.bindings.get_input_threshold(input) {
+            Some(threshold) => threshold,
+            None => return,
+        };
+        let travel = self.bindings.shape_position(input, position) * threshold.signum();
+        if travel <= 0.0 {
+            self.press_rises.remove(input);
+            return;
+        }
+        let crossed = travel >= threshold.abs();
+        match self.press_rises.get(input).cloned() {
+            None if crossed => {
+                self.press_speeds
+                    .insert(button.clone(), Duration::default());
+                self.press_rises.insert(input.clone(), None);
+            }
+            None => {
+                self.press_rises.insert(input.clone(), Some(now));
+            }
+            Some(Some(start)) if crossed => {
+                self.press_speeds
+                    .insert(button.clone(), now.saturating_sub(start));
+                self.press_rises.insert(input.clone(), None);
+            }
+            Some(_) => {}
+        }
+    }
+    /// A threshold input holding its button releases it when rebound, so
+    /// the old action doesn't stay held and repeating.
+    fn set_binding(&mut self, input: C, binding: Option<Binding<A, B>>) {
+        if self.threshold_held.remove(&input) {
+            self.press_rises.remove(&input);
+            if let Some(previous) = self.resolve_action(&input) {
+                self.enqueue_change(&previous, ButtonChange(false).into(), None);
+            }
+        }
+        match binding {
+            Some(binding) => {
+                self.states
+                    .get_or_insert_with(binding.clone(), || Self::default_state(&binding));
+                self.bindings.set_binding(input, Some(binding));
+            }
+            None => {
+                self.bindings.set_binding(input, None);
+            }
+        }
+    }
+    fn record_binding_mutation(&mut self, mutation: BindingMutation<A, B, C>) {
+        if !mutation.is_empty() {
+            self.binding_undo.push(mutation);
+            self.binding_redo.clear();
+        }
+    }
+    fn bind(&mut self, input: C, binding: Binding<A, B>) {
+        if let Some(existing) = self.find_kind_conflict(&binding) {
+            self.registration_conflicts
+                .push(RegistrationConflict::ActionKind {
+                    existing,
+                    conflicting: binding,
+                });
+            return;
+        }
+        if let Some(change) = self.bindings.get_default_change(&input) {
+            if !binding.accepts(change) {
+                let change = change.clone();
+                self.registration_conflicts
+                    .push(RegistrationConflict::DefaultChange {
+                        input,
+                        binding,
+                        change,
+                    });
+                return;
+            }
+        }
+        let previous = self.bindings.resolve(&input).cloned();
+        if previous.as_ref() != Some(&binding) {
+            self.set_binding(input.clone(), Some(binding.clone()));
+            self.record_binding_mutation(vec![(input, previous, Some(binding))]);
+        }
+    }
+}
+
+#[cfg(feature = "polling")]
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq + Send + 'static> IPolling<C>
+    for Manager<A, B, C>
+{
+    fn start_polling(&mut self, sources: Vec<Box<dyn InputSource<C>>>, interval: Duration) {
+        self.stop_polling();
+        self.poller = Some(Poller::spawn(sources, interval));
+    }
+    fn stop_polling(&mut self) {
+        if let Some(mut poller) = self.poller.take() {
+            poller.stop();
+        }
+    }
+    fn get_poll_errors(&self) -> &[(C, InputError)] {
+        &self.poll_errors
+    }
+}
+
+#[cfg(feature = "device")]
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> IDeviceTracking<A, B, C>
+    for Manager<A, B, C>
+{
+    fn set_device_classifier(&mut self, classifier: fn(&C) -> DeviceClass) {
+        self.device_classifier = Some(classifier);
+    }
+    fn observe_input(&mut self, input: &C, change: &Change) {
+        if let Some(classifier) = self.device_classifier {
+            self.device_tracker.observe(classifier(input), change);
+        }
+    }
+    fn get_active_device(&self) -> Option<DeviceClass> {
+        self.device_tracker.get_active()
+    }
+    fn get_device_switch(&self) -> Option<&DeviceSwitch> {
+        self.device_tracker.get_switch()
+    }
+    fn prompt_for(&self, action: &Binding<A, B>) -> Option<&C> {
+        let inputs = self.bindings.inputs_for(action);
+        match (self.device_classifier, self.get_active_device()) {
+            (Some(classifier), Some(active)) => inputs
+                .iter()
+                .find(|input| classifier(input) == active)
+                .or_else(|| inputs.first())
+                .cloned(),
+            _ => inputs.first().cloned(),
+        }
+    }
+}
+
+#[cfg(feature = "contexts")]
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq + Clone> IFocusScopes<A, B>
+    for Manager<A, B, C>
+{
+    fn add_to_scope(&mut self, scope: String, action: Binding<A, B>) {
+        self.focus_scopes
+            .entry(scope)
+            .or_insert_with(|| FocusScope {
+                focused: true,
+                actions: Vec::new(),
+                policy: TransitionPolicy::default(),
+            })
+            .actions
+            .push(action);
+    }
+    fn add_actions_to_scope(&mut self, scope: String, actions: Vec<Binding<A, B>>) {
+        for action in actions {
+            self.add_to_scope(scope.clone(), action);
+        }
+    }
+    fn focus_only(&mut self, scopes: &[&str]) {
+        let names: Vec<String> = self.focus_scopes.keys().cloned().collect();
+        for name in names {
+            self.set_scope_focus(&name, scopes.contains(&name.as_str()));
+        }
+    }
+    fn set_scope_focus(&mut self, scope: &str, focused: bool) {
+        let (actions, policy) = match self.focus_scopes.get(scope) {
+            Some(s) if s.focused != focused => (s.actions.clone(), s.policy),
+            _ => return,
+        };
+        if focused {
+            if let Some(s) = self.focus_scopes.get_mut(scope) {
+                s.focused = true;
+            }
+            self.pending_scope_transitions
+                .push(ScopeTransition::Enter(scope.to_string()));
+            if policy == TransitionPolicy::Reevaluate {
+                let suppressed: Vec<_> = self.suppressed_changes.drain(..).collect();
+                let (replay, suppressed): (Vec<_>, Vec<_>) = suppressed
+                    .into_iter()
+                    .partition(|(binding, _)| self.is_focused(binding));
+                self.suppressed_changes = suppressed;
+                for (binding, change) in replay {
+                    self.enqueue_change(&binding, change, None);
+                }
+            }
+        } else {
+            if policy != TransitionPolicy::CarryOver {
+                for action in actions {
+                    for change in self.release_changes(&action) {
+                        self.enqueue_change(&action, change, None);
+                    }
+                }
+            }
+            if let Some(s) = self.focus_scopes.get_mut(scope) {
+                s.focused = false;
+            }
+            self.pending_scope_transitions
+                .push(ScopeTransition::Exit(scope.to_string()));
+        }
+    }
+    fn is_scope_focused(&self, scope: &str) -> bool {
+        self.focus_scopes.get(scope).is_none_or(|s| s.focused)
+    }
+    fn set_scope_policy(&mut self, scope: &str, policy: TransitionPolicy) {
+        if let Some(s) = self.focus_scopes.get_mut(scope) {
+            s.policy = policy;
+        }
+    }
+    fn get_scope_transitions(&self) -> &[ScopeTransition] {
+        &self.scope_transitions
+    }
+}
+
+#[cfg(feature = "contexts")]
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> Manager<A, B, C> {
+    fn is_focused(&self, binding: &Binding<A, B>) -> bool {
+        self.focus_scopes
+            .values()
+            .all(|s| s.focused || !s.actions.contains(binding))
+    }
+    fn admit_change(&mut self, binding: &Binding<A, B>, change: &Change) -> bool {
+        let policy = match self
+            .focus_scopes
+            .values()
+            .find(|s| !s.focused && s.actions.contains(binding))
+        {
+            Some(s) => s.policy,
+            None => return true,
+        };
+        match policy {
+            TransitionPolicy::Release => false,
+            TransitionPolicy::CarryOver => matches!(
+                change,
+                Change::Button(ButtonChange(false)) | Change::Axis(AxisChange::Falling(_))
+            ),
+            TransitionPolicy::Reevaluate => {
+                self.suppress_change(binding, change);
+                false
+            }
+        }
+    }
+    /// Keeps only the changes needed to rebuild what is still held.
+    fn suppress_change(&mut self, binding: &Binding<A, B>, change: &Change) {
+        match change {
+            Change::Axis(AxisChange::Falling(direction)) => {
+                let velocity: Change = AxisChange::Velocity(direction.clone()).into();
+                self.suppressed_changes
+                    .retain(|(b, c)| b != binding || c != &velocity);
+            }
+            Change::Axis(AxisChange::Velocity(_)) => {
+                self.suppressed_changes
+                    .retain(|(b, c)| b != binding || c != change);
+                self.suppressed_changes
+                    .push((binding.clone(), change.clone()));
+            }
+            Change::Button(ButtonChange(pressed)) => {
+                self.suppressed_changes.retain(|(b, _)| b != binding);
+                if *pressed {
+                    self.suppressed_changes
+                        .push((binding.clone(), change.clone()));
+                }
+            }
+            Change::Axis(AxisChange::Position(_))
+            | Change::Value(_)
+            | Change::Pressure(_)
+            | Change::Trigger(_)
+            | Change::Axis2d(_) => {
+                let same_kind = |c: &Change| {
+                    matches!(
+                        (c, change),
+                        (
+                            Change::Axis(AxisChange::Position(_)),
+                            Change::Axis(AxisChange::Position(_))
+                        ) | (Change::Value(_), Change::Value(_))
+                            | (Change::Pressure(_), Change::Pressure(_))
+                            | (Change::Trigger(_), Change::Trigger(_))
+                            | (Change::Axis2d(_), Change::Axis2d(_))
+                    )
+                };
+                self.suppressed_changes
+                    .retain(|(b, c)| b != binding || !same_kind(c));
+                self.suppressed_changes
+                    .push((binding.clone(), change.clone()));
+            }
+        }
+    }
+    fn release_changes(&self, binding: &Binding<A, B>) -> Vec<Change> {
+        match self.states.get(binding) {
+            Some(State::Button(b)) if b.pressed || b.is_held() => {
+                vec![ButtonChange(false).into()]
+            }
+            Some(State::Axis(a)) if a.velocity.is_some() || a.position != 0.0 => {
+                let mut changes = vec![
+                    AxisChange::Falling(Direction::Up).into(),
+                    AxisChange::Falling(Direction::Down).into(),
+                ];
+                if a.velocity.is_none() {
+                    changes.push(AxisChange::Position(0.0).into());
+                }
+                changes
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "filter")]
+impl<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> IChangeFilters<A, B> for Manager<A, B, C> {
+    fn add_filter(&mut self, filter: Box<dyn ChangeFilter<A, B>>) {
+        self.change_filters.push(filter);
+    }
+    fn clear_filters(&mut self) {
+        self.change_filters.clear();
+    }
+}
+
+#[cfg(feature = "filter")]
+impl<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> IStateProcessors<A, B> for Manager<A, B, C> {
+    fn add_state_processor(&mut self, action: Binding<A, B>, processor: Box<dyn StateProcessor>) {
+        self.state_processors.push((action, processor));
+    }
+    fn clear_state_processors(&mut self) {
+        self.state_processors.clear();
+    }
+}
+
+#[cfg(feature = "custom_state")]
+impl<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> ICustomStates<C> for Manager<A, B, C> {
+    fn add_custom_state(&mut self, name: String, state: Box<dyn CustomState>) {
+        self.custom_states.insert(name, state);
+    }
+    fn add_custom_binding(&mut self, name: String, input: C, slot: usize) {
+        self.custom_bindings.insert(input, (name, slot));
+    }
+    fn apply_custom_change(&mut self, name: &str, slot: usize, change: Change) {
+        if let Some(state) = self.custom_states.get_mut(name) {
+            state.apply(slot, &change);
+        }
+    }
+    fn get_custom_state(&self, name: &str) -> Option<&dyn CustomState> {
+        self.custom_states.get(name).map(|state| state.as_ref())
+    }
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> Manager<A, B, C> {
+    fn find_kind_conflict(&self, action: &Binding<A, B>) -> Option<Binding<A, B>> {
+        self.states
+            .keys()
+            .chain(self.registered_actions.iter())
+            .find(|existing| existing.conflicts_with(action))
+            .cloned()
+    }
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> IActions<A, B> for Manager<A, B, C> {
+    fn register_action(&mut self, action: Binding<A, B>) {
+        if let Some(existing) = self.find_kind_conflict(&action) {
+            self.registration_conflicts
+                .push(RegistrationConflict::ActionKind {
+                    existing,
+                    conflicting: action,
+                });
+        } else if !self.registered_actions.contains(&action) {
+            self.registered_actions.push(action);
+        }
+    }
+    fn get_registered_actions(&self) -> &[Binding<A, B>] {
+        &self.registered_actions
+    }
+    fn unbound_actions(&self) -> Vec<&Binding<A, B>> {
+        self.registered_actions
+            .iter()
+            .filter(|action| self.bindings.inputs_for(action).is_empty())
+            .collect()
+    }
+}
+
+impl<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> IActionEvents<A, B, C> for Manager<A, B, C> {
+    fn set_action_events_enabled(&mut self, enabled: bool) {
+        self.action_events = if enabled {
+            self.action_events.take().or_else(|| Some(Vec::new()))
+        } else {
+            None
+        };
+    }
+    fn drain_action_events(&mut self) -> Vec<ActionEvent<A, B, C>> {
+        self.action_events
+            .as_mut()
+            .map(::std::mem::take)
+            .unwrap_or_default()
+    }
+    fn last_source_for(&self, action: &Binding<A, B>) -> Option<&ChangeSource<C>> {
+        self.last_sources.get(action)
+    }
+    #[cfg(feature = "export")]
+    fn set_event_log(&mut self, log: Option<Box<dyn IEventLog<A, B>>>) {
+        self.event_log = log;
+    }
+}
+
+impl<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> ILatencyBudgets<A, B> for Manager<A, B, C> {
+    fn set_latency_budget(&mut self, action: Binding<A, B>, budget: Option<Duration>) {
+        match budget {
+            Some(budget) => self.latency_budgets.insert(action, budget),
+            None => self.latency_budgets.remove(&action),
+        };
+    }
+    fn get_latency_warnings(&self) -> &[LatencyWarning<A, B>] {
+        &self.latency_warnings
+    }
+}
+
+#[cfg(feature = "animation")]
+impl<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> IAnimationParameters<A, B> for Manager<A, B, C> {
+    fn add_animation_parameter(
+        &mut self,
+        name: String,
+        action: Binding<A, B>,
+        smoothing: Duration,
+    ) {
+        self.animation_parameters
+            .insert(name, (action, AnimationParameter::new(smoothing)));
+    }
+    fn remove_animation_parameter(&mut self, name: &str) {
+        self.animation_parameters.remove(name);
+    }
+    fn get_animation_parameter(&self, name: &str) -> Option<f64> {
+        self.animation_parameters
+            .get(name)
+            .map(|(_, parameter)| parameter.value())
+    }
+    fn animation_parameters(&self) -> Vec<(&str, f64)> {
+        self.animation_parameters
+            .iter()
+            .map(|(name, (_, parameter))| (name.as_str(), parameter.value()))
+            .collect()
+    }
+}
+
+impl<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> IClockDomains<A, B> for Manager<A, B, C> {
+    fn set_clock_domain(&mut self, action: Binding<A, B>, domain: ClockDomain) {
+        self.clock_domains.insert(action, domain);
+    }
+    fn set_time_scale(&mut self, scale: f64) -> bool {
+        if !scale.is_finite() {
+            return false;
+        }
+        self.time_scale = scale.max(0.0);
+        true
+    }
+    fn get_time_scale(&self) -> f64 {
+        self.time_scale
+    }
+}
+
+impl<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> IActivity<A, B> for Manager<A, B, C> {
+    fn set_activity_weight(&mut self, action: Binding<A, B>, weight: f64) {
+        self.activity_weights.insert(action, weight);
+    }
+    fn get_activity(&self) -> f64 {
+        self.last_frame
+            .changes
+            .iter()
+            .map(|(binding, change)| {
+                change.magnitude() * self.activity_weights.get(binding).cloned().unwrap_or(1.0)
+            })
+            .sum()
+    }
+}
+
+impl<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> IChangeCursors<A, B> for Manager<A, B, C> {
+    fn new_cursor(&self) -> ChangeCursor {
+        ChangeCursor {
+            seen: self.change_seq,
+        }
+    }
+    fn changes_since(&self, cursor: &mut ChangeCursor) -> Vec<&Binding<A, B>> {
+        let mut changed: Vec<_> = self
+            .last_changed
+            .iter()
+            .filter(|&(_, &seq)| seq > cursor.seen)
+            .collect();
+        changed.sort_by_key(|&(_, &seq)| seq);
+        cursor.seen = self.change_seq;
+        changed.into_iter().map(|(binding, _)| binding).collect()
+    }
+}
+
+#[cfg(feature = "heatmap")]
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> IHeatmaps<A, B>
+    for Manager<A, B, C>
+{
+    fn start_heatmap(&mut self, bucket: Duration, columns: usize, rows: usize) {
+        self.heatmap = Some(Heatmap::new(bucket, columns, rows));
+    }
+    fn record_pointer(&mut self, x: f64, y: f64) {
+        if let Some(ref mut heatmap) = self.heatmap {
+            heatmap.record_point(x, y);
+        }
+    }
+    fn stop_heatmap(&mut self) -> Option<Heatmap<A, B>> {
+        self.heatmap.take()
+    }
+}
+
+#[cfg(feature = "audit")]
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> IAudit for Manager<A, B, C> {
+    fn start_audit(&mut self) {
+        self.audit = Some(AuditTrace::default());
+    }
+    fn stop_audit(&mut self) -> Option<AuditTrace> {
+        self.audit.take()
+    }
+    fn get_state_checksum(&self) -> u64 {
+        self.states
+            .iter()
+            .map(|entry| stable_hash(&entry))
+            .fold(0, u64::wrapping_add)
+    }
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq + Clone> ISaveState<A, B>
+    for Manager<A, B, C>
+{
+    fn save(&self) -> SaveState<A, B> {
+        SaveState {
+            snapshot: self.snapshot(),
+            #[cfg(feature = "recording")]
+            macro_recorder: self.macro_recorder.clone(),
+            #[cfg(feature = "recording")]
+            macro_player: self.macro_player.clone(),
+            frame_changes: self.frame_changes.clone(),
+            last_frame: self.last_frame.clone(),
+            #[cfg(feature = "frame_clock")]
+            frame_clock: self.frame_clock.clone(),
+        }
+    }
+    fn load(&mut self, state: &SaveState<A, B>) {
+        self.rewind_to(&state.snapshot);
+        #[cfg(feature = "recording")]
+        {
+            self.macro_recorder = state.macro_recorder.clone();
+            self.macro_player = state.macro_player.clone();
+        }
+        self.frame_changes = state.frame_changes.clone();
+        self.last_frame = state.last_frame.clone();
+        #[cfg(feature = "frame_clock")]
+        self.frame_clock.clone_from(&state.frame_clock);
+    }
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq + Clone> IRollback<A, B>
+    for Manager<A, B, C>
+{
+    fn snapshot(&self) -> Snapshot<A, B> {
+        Snapshot {
+            states: self.states.clone(),
+            #[cfg(feature = "combo")]
+            combos: self.combos.clone(),
+            queued_changes: self.queued_changes.clone(),
+            delayed_changes: self.delayed_changes.clone(),
+            press_order: self.press_order.clone(),
+        }
+    }
+    fn rewind_to(&mut self, snapshot: &Snapshot<A, B>) {
+        self.states.restore_from(&snapshot.states);
+        #[cfg(feature = "combo")]
+        self.combos.clone_from(&snapshot.combos);
+        self.queued_changes = snapshot.queued_changes.clone();
+        self.delayed_changes = snapshot.delayed_changes.clone();
+        self.press_order = snapshot.press_order.clone();
+    }
+    fn replay_changes(&mut self, frames: &[InputFrame<A, B>]) {
+        for frame in frames {
+            for (binding, change) in frame.changes.iter() {
+                self.enqueue_change(binding, change.clone(), None);
+            }
+            self.tick(frame.delta);
+        }
+    }
+    fn get_last_frame(&self) -> &InputFrame<A, B> {
+        &self.last_frame
+    }
+}
+
+#[cfg(feature = "recording")]
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> IMacros<A, B, C>
+    for Manager<A, B, C>
+{
+    fn start_macro_recording(&mut self) {
+        self.macro_recorder = Some(MacroRecorder::default());
+    }
+    fn stop_macro_recording(&mut self) -> Option<InputMacro<A, B>> {
+        self.macro_recorder.take().map(|recorder| recorder.finish())
+    }
+    fn add_macro_binding(&mut self, input: C, input_macro: InputMacro<A, B>, speed: f64) -> bool {
+        if !speed.is_finite() || speed < 0.0 {
+            return false;
+        }
+        self.macro_bindings.insert(input, (input_macro, speed));
+        true
+    }
+    fn play_macro(&mut self, input: C) -> bool {
+        match self.macro_bindings.get(&input).cloned() {
+            Some((input_macro, speed)) => self.macro_player.play(input_macro, speed),
+            None => false,
+        }
+    }
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq + Clone> IConverter<A, B, C>
+    for Manager<A, B, C>
+{
+    fn add_axis_binding(&mut self, axis: A, input: C) {
+        self.bind(input, Binding::Axis(axis));
+    }
+
+    fn add_default_change(&mut self, change: Change, input: C) {
+        if let Some(binding) = self.bindings.resolve(&input) {
+            if !binding.accepts(&change) {
+                let binding = binding.clone();
+                self.registration_conflicts
+                    .push(RegistrationConflict::DefaultChange {
+                        input,
+                        binding,
+                        change,
+                    });
+                return;
+            }
+        }
+        self.bindings.add_default_change(input, change);
+    }
+
+    fn get_binding(&self, input: C) -> Option<Binding<A, B>> {
+        self.resolve_action(&input)
+    }
+    fn process_input<T: Into<Change>>(&mut self, input: C, change: T) -> Result<bool, InputError> {
+        self.apply_input(input, change.into(), None)
+    }
+    fn get_default_change(&self, input: C) -> Option<Change> {
+        self.bindings.get_default_change(&input).cloned()
+    }
+
+    fn add_button_binding(&mut self, button: B, input: C) {
+        self.bind(input, Binding::Button(button));
+    }
+
+    fn add_axis_button_binding(&mut self, button: B, input: C, threshold: f64) {
+        self.bindings.set_input_threshold(input.clone(), threshold);
+        self.bind(input, Binding::Button(button));
+    }
+
+    fn add_value_binding(&mut self, value: A, input: C) {
+        self.bind(input, Binding::Value(value));
+    }
+
+    fn add_accumulator_binding(&mut self, accumulator: A, input: C) {
+        self.bind(input, Binding::Accumulator(accumulator));
+    }
+
+    fn add_trigger_binding(&mut self, trigger: A, input: C) {
+        self.bind(input, Binding::Trigger(trigger));
+    }
+
+    fn add_axis2d_binding(&mut self, axis: A, input: C) {
+        self.bind(input, Binding::Axis2d(axis));
+    }
+
+    fn configure_button(&mut self, button: B, config: ButtonConfig) {
+        if let State::Button(b) = self
+            .states
+            .get_or_insert_with(Binding::Button(button), || State::Button(Button::default()))
+        {
+            b.configure(config);
+        }
+    }
+
+    fn configure_axis(&mut self, axis: A, config: AxisConfig) -> bool {
+        match self
+            .states
+            .get_or_insert_with(Binding::Axis(axis), || State::Axis(Axis::default()))
+        {
+            State::Axis(a) => a.configure(config),
+            _ => false,
+        }
+    }
+
+    fn configure_axis2d(&mut self, axis: A, config: InputAxisConfig) {
+        if let State::Axis2d(a) = self
+            .states
+            .get_or_insert_with(Binding::Axis2d(axis), || State::Axis2d(Axis2d::default()))
+        {
+            a.configure(config);
+        }
+    }
+
+    #[cfg(feature = "combo")]
+    fn add_combo(&mut self, name: String, combo: Combo<A, B>) {
+        self.combos.add_combo(name, combo);
+    }
+
+    #[cfg(feature = "combo")]
+    fn add_hold_confirm(&mut self, action: B, confirm: HoldConfirm<B>) {
+        let binding = Binding::Button(action.clone());
+        self.states
+            .get_or_insert_with(binding, || State::Button(Button::default()));
+        self.hold_confirms
+            .retain(|(existing, _)| existing != &action);
+        self.hold_confirms.push((action, confirm));
+    }
+
+    #[cfg(feature = "drift")]
+    fn watch_axis_drift(&mut self, axis: A, config: DriftConfig) {
+        self.drift_detectors
+            .insert(axis, DriftDetector::new(config));
+    }
+
+    #[cfg(feature = "timing")]
+    fn watch_button_timing(&mut self, button: B, window: usize) {
+        self.button_timings
+            .insert(button, ButtonTimings::new(window));
+    }
+
+    fn get_binding_map(&self) -> &BindingMap<A, B, C> {
+        &self.bindings
+    }
+
+    fn set_binding_map(&mut self, binding_map: BindingMap<A, B, C>) {
+        self.commit_binding_edit(BindingEdit::new(binding_map.get_bindings().clone()));
+        self.bindings = binding_map;
+    }
+
+    fn get_registration_conflicts(&self) -> &[RegistrationConflict<A, B, C>] {
+        &self.registration_conflicts
+    }
+
+    fn set_input_index(&mut self, index: fn(&C) -> usize) {
+        self.bindings.set_input_index(index);
+    }
+
+    fn configure_input_axis(&mut self, input: C, config: InputAxisConfig) {
+        self.bindings.set_input_config(input, config);
+    }
+
+    fn remap_action(&mut self, from: Binding<A, B>, to: Binding<A, B>) -> bool {
+        if discriminant(&from) != discriminant(&to) {
+            return false;
+        }
+        self.states
+            .get_or_insert_with(to.clone(), || Self::default_state(&to));
+        self.action_remap.insert(from, to);
+        true
+    }
+
+    fn remove_action_remap(&mut self, from: &Binding<A, B>) {
+        self.action_remap.remove(from);
+    }
+
+    fn set_strong_press_window(&mut self, window: Duration) {
+        self.strong_press_window = window;
+    }
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq> IManager<A, B, C>
+    for Manager<A, B, C>
+{
+    fn new() -> Self {
+        Manager {
+            states: StateArena::default(),
+            bindings: BindingMap::new(),
+            #[cfg(feature = "combo")]
+            combos: ComboRegistry::default(),
+            #[cfg(feature = "recording")]
+            macro_recorder: None,
+            #[cfg(feature = "recording")]
+            macro_bindings: HashMap::new(),
+            #[cfg(feature = "recording")]
+            macro_player: MacroPlayer::default(),
+            #[cfg(feature = "recording")]
+            held_macro_inputs: HashSet::new(),
+            binding_undo: Vec::new(),
+            binding_redo: Vec::new(),
+            sub_tick_ordering: SubTickOrdering::default(),
+            queued_changes: Vec::new(),
+            #[cfg(feature = "polling")]
+            poller: None,
+            #[cfg(feature = "polling")]
+            poll_errors: Vec::new(),
+            input_delay: 0,
+            delayed_changes: VecDeque::new(),
+            #[cfg(feature = "audit")]
+            audit: None,
+            #[cfg(feature = "heatmap")]
+            heatmap: None,
+            frame_changes: Vec::new(),
+            last_frame: InputFrame {
+                changes: Vec::new(),
+                delta: Duration::default(),
+            },
+            #[cfg(feature = "drift")]
+            drift_detectors: HashMap::new(),
+            #[cfg(feature = "timing")]
+            button_timings: HashMap::new(),
+            #[cfg(feature = "combo")]
+            hold_confirms: Vec::new(),
+            #[cfg(feature = "export")]
+            event_log: None,
+            frame: 0,
+            paused: false,
+            stepping: false,
+            paused_changes: Vec::new(),
+            action_remap: HashMap::new(),
+            clock: Duration::default(),
+            press_rises: HashMap::new(),
+            threshold_held: HashSet::new(),
+            press_speeds: HashMap::new(),
+            strong_press_window: Duration::from_millis(50),
+            #[cfg(feature = "combo")]
+            confirmed: Vec::new(),
+            #[cfg(feature = "device")]
+            device_classifier: None,
+            #[cfg(feature = "device")]
+            device_tracker: ActiveDeviceTracker::default(),
+            #[cfg(feature = "contexts")]
+            focus_scopes: HashMap::new(),
+            #[cfg(feature = "contexts")]
+            suppressed_changes: Vec::new(),
+            #[cfg(feature = "contexts")]
+            pending_scope_transitions: Vec::new(),
+            #[cfg(feature = "contexts")]
+            scope_transitions: Vec::new(),
+            #[cfg(feature = "frame_clock")]
+            frame_clock: FrameClock::default(),
+            #[cfg(feature = "filter")]
+            change_filters: Vec::new(),
+            #[cfg(feature = "filter")]
+            state_processors: Vec::new(),
+            #[cfg(feature = "custom_state")]
+            custom_states: HashMap::new(),
+            #[cfg(feature = "custom_state")]
+            custom_bindings: HashMap::new(),
+            registered_actions: Vec::new(),
+            registration_conflicts: Vec::new(),
+            change_seq: 0,
+            last_changed: HashMap::new(),
+            press_order: Vec::new(),
+            manual_frames: false,
+            last_sources: HashMap::new(),
+            pending_source: None,
+            action_events: None,
+            clock_domains: HashMap::new(),
+            time_scale: 1.0,
+            activity_weights: HashMap::new(),
+            latency_budgets: HashMap::new(),
+            pending_latencies: Vec::new(),
+            latency_warnings: Vec::new(),
+            #[cfg(feature = "animation")]
+            animation_parameters: HashMap::new(),
+        }
+    }
+
+    fn get_axis(&self, binding: A) -> Option<&Axis> {
+        self.states.get(&Binding::Axis(binding)).and_then(|val| {
+            if let State::Axis(a) = val {
+                Some(a)
+            } else {
+                None
+            }
+        })
+    }
+    fn get_states(&self) -> &StateArena<A, B> {
+        &self.states
+    }
+    fn axis_handle(&self, axis: A) -> Option<AxisHandle> {
+        self.states.handle(&Binding::Axis(axis)).map(AxisHandle)
+    }
+    fn button_handle(&self, button: B) -> Option<ButtonHandle> {
+        self.states
+            .handle(&Binding::Button(button))
+            .map(ButtonHandle)
+    }
+    fn value_handle(&self, value: A) -> Option<ValueHandle> {
+        self.states.handle(&Binding::Value(value)).map(ValueHandle)
+    }
+    fn get_axis_by_handle(&self, handle: AxisHandle) -> Option<&Axis> {
+        match self.states.get_by_handle(handle.0) {
+            Some(State::Axis(axis)) => Some(axis),
+            _ => None,
+        }
+    }
+    fn get_button_by_handle(&self, handle: ButtonHandle) -> Option<&Button> {
+        match self.states.get_by_handle(handle.0) {
+            Some(State::Button(button)) => Some(button),
+            _ => None,
+        }
+    }
+    fn get_value_by_handle(&self, handle: ValueHandle) -> Option<&Value> {
+        match self.states.get_by_handle(handle.0) {
+            Some(State::Value(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn get_button(&self, binding: B) -> Option<&Button> {
+        self.states.get(&Binding::Button(binding)).and_then(|val| {
+            if let State::Button(b) = val {
+                Some(b)
+            } else {
+                None
+            }
+        })
+    }
+    fn get_value(&self, binding: A) -> Option<&Value> {
+        match self.states.get(&Binding::Value(binding)) {
+            Some(State::Value(v)) => Some(v),
+            _ => None,
+        }
+    }
+    fn get_accumulator(&self, binding: A) -> Option<&Accumulator> {
+        match self.states.get(&Binding::Accumulator(binding)) {
+            Some(State::Accumulator(a)) => Some(a),
+            _ => None,
+        }
+    }
+    fn get_trigger(&self, binding: A) -> Option<&Trigger> {
+        match self.states.get(&Binding::Trigger(binding)) {
+            Some(State::Trigger(t)) => Some(t),
+            _ => None,
+        }
+    }
+    fn get_axis2d(&self, binding: A) -> Option<&Axis2d> {
+        match self.states.get(&Binding::Axis2d(binding)) {
+            Some(State::Axis2d(s)) => Some(s),
+            _ => None,
+        }
+    }
+    fn axes(&self) -> StateIter<'_, A, Axis> {
+        Box::new(self.states.iter().filter_map(|entry| match entry {
+            (Binding::Axis(key), State::Axis(axis)) => Some((key, axis)),
+            _ => None,
+        }))
+    }
+    fn buttons(&self) -> StateIter<'_, B, Button> {
+        Box::new(self.states.iter().filter_map(|entry| match entry {
+            (Binding::Button(key), State::Button(button)) => Some((key, button)),
+            _ => None,
+        }))
+    }
+    fn values(&self) -> StateIter<'_, A, Value> {
+        Box::new(self.states.iter().filter_map(|entry| match entry {
+            (Binding::Value(key), State::Value(value)) => Some((key, value)),
+            _ => None,
+        }))
+    }
+    fn held_buttons_ordered(&self) -> &[B] {
+        &self.press_order
+    }
+    fn get_changed_buttons(&self) -> HashMap<&B, &Button> {
+        self.buttons().filter(|(_, b)| b.new_event).collect()
+    }
+
+    fn get_button_pressed(&self, button: B) -> bool {
+        self.get_button(button)
+            .map(|button| button.fired())
+            .unwrap_or(false)
+    }
+    fn get_button_released(&self, button: B) -> bool {
+        self.get_button(button)
+            .map(|button| button.just_released)
+            .unwrap_or(false)
+    }
+    fn get_button_held_duration(&self, button: B) -> Duration {
+        self.get_button(button)
+            .map(|button| button.held_duration)
+            .unwrap_or_default()
+    }
+    fn get_button_value(&self, button: B) -> f64 {
+        self.get_button(button)
+            .map(|button| button.value)
+            .unwrap_or_default()
+    }
+
+    #[cfg(feature = "combo")]
+    fn get_completed_combos(&self) -> &[String] {
+        self.combos.get_completed()
+    }
+
+    #[cfg(feature = "drift")]
+    fn get_drift_report(&self, axis: A) -> Option<DriftReport> {
+        self.drift_detectors
+            .get(&axis)
+            .and_then(|detector| detector.report())
+    }
+
+    #[cfg(feature = "timing")]
+    fn get_button_timing(&self, button: B) -> Option<&ButtonTimings> {
+        self.button_timings.get(&button)
+    }
+
+    fn get_press_speed(&self, button: B) -> Option<Duration> {
+        self.press_speeds.get(&button).cloned()
+    }
+
+    fn get_press_strength(&self, button: B) -> Option<PressStrength> {
+        self.get_press_speed(button).map(|speed| {
+            if speed <= self.strong_press_window {
+                PressStrength::Strong
+            } else {
+                PressStrength::Weak
+            }
+        })
+    }
+
+    fn get_axis_activity(&self, axis: A) -> Option<AxisActivity> {
+        self.get_axis(axis).and_then(|axis| axis.activity())
+    }
+    fn get_zone_events(&self, axis: A) -> &[ZoneEvent] {
+        self.get_axis(axis).map_or(&[], |axis| axis.zone_events())
+    }
+    fn get_axis_in_zone(&self, axis: A, zone: &str) -> bool {
+        self.get_axis(axis).is_some_and(|axis| axis.in_zone(zone))
+    }
+
+    fn get_axis_output(&self, axis: A) -> f64 {
+        self.get_axis(axis)
+            .map(|axis| axis.output())
+            .unwrap_or_default()
+    }
+
+    fn get_cooldown_remaining(&self, button: B) -> Duration {
+        self.get_button(button)
+            .map(|button| button.cooldown_remaining())
+            .unwrap_or_default()
+    }
+
+    fn get_button_edges(&self, button: B) -> &[ButtonEdge] {
+        self.get_button(button)
+            .map(|button| button.edges())
+            .unwrap_or(&[])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use change::Direction;
     #[derive(PartialEq, Eq, Hash, Debug, Clone)]
     enum Axes {
         Vertical,
     }
-    #[derive(PartialEq, Eq, Hash, Debug, Clone)]
-    enum Buttons {
-        Fire,
-        Block,
+    #[derive(PartialEq, Eq, Hash, Debug, Clone)]
+    enum Buttons {
+        Fire,
+        Block,
+        #[cfg(feature = "combo")]
+        Delete,
+    }
+    #[derive(PartialEq, Eq, Hash, Debug, Clone)]
+    enum Keyboard {
+        A,
+        B,
+    }
+    #[derive(PartialEq, Eq, Hash, Debug, Clone)]
+    enum GamePadInput {
+        Left,
+        Right,
+    }
+    #[derive(PartialEq, Eq, Hash, Debug, Clone)]
+    enum Input {
+        Button(Keyboard),
+        Gamepad(GamePadInput),
+    }
+    #[test]
+    fn can_add_axis() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Button(Keyboard::A));
+        assert_eq!(manager.get_binding_map().get_bindings().len(), 1);
+        assert_eq!(
+            manager.get_binding(Input::Button(Keyboard::A)).unwrap(),
+            Binding::Axis(Axes::Vertical)
+        );
+        assert_eq!(manager.get_axis(Axes::Vertical).unwrap(), &Axis::default());
+    }
+    #[test]
+    fn can_add_button() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        assert_eq!(manager.states.len(), 1);
+        assert_eq!(
+            manager.get_binding(Input::Button(Keyboard::A)).unwrap(),
+            Binding::Button(Buttons::Fire)
+        );
+        assert_eq!(
+            manager.get_button(Buttons::Fire).unwrap(),
+            &Button::default()
+        );
+    }
+
+    #[test]
+    fn can_update_axis_pos() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Button(Keyboard::A));
+        let binding = manager.get_binding(Input::Button(Keyboard::A)).unwrap();
+        manager
+            .apply_change(&binding, AxisChange::Position(0.5).into())
+            .unwrap();
+        assert_eq!(manager.get_axis(Axes::Vertical).unwrap().position, 0.5);
+    }
+    #[test]
+    fn can_update_axis_vel() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Button(Keyboard::A));
+        let binding = &manager
+            .get_binding(Input::Button(Keyboard::A))
+            .unwrap()
+            .clone();
+        manager
+            .apply_change(binding, AxisChange::Velocity(Direction::Up).into())
+            .unwrap();
+        assert_eq!(
+            manager.get_axis(Axes::Vertical).unwrap().velocity,
+            Some(Direction::Up)
+        );
+    }
+
+    #[test]
+    fn can_toggle_button() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        let binding = &manager
+            .get_binding(Input::Button(Keyboard::A))
+            .unwrap()
+            .clone();
+        manager
+            .apply_change(binding, ButtonChange(true).into())
+            .unwrap();
+        assert_eq!(manager.get_button(Buttons::Fire).unwrap().pressed, true);
+        let binding = &manager
+            .get_binding(Input::Button(Keyboard::A))
+            .unwrap()
+            .clone();
+        manager
+            .apply_change(binding, ButtonChange(false).into())
+            .unwrap();
+        assert_eq!(manager.get_button(Buttons::Fire).unwrap().pressed, false);
+    }
+
+    #[test]
+    fn get_changed_buttons() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_button_binding(Buttons::Block, Input::Button(Keyboard::B));
+        let binding = &manager
+            .get_binding(Input::Button(Keyboard::A))
+            .unwrap()
+            .clone();
+        manager
+            .apply_change(binding, ButtonChange(true).into())
+            .unwrap();
+        assert_eq!(manager.get_changed_buttons().len(), 1);
+        manager.tick(Duration::default());
+        assert_eq!(manager.get_changed_buttons().len(), 0);
+    }
+
+    #[test]
+    fn configure_button_keeps_config_when_bound() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.configure_button(
+            Buttons::Fire,
+            ButtonConfig {
+                slow_keys: Some(Duration::from_millis(100)),
+                ..ButtonConfig::default()
+            },
+        );
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        let binding = manager.get_binding(Input::Button(Keyboard::A)).unwrap();
+        manager
+            .apply_change(&binding, ButtonChange(true).into())
+            .unwrap();
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+        manager.tick(Duration::from_millis(100));
+        assert!(manager.get_button_pressed(Buttons::Fire));
+    }
+
+    #[test]
+    #[cfg(feature = "combo")]
+    fn reports_completed_combos() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_combo(
+            "Uppercut".to_string(),
+            Combo {
+                steps: vec![
+                    ComboStep::Axis(Axes::Vertical, Direction::Up),
+                    ComboStep::Button(Buttons::Fire),
+                ],
+                window: Duration::from_millis(250),
+            },
+        );
+        manager
+            .apply_change(
+                &Binding::Axis(Axes::Vertical),
+                AxisChange::Velocity(Direction::Up).into(),
+            )
+            .unwrap();
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        assert_eq!(manager.get_completed_combos(), &["Uppercut".to_string()]);
+        manager.tick(Duration::from_millis(16));
+        assert!(manager.get_completed_combos().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "recording")]
+    fn can_record_and_play_macro() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.start_macro_recording();
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(50));
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(false).into())
+            .unwrap();
+        let input_macro = manager.stop_macro_recording().unwrap();
+        manager.tick(Duration::from_millis(16));
+
+        assert!(!manager.add_macro_binding(
+            Input::Button(Keyboard::B),
+            input_macro.clone(),
+            f64::INFINITY
+        ));
+        assert!(!manager.play_macro(Input::Button(Keyboard::B)));
+        assert!(manager.add_macro_binding(Input::Button(Keyboard::B), input_macro, 1.0));
+        assert!(manager.play_macro(Input::Button(Keyboard::B)));
+        assert!(!manager.play_macro(Input::Button(Keyboard::A)));
+        manager.tick(Duration::from_millis(16));
+        assert!(manager.get_button_pressed(Buttons::Fire));
+        manager.tick(Duration::from_millis(50));
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+    }
+
+    #[test]
+    #[cfg(feature = "recording")]
+    fn bound_input_starts_macro() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.start_macro_recording();
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(50));
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(false).into())
+            .unwrap();
+        let input_macro = manager.stop_macro_recording().unwrap();
+        manager.tick(Duration::from_millis(100));
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+
+        manager.add_macro_binding(Input::Button(Keyboard::B), input_macro, 1.0);
+        assert!(manager
+            .process_input(Input::Button(Keyboard::B), ButtonChange(true))
+            .unwrap());
+        manager.tick(Duration::from_millis(16));
+        assert!(manager.get_button_pressed(Buttons::Fire));
+        manager.tick(Duration::from_millis(50));
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+        // Holding the key doesn't restart playback.
+        manager
+            .process_input(Input::Button(Keyboard::B), ButtonChange(true))
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+    }
+
+    #[test]
+    #[cfg(feature = "combo")]
+    fn negative_edge_feeds_combos_on_release() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_button_binding(Buttons::Block, Input::Button(Keyboard::B));
+        manager.configure_button(
+            Buttons::Fire,
+            ButtonConfig {
+                edge: ButtonEdge::Release,
+                ..ButtonConfig::default()
+            },
+        );
+        manager.add_combo(
+            "Counter".to_string(),
+            Combo {
+                steps: vec![
+                    ComboStep::Button(Buttons::Block),
+                    ComboStep::Button(Buttons::Fire),
+                ],
+                window: Duration::from_millis(250),
+            },
+        );
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        manager
+            .apply_change(&Binding::Button(Buttons::Block), ButtonChange(true).into())
+            .unwrap();
+        assert!(!manager.get_button_pressed(Buttons::Fire));
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(false).into())
+            .unwrap();
+        assert!(manager.get_button_pressed(Buttons::Fire));
+        manager.tick(Duration::from_millis(16));
+        assert_eq!(manager.get_completed_combos(), &["Counter".to_string()]);
+    }
+
+    #[test]
+    fn binding_edits_apply_only_on_commit() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        let mut edit = manager.begin_binding_edit();
+        edit.add_button_binding(Buttons::Block, Input::Button(Keyboard::A));
+        assert_eq!(edit.conflicts().len(), 2);
+        assert_eq!(
+            manager.get_binding(Input::Button(Keyboard::A)),
+            Some(Binding::Button(Buttons::Fire))
+        );
+        edit.commit(&mut manager);
+        assert_eq!(
+            manager.get_binding(Input::Button(Keyboard::A)),
+            Some(Binding::Button(Buttons::Block))
+        );
+        assert!(manager.get_button(Buttons::Block).is_some());
+    }
+
+    #[test]
+    fn can_undo_and_redo_binding_changes() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_button_binding(Buttons::Block, Input::Button(Keyboard::A));
+        assert!(manager.undo_binding_change());
+        assert_eq!(
+            manager.get_binding(Input::Button(Keyboard::A)),
+            Some(Binding::Button(Buttons::Fire))
+        );
+        assert!(manager.undo_binding_change());
+        assert_eq!(manager.get_binding(Input::Button(Keyboard::A)), None);
+        assert!(!manager.undo_binding_change());
+        assert!(manager.redo_binding_change());
+        assert!(manager.redo_binding_change());
+        assert!(!manager.redo_binding_change());
+        assert_eq!(
+            manager.get_binding(Input::Button(Keyboard::A)),
+            Some(Binding::Button(Buttons::Block))
+        );
+    }
+    #[test]
+    fn committed_edit_undoes_as_one_step() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        let mut edit = manager.begin_binding_edit();
+        edit.add_button_binding(Buttons::Block, Input::Button(Keyboard::A));
+        edit.add_button_binding(Buttons::Fire, Input::Button(Keyboard::B));
+        edit.commit(&mut manager);
+        assert!(manager.undo_binding_change());
+        assert_eq!(
+            manager.get_binding(Input::Button(Keyboard::A)),
+            Some(Binding::Button(Buttons::Fire))
+        );
+        assert_eq!(manager.get_binding(Input::Button(Keyboard::B)), None);
+    }
+
+    #[test]
+    #[cfg(feature = "profile")]
+    fn can_save_and_load_profiles() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        let keyboard = manager.get_binding_profile();
+        let mut gamepad = BindingProfile::new();
+        gamepad.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager.load_binding_profile(&gamepad);
+        assert_eq!(manager.get_binding(Input::Button(Keyboard::A)), None);
+        assert_eq!(keyboard.diff(&manager.get_binding_profile()).added.len(), 1);
+        assert!(manager.undo_binding_change());
+        assert_eq!(manager.get_binding_profile(), keyboard);
+    }
+
+    #[test]
+    fn timestamps_reach_state() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        let at = Duration::from_millis(1234);
+        manager
+            .apply_change_at(
+                &Binding::Button(Buttons::Fire),
+                ButtonChange(true).into(),
+                at,
+            )
+            .unwrap();
+        manager
+            .apply_change_at(
+                &Binding::Axis(Axes::Vertical),
+                AxisChange::Position(0.5).into(),
+                at,
+            )
+            .unwrap();
+        assert_eq!(
+            manager.get_button(Buttons::Fire).unwrap().last_changed_at(),
+            Some(at)
+        );
+        assert_eq!(
+            manager.get_axis(Axes::Vertical).unwrap().last_changed_at(),
+            Some(at)
+        );
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(false).into())
+            .unwrap();
+        assert_eq!(
+            manager.get_button(Buttons::Fire).unwrap().last_changed_at(),
+            None
+        );
+    }
+
+    #[test]
+    fn ordered_changes_apply_by_timestamp_on_tick() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager.set_sub_tick_ordering(SubTickOrdering::Ordered);
+        let binding = Binding::Axis(Axes::Vertical);
+        manager
+            .apply_change_at(
+                &binding,
+                AxisChange::Position(0.2).into(),
+                Duration::from_millis(8),
+            )
+            .unwrap();
+        manager
+            .apply_change_at(
+                &binding,
+                AxisChange::Position(0.7).into(),
+                Duration::from_millis(4),
+            )
+            .unwrap();
+        assert_eq!(manager.get_axis(Axes::Vertical).unwrap().position, 0.0);
+        manager.tick(Duration::from_millis(16));
+        assert_eq!(manager.get_axis(Axes::Vertical).unwrap().position, 0.2);
+    }
+    #[test]
+    fn integrated_changes_keep_intra_frame_movement() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager.set_sub_tick_ordering(SubTickOrdering::Integrated);
+        let binding = Binding::Axis(Axes::Vertical);
+        manager
+            .apply_change_at(
+                &binding,
+                AxisChange::Velocity(Direction::Up).into(),
+                Duration::from_millis(0),
+            )
+            .unwrap();
+        manager
+            .apply_change_at(
+                &binding,
+                AxisChange::Falling(Direction::Up).into(),
+                Duration::from_millis(100),
+            )
+            .unwrap();
+        manager.tick(Duration::from_millis(120));
+        assert_eq!(manager.get_axis(Axes::Vertical).unwrap().position, 0.16);
+    }
+
+    #[test]
+    fn integrated_changes_follow_mid_frame_velocity() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager.set_sub_tick_ordering(SubTickOrdering::Integrated);
+        manager.set_time_scale(0.5);
+        let binding = Binding::Axis(Axes::Vertical);
+        manager
+            .apply_change_at(
+                &binding,
+                AxisChange::Velocity(Direction::Up).into(),
+                Duration::from_millis(0),
+            )
+            .unwrap();
+        manager.tick(Duration::from_millis(100));
+        assert_eq!(manager.get_axis(Axes::Vertical).unwrap().position, 0.1);
+        manager
+            .apply_change_at(
+                &binding,
+                AxisChange::Velocity(Direction::Down).into(),
+                Duration::from_millis(175),
+            )
+            .unwrap();
+        manager.tick(Duration::from_millis(100));
+        let position = manager.get_axis(Axes::Vertical).unwrap().position;
+        assert!((position - 0.15).abs() < 1e-9, "{}", position);
+    }
+
+    #[test]
+    fn queued_edges_survive_intra_frame_tap() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(false).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        assert_eq!(
+            manager.get_button_edges(Buttons::Fire),
+            &[ButtonEdge::Press, ButtonEdge::Release]
+        );
+        assert!(manager.get_button_edges(Buttons::Block).is_empty());
+    }
+
+    #[test]
+    #[cfg(all(feature = "device", feature = "polling"))]
+    fn tick_consumes_polled_inputs() {
+        struct Pressed(bool);
+        impl InputSource<Input> for Pressed {
+            fn poll(&mut self) -> Vec<(Input, Change)> {
+                if self.0 {
+                    vec![]
+                } else {
+                    self.0 = true;
+                    vec![(Input::Button(Keyboard::A), ButtonChange(true).into())]
+                }
+            }
+        }
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.set_action_events_enabled(true);
+        manager.start_polling(vec![Box::new(Pressed(false))], Duration::from_millis(1));
+        for _ in 0..1000 {
+            manager.tick(Duration::from_millis(1));
+            if manager.get_button(Buttons::Fire).unwrap().pressed {
+                break;
+            }
+            ::std::thread::sleep(Duration::from_millis(1));
+        }
+        manager.stop_polling();
+        assert!(manager.get_button(Buttons::Fire).unwrap().pressed);
+        assert_eq!(
+            manager.last_source_for(&Binding::Button(Buttons::Fire)),
+            Some(&ChangeSource {
+                input: Input::Button(Keyboard::A),
+                device: None,
+            })
+        );
+        let events = manager.drain_action_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].source.as_ref().map(|source| &source.input),
+            Some(&Input::Button(Keyboard::A))
+        );
+    }
+
+    #[test]
+    fn input_delay_holds_changes_for_frames() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.set_input_delay(2);
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+        manager.tick(Duration::from_millis(16));
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+        manager.tick(Duration::from_millis(16));
+        assert!(manager.get_button_pressed(Buttons::Fire));
+    }
+
+    #[test]
+    fn rewind_and_replay_matches_forward_simulation() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        let frames = vec![
+            InputFrame {
+                changes: vec![(
+                    Binding::Axis(Axes::Vertical),
+                    AxisChange::Velocity(Direction::Up).into(),
+                )],
+                delta: Duration::from_millis(100),
+            },
+            InputFrame {
+                changes: vec![(Binding::Button(Buttons::Fire), ButtonChange(true).into())],
+                delta: Duration::from_millis(100),
+            },
+        ];
+        let snapshot = manager.snapshot();
+        manager.replay_changes(&frames);
+        let forward = manager.get_states().clone();
+        manager.rewind_to(&snapshot);
+        assert_eq!(manager.get_axis(Axes::Vertical).unwrap().position, 0.0);
+        manager.replay_changes(&frames);
+        assert_eq!(manager.get_states(), &forward);
+    }
+
+    #[test]
+    #[cfg(feature = "audit")]
+    fn audit_traces_match_for_identical_runs() {
+        let run = |position: f64| {
+            let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+            manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+            manager.start_audit();
+            manager.tick(Duration::from_millis(16));
+            manager
+                .apply_change(
+                    &Binding::Axis(Axes::Vertical),
+                    AxisChange::Position(position).into(),
+                )
+                .unwrap();
+            manager.tick(Duration::from_millis(16));
+            manager.stop_audit().unwrap()
+        };
+        assert_eq!(run(0.5).compare(&run(0.5)), None);
+        assert_eq!(
+            run(0.5).compare(&run(0.25)),
+            Some(AuditDivergence::Changes(1))
+        );
+    }
+
+    #[test]
+    fn last_frame_collects_tick_inputs() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        assert_eq!(
+            manager.get_last_frame(),
+            &InputFrame {
+                changes: vec![(Binding::Button(Buttons::Fire), ButtonChange(true).into())],
+                delta: Duration::from_millis(16),
+            }
+        );
+        manager.tick(Duration::from_millis(16));
+        assert!(manager.get_last_frame().changes.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "drift")]
+    fn reports_drift_on_watched_axis() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager.watch_axis_drift(Axes::Vertical, DriftConfig::default());
+        manager
+            .apply_change(
+                &Binding::Axis(Axes::Vertical),
+                AxisChange::Position(0.08).into(),
+            )
+            .unwrap();
+        manager.tick(Duration::from_secs(1));
+        assert_eq!(manager.get_drift_report(Axes::Vertical), None);
+        manager.tick(Duration::from_secs(1));
+        assert!(manager.get_drift_report(Axes::Vertical).is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "device")]
+    fn tracks_active_device() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.set_device_classifier(|input| match input {
+            Input::Button(_) => DeviceClass::KeyboardMouse,
+            Input::Gamepad(_) => DeviceClass::Gamepad(0),
+        });
+        manager.observe_input(
+            &Input::Gamepad(GamePadInput::Left),
+            &ButtonChange(true).into(),
+        );
+        manager.tick(Duration::default());
+        assert_eq!(manager.get_active_device(), Some(DeviceClass::Gamepad(0)));
+        assert_eq!(
+            manager.get_device_switch().map(|switch| switch.to),
+            Some(DeviceClass::Gamepad(0))
+        );
+        manager.tick(Duration::default());
+        assert_eq!(manager.get_device_switch(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "device")]
+    fn prompt_follows_active_device() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_button_binding(Buttons::Fire, Input::Gamepad(GamePadInput::Right));
+        manager.set_device_classifier(|input| match input {
+            Input::Button(_) => DeviceClass::KeyboardMouse,
+            Input::Gamepad(_) => DeviceClass::Gamepad(0),
+        });
+        let fire = Binding::Button(Buttons::Fire);
+        manager.observe_input(
+            &Input::Gamepad(GamePadInput::Left),
+            &ButtonChange(true).into(),
+        );
+        assert_eq!(
+            manager.prompt_for(&fire),
+            Some(&Input::Gamepad(GamePadInput::Right))
+        );
+        manager.observe_input(&Input::Button(Keyboard::B), &ButtonChange(true).into());
+        assert_eq!(manager.prompt_for(&fire), Some(&Input::Button(Keyboard::A)));
+        assert_eq!(manager.prompt_for(&Binding::Button(Buttons::Block)), None);
+    }
+
+    #[test]
+    #[cfg(feature = "contexts")]
+    fn losing_focus_releases_scoped_inputs() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        let fire = Binding::Button(Buttons::Fire);
+        let vertical = Binding::Axis(Axes::Vertical);
+        manager.add_to_scope("gameplay".to_string(), fire.clone());
+        manager.add_to_scope("gameplay".to_string(), vertical.clone());
+        manager
+            .apply_change(&fire, ButtonChange(true).into())
+            .unwrap();
+        manager
+            .apply_change(&vertical, AxisChange::Velocity(Direction::Up).into())
+            .unwrap();
+        manager.set_scope_focus("gameplay", false);
+        assert!(!manager.is_scope_focused("gameplay"));
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+        assert_eq!(manager.get_axis(Axes::Vertical).unwrap().velocity, None);
+        manager
+            .apply_change(&fire, ButtonChange(true).into())
+            .unwrap();
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+        manager.set_scope_focus("gameplay", true);
+        manager
+            .apply_change(&fire, ButtonChange(true).into())
+            .unwrap();
+        assert!(manager.get_button(Buttons::Fire).unwrap().pressed);
+    }
+
+    #[test]
+    #[cfg(feature = "frame_clock")]
+    fn timestamp_ticks_clamp_long_gaps() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        let vertical = Binding::Axis(Axes::Vertical);
+        manager.tick_at_timestamp(0.0);
+        manager
+            .apply_change(&vertical, AxisChange::Velocity(Direction::Up).into())
+            .unwrap();
+        manager.tick_at_timestamp(10_000.0);
+        let mut expected: Manager<Axes, Buttons, Input> = Manager::new();
+        expected.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        expected
+            .apply_change(&vertical, AxisChange::Velocity(Direction::Up).into())
+            .unwrap();
+        expected.tick(Duration::from_millis(100));
+        assert_eq!(
+            manager.get_axis(Axes::Vertical),
+            expected.get_axis(Axes::Vertical)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "device")]
+    fn process_input_tracks_device() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.set_device_classifier(|input| match input {
+            Input::Button(_) => DeviceClass::KeyboardMouse,
+            Input::Gamepad(_) => DeviceClass::Gamepad(0),
+        });
+        assert!(manager
+            .process_input(Input::Button(Keyboard::A), ButtonChange(true))
+            .unwrap());
+        manager.tick(Duration::default());
+        assert!(manager.get_button(Buttons::Fire).unwrap().pressed);
+        assert_eq!(
+            manager.get_active_device(),
+            Some(DeviceClass::KeyboardMouse)
+        );
+    }
+
+    #[test]
+    fn set_binding_map_creates_states() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        let mut binding_map = BindingMap::new();
+        binding_map.set_binding(
+            Input::Button(Keyboard::A),
+            Some(Binding::Button(Buttons::Fire)),
+        );
+        binding_map.add_default_change(Input::Button(Keyboard::A), ButtonChange(true).into());
+        manager.set_binding_map(binding_map);
+        assert_eq!(
+            manager.get_binding(Input::Button(Keyboard::A)),
+            Some(Binding::Button(Buttons::Fire))
+        );
+        assert_eq!(
+            manager.get_default_change(Input::Button(Keyboard::A)),
+            Some(ButtonChange(true).into())
+        );
+        assert!(manager.get_button(Buttons::Fire).is_some());
+        assert!(manager.undo_binding_change());
+        assert_eq!(manager.get_binding(Input::Button(Keyboard::A)), None);
+    }
+
+    #[test]
+    fn usable_as_trait_object() {
+        let mut manager: Box<dyn InputManager<Axes, Buttons, Input>> =
+            Box::new(Manager::<Axes, Buttons, Input>::new());
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_default_change(ButtonChange(true).into(), Input::Button(Keyboard::A));
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        assert!(manager.get_button_pressed(Buttons::Fire));
+        manager.tick(Duration::from_millis(16));
+        assert!(!manager.get_button_pressed(Buttons::Fire));
+    }
+
+    #[test]
+    #[cfg(feature = "custom_state")]
+    fn custom_states_tick_and_receive_changes() {
+        use std::any::Any;
+        #[derive(Default)]
+        struct RadialSelector {
+            x: f64,
+            y: f64,
+            held_for: Duration,
+        }
+        impl CustomState for RadialSelector {
+            fn apply(&mut self, slot: usize, change: &Change) {
+                if let Change::Axis(AxisChange::Position(position)) = *change {
+                    match slot {
+                        0 => self.x = position,
+                        _ => self.y = position,
+                    }
+                }
+            }
+            fn tick(&mut self, delta: Duration) {
+                if self.x != 0.0 || self.y != 0.0 {
+                    self.held_for += delta;
+                }
+            }
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+        }
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_custom_state("radial".to_string(), Box::new(RadialSelector::default()));
+        manager.add_custom_binding("radial".to_string(), Input::Gamepad(GamePadInput::Left), 0);
+        manager.add_custom_binding("radial".to_string(), Input::Gamepad(GamePadInput::Right), 1);
+        assert_eq!(
+            manager.process_input(
+                Input::Gamepad(GamePadInput::Left),
+                AxisChange::Position(-0.25)
+            ),
+            Ok(true)
+        );
+        manager
+            .process_input(
+                Input::Gamepad(GamePadInput::Right),
+                AxisChange::Position(0.5),
+            )
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        let radial = manager
+            .get_custom_state("radial")
+            .and_then(|state| state.as_any().downcast_ref::<RadialSelector>())
+            .unwrap();
+        assert_eq!(radial.x, -0.25);
+        assert_eq!(radial.y, 0.5);
+        assert_eq!(radial.held_for, Duration::from_millis(16));
+    }
+
+    #[test]
+    fn value_binding_keeps_absolute_value() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_value_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Right));
+        let throttle = Binding::Value(Axes::Vertical);
+        manager
+            .apply_change(&throttle, ValueChange::Set(0.75).into())
+            .unwrap();
+        manager
+            .apply_change(&throttle, ValueChange::Offset(0.5).into())
+            .unwrap();
+        manager.tick(Duration::from_secs(1));
+        assert_eq!(manager.get_value(Axes::Vertical).unwrap().value, 1.25);
+        assert_eq!(manager.get_axis(Axes::Vertical), None);
+    }
+
+    #[test]
+    fn reports_registered_actions_without_inputs() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.register_action(Binding::Button(Buttons::Fire));
+        manager.register_action(Binding::Button(Buttons::Block));
+        manager.register_action(Binding::Button(Buttons::Fire));
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        assert_eq!(
+            manager.unbound_actions(),
+            vec![&Binding::Button(Buttons::Block)]
+        );
+        manager.add_button_binding(Buttons::Block, Input::Button(Keyboard::B));
+        assert!(manager.unbound_actions().is_empty());
+    }
+
+    #[test]
+    fn refuses_conflicting_registrations() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager.add_value_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Right));
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_default_change(
+            AxisChange::Velocity(Direction::Up).into(),
+            Input::Button(Keyboard::A),
+        );
+        assert_eq!(
+            manager.get_binding(Input::Gamepad(GamePadInput::Right)),
+            None
+        );
+        assert_eq!(manager.get_default_change(Input::Button(Keyboard::A)), None);
+        assert_eq!(
+            manager.get_registration_conflicts(),
+            &[
+                RegistrationConflict::ActionKind {
+                    existing: Binding::Axis(Axes::Vertical),
+                    conflicting: Binding::Value(Axes::Vertical),
+                },
+                RegistrationConflict::DefaultChange {
+                    input: Input::Button(Keyboard::A),
+                    binding: Binding::Button(Buttons::Fire),
+                    change: AxisChange::Velocity(Direction::Up).into(),
+                },
+            ]
+        );
     }
-    #[derive(PartialEq, Eq, Hash, Debug, Clone)]
-    enum Keyboard {
-        A,
-        B,
+
+    #[test]
+    fn iterates_states_by_kind() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_button_binding(Buttons::Block, Input::Button(Keyboard::B));
+        let axes: Vec<_> = manager.axes().map(|(axis, _)| axis).collect();
+        assert_eq!(axes, vec![&Axes::Vertical]);
+        assert_eq!(manager.buttons().count(), 2);
+        assert_eq!(manager.values().count(), 0);
     }
-    #[derive(PartialEq, Eq, Hash, Debug, Clone)]
-    enum GamePadInput {
-        Left,
-        Right,
+
+    #[test]
+    fn cursors_track_changes_independently() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_button_binding(Buttons::Block, Input::Button(Keyboard::B));
+        let mut early = manager.new_cursor();
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        let mut late = manager.new_cursor();
+        manager
+            .apply_change(&Binding::Button(Buttons::Block), ButtonChange(true).into())
+            .unwrap();
+        assert_eq!(
+            manager.changes_since(&mut early),
+            vec![
+                &Binding::Button(Buttons::Fire),
+                &Binding::Button(Buttons::Block)
+            ]
+        );
+        assert_eq!(
+            manager.changes_since(&mut late),
+            vec![&Binding::Button(Buttons::Block)]
+        );
+        assert!(manager.changes_since(&mut early).is_empty());
     }
-    #[derive(PartialEq, Eq, Hash, Debug, Clone)]
-    enum Input {
-        Button(Keyboard),
-        Gamepad(GamePadInput),
+
+    #[test]
+    fn manual_frames_keep_edges_until_end_frame() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.set_manual_frames(true);
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        assert!(manager.get_button_pressed(Buttons::Fire));
+        manager.end_frame();
+        assert!(!manager.get_button_pressed(Buttons::Fire));
+        assert!(manager.get_button(Buttons::Fire).unwrap().pressed);
     }
+
     #[test]
-    fn can_add_axis() {
+    fn handles_skip_lookup_by_action() {
         let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
-        manager.add_axis_binding(Axes::Vertical, Input::Button(Keyboard::A));
-        assert_eq!(manager.bindings.len(), 1);
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        let fire = manager.button_handle(Buttons::Fire).unwrap();
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        assert!(manager.get_button_by_handle(fire).unwrap().pressed);
+        assert_eq!(manager.button_handle(Buttons::Block), None);
+    }
+
+    #[test]
+    #[cfg(feature = "contexts")]
+    fn focus_only_switches_action_groups() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_button_binding(Buttons::Block, Input::Button(Keyboard::B));
+        let fire = Binding::Button(Buttons::Fire);
+        let block = Binding::Button(Buttons::Block);
+        manager.add_actions_to_scope("OnFoot".to_string(), vec![fire.clone()]);
+        manager.add_actions_to_scope("Vehicle".to_string(), vec![block.clone()]);
+        manager
+            .apply_change(&fire, ButtonChange(true).into())
+            .unwrap();
+        manager.focus_only(&["Vehicle"]);
+        assert!(!manager.is_scope_focused("OnFoot"));
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+        manager
+            .apply_change(&block, ButtonChange(true).into())
+            .unwrap();
+        assert!(manager.get_button(Buttons::Block).unwrap().pressed);
+    }
+
+    #[test]
+    #[cfg(feature = "contexts")]
+    fn reevaluate_replays_inputs_held_across_transition() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_button_binding(Buttons::Block, Input::Button(Keyboard::B));
+        let fire = Binding::Button(Buttons::Fire);
+        let block = Binding::Button(Buttons::Block);
+        manager.add_actions_to_scope("gameplay".to_string(), vec![fire.clone(), block.clone()]);
+        manager.set_scope_policy("gameplay", TransitionPolicy::Reevaluate);
+        manager.set_scope_focus("gameplay", false);
+        manager
+            .apply_change(&fire, ButtonChange(true).into())
+            .unwrap();
+        manager
+            .apply_change(&block, ButtonChange(true).into())
+            .unwrap();
+        manager
+            .apply_change(&block, ButtonChange(false).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
         assert_eq!(
-            manager.get_binding(Input::Button(Keyboard::A)).unwrap(),
-            Binding::Axis(Axes::Vertical)
+            manager.get_scope_transitions(),
+            &[ScopeTransition::Exit("gameplay".to_string())]
         );
-        assert_eq!(manager.get_axis(Axes::Vertical).unwrap(), &Axis::default());
+        manager.set_scope_focus("gameplay", true);
+        assert!(manager.get_button(Buttons::Fire).unwrap().pressed);
+        assert!(!manager.get_button(Buttons::Block).unwrap().pressed);
     }
+
     #[test]
-    fn can_add_button() {
+    #[cfg(feature = "contexts")]
+    fn carry_over_keeps_held_inputs_but_allows_release() {
         let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
         manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
-        assert_eq!(manager.states.len(), 1);
+        let fire = Binding::Button(Buttons::Fire);
+        manager.add_to_scope("gameplay".to_string(), fire.clone());
+        manager.set_scope_policy("gameplay", TransitionPolicy::CarryOver);
+        manager
+            .apply_change(&fire, ButtonChange(true).into())
+            .unwrap();
+        manager.set_scope_focus("gameplay", false);
+        assert!(manager.get_button(Buttons::Fire).unwrap().pressed);
+        manager
+            .apply_change(&fire, ButtonChange(false).into())
+            .unwrap();
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+    }
+
+    #[test]
+    fn realtime_actions_ignore_time_scale() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        let mut menu: Manager<Axes, Buttons, Input> = Manager::new();
+        menu.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        menu.set_clock_domain(Binding::Axis(Axes::Vertical), ClockDomain::Realtime);
+        for manager in [&mut manager, &mut menu] {
+            manager.set_time_scale(0.0);
+            manager
+                .apply_change(
+                    &Binding::Axis(Axes::Vertical),
+                    AxisChange::Velocity(Direction::Up).into(),
+                )
+                .unwrap();
+            manager.tick(Duration::from_millis(250));
+        }
+        assert_eq!(manager.get_axis(Axes::Vertical).unwrap().position, 0.0);
+        assert_eq!(menu.get_axis(Axes::Vertical).unwrap().position, 0.5);
+    }
+
+    #[test]
+    fn rejects_non_finite_time_scale() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        assert!(manager.set_time_scale(0.5));
+        assert!(!manager.set_time_scale(f64::INFINITY));
+        assert!(!manager.set_time_scale(f64::NAN));
+        assert_eq!(manager.get_time_scale(), 0.5);
+        assert!(manager.set_time_scale(1e20));
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager
+            .process_input(Input::Button(Keyboard::A), ButtonChange(true))
+            .unwrap();
+        manager.tick(Duration::from_secs(1));
+        manager.tick(Duration::from_secs(1));
+        assert!(manager.get_button(Buttons::Fire).unwrap().pressed);
+    }
+
+    #[test]
+    fn save_state_restores_buffered_input() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.set_input_delay(1);
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        let save = manager.save();
+        manager.tick(Duration::from_millis(16));
+        manager.tick(Duration::from_millis(16));
+        assert!(manager.get_button(Buttons::Fire).unwrap().pressed);
+        manager.load(&save);
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+        manager.tick(Duration::from_millis(16));
+        manager.tick(Duration::from_millis(16));
+        assert!(manager.get_button(Buttons::Fire).unwrap().pressed);
+    }
+
+    #[test]
+    fn accumulator_binding_sums_until_taken() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_accumulator_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        let scroll = Binding::Accumulator(Axes::Vertical);
+        manager
+            .apply_change(&scroll, ValueChange::Offset(2.0).into())
+            .unwrap();
+        manager.tick(Duration::from_secs(1));
+        manager
+            .apply_change(&scroll, ValueChange::Offset(1.5).into())
+            .unwrap();
+        assert_eq!(manager.take_accumulated(Axes::Vertical), 3.5);
+        assert_eq!(manager.get_accumulator(Axes::Vertical).unwrap().total, 0.0);
+    }
+
+    #[test]
+    fn tracks_held_button_press_order() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_button_binding(Buttons::Block, Input::Button(Keyboard::B));
+        let (fire, block) = (
+            Binding::Button(Buttons::Fire),
+            Binding::Button(Buttons::Block),
+        );
+        manager
+            .apply_change(&block, ButtonChange(true).into())
+            .unwrap();
+        manager
+            .apply_change(&fire, ButtonChange(true).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
         assert_eq!(
-            manager.get_binding(Input::Button(Keyboard::A)).unwrap(),
-            Binding::Button(Buttons::Fire)
+            manager.held_buttons_ordered(),
+            &[Buttons::Block, Buttons::Fire]
         );
+        manager
+            .apply_change(&block, ButtonChange(false).into())
+            .unwrap();
+        manager
+            .apply_change(&block, ButtonChange(true).into())
+            .unwrap();
         assert_eq!(
-            manager.get_button(Buttons::Fire).unwrap(),
-            &Button::default()
+            manager.held_buttons_ordered(),
+            &[Buttons::Fire, Buttons::Block]
         );
     }
 
     #[test]
-    fn can_update_axis_pos() {
+    #[cfg(feature = "timing")]
+    fn samples_watched_button_timing() {
         let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
-        manager.add_axis_binding(Axes::Vertical, Input::Button(Keyboard::A));
-        let binding = manager.get_binding(Input::Button(Keyboard::A)).unwrap();
-        manager.apply_change(&binding, AxisChange::Position(0.5));
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.watch_button_timing(Buttons::Fire, 4);
+        let fire = Binding::Button(Buttons::Fire);
+        manager
+            .apply_change(&fire, ButtonChange(true).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(50));
+        manager.tick(Duration::from_millis(50));
+        manager
+            .apply_change(&fire, ButtonChange(false).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(50));
+        assert_eq!(
+            manager
+                .get_button_timing(Buttons::Fire)
+                .and_then(|timings| timings.average_press_duration()),
+            Some(Duration::from_millis(100))
+        );
+        assert!(manager.get_button_timing(Buttons::Block).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "combo")]
+    fn hold_confirm_presses_action_once() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_button_binding(Buttons::Block, Input::Button(Keyboard::B));
+        manager.add_hold_confirm(
+            Buttons::Delete,
+            HoldConfirm::new(Buttons::Fire, Buttons::Block, Duration::from_millis(500)),
+        );
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        manager
+            .apply_change(&Binding::Button(Buttons::Block), ButtonChange(true).into())
+            .unwrap();
+        let mut fired = 0;
+        for _ in 0..10 {
+            manager.tick(Duration::from_millis(100));
+            if manager.get_button_pressed(Buttons::Delete) {
+                fired += 1;
+            }
+        }
+        assert_eq!(fired, 1);
+        assert!(!manager.get_button(Buttons::Delete).unwrap().pressed);
+    }
+
+    #[test]
+    #[cfg(feature = "export")]
+    fn streams_changes_and_fired_buttons_to_event_log() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+        struct Log(Rc<RefCell<Vec<String>>>);
+        impl IEventLog<Axes, Buttons> for Log {
+            fn log(&mut self, frame: u64, event: LoggedEvent<Axes, Buttons>) {
+                self.0.borrow_mut().push(format!("{} {:?}", frame, event));
+            }
+        }
+        let lines = Rc::new(RefCell::new(Vec::new()));
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.set_event_log(Some(Box::new(Log(lines.clone()))));
+        manager.tick(Duration::from_millis(16));
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        assert_eq!(
+            *lines.borrow(),
+            vec![
+                "1 Change(Button(Fire), Button(ButtonChange(true)))".to_string(),
+                "1 Fired(Fire)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn paused_manager_holds_changes_until_stepped() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.set_paused(true);
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+        assert_eq!(manager.get_paused_changes().len(), 1);
+        manager.step_frame(Duration::from_millis(16));
+        assert!(manager.get_paused_changes().is_empty());
+        assert!(manager.get_button_pressed(Buttons::Fire));
+    }
+
+    #[test]
+    fn remaps_actions_after_binding_lookup() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        let fire = Binding::Button(Buttons::Fire);
+        assert!(!manager.remap_action(fire.clone(), Binding::Axis(Axes::Vertical)));
+        assert!(manager.remap_action(fire.clone(), Binding::Button(Buttons::Block)));
+        assert_eq!(
+            manager.get_binding(Input::Button(Keyboard::A)),
+            Some(Binding::Button(Buttons::Block))
+        );
+        assert!(manager.get_button(Buttons::Block).is_some());
+        manager.remove_action_remap(&fire);
+        assert_eq!(manager.get_binding(Input::Button(Keyboard::A)), Some(fire));
+    }
+
+    #[test]
+    fn process_input_resolves_and_applies() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        assert_eq!(
+            manager.process_input(Input::Button(Keyboard::A), ButtonChange(true)),
+            Ok(true)
+        );
+        assert_eq!(
+            manager.process_input(Input::Button(Keyboard::B), ButtonChange(true)),
+            Ok(false)
+        );
+        assert_eq!(
+            manager.process_input(Input::Button(Keyboard::A), AxisChange::Position(1.0)),
+            Err(InputError::ChangeKindMismatch)
+        );
+        assert!(manager.get_button_pressed(Buttons::Fire));
+        assert_eq!(
+            manager
+                .last_source_for(&Binding::Button(Buttons::Fire))
+                .map(|source| &source.input),
+            Some(&Input::Button(Keyboard::A))
+        );
+    }
+
+    #[test]
+    fn dropped_changes_keep_the_last_source() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::B));
+        manager.configure_button(
+            Buttons::Fire,
+            ButtonConfig {
+                debounce: Some(Duration::from_millis(50)),
+                ..ButtonConfig::default()
+            },
+        );
+        let fire = Binding::Button(Buttons::Fire);
+        manager
+            .process_input(Input::Button(Keyboard::A), ButtonChange(true))
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        manager
+            .process_input(Input::Button(Keyboard::A), ButtonChange(false))
+            .unwrap();
+        manager
+            .process_input(Input::Button(Keyboard::B), ButtonChange(true))
+            .unwrap();
+        assert!(!manager.get_button_pressed(Buttons::Fire));
+        assert_eq!(
+            manager.last_source_for(&fire).map(|source| &source.input),
+            Some(&Input::Button(Keyboard::A))
+        );
+    }
+
+    #[test]
+    fn rebinding_threshold_input_releases_its_button() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        let stick = Input::Gamepad(GamePadInput::Left);
+        manager.add_axis_button_binding(Buttons::Fire, stick.clone(), 0.5);
+        manager.configure_button(
+            Buttons::Fire,
+            ButtonConfig {
+                repeat: Some(ButtonRepeat {
+                    delay: Duration::from_millis(100),
+                    interval: Duration::from_millis(50),
+                }),
+                ..ButtonConfig::default()
+            },
+        );
+        manager
+            .process_input(stick.clone(), AxisChange::Position(1.0))
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        assert!(manager.get_button(Buttons::Fire).unwrap().pressed);
+        manager.add_button_binding(Buttons::Block, stick.clone());
+        manager.tick(Duration::from_millis(200));
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+        assert_eq!(
+            manager.get_button_edges(Buttons::Fire),
+            &[ButtonEdge::Release]
+        );
+        manager
+            .process_input(stick.clone(), AxisChange::Position(1.0))
+            .unwrap();
+        assert!(manager.get_button(Buttons::Block).unwrap().pressed);
+        assert!(manager.undo_binding_change());
+        assert!(!manager.get_button(Buttons::Block).unwrap().pressed);
+    }
+
+    #[test]
+    fn classifies_analog_press_speed() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        let trigger = Input::Gamepad(GamePadInput::Right);
+        manager.add_axis_button_binding(Buttons::Fire, trigger.clone(), 0.5);
+        manager.set_strong_press_window(Duration::from_millis(40));
+        manager
+            .process_input(trigger.clone(), AxisChange::Position(0.25))
+            .unwrap();
+        manager.tick(Duration::from_millis(100));
+        manager
+            .process_input(trigger.clone(), AxisChange::Position(0.75))
+            .unwrap();
+        assert_eq!(
+            manager.get_press_speed(Buttons::Fire),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            manager.get_press_strength(Buttons::Fire),
+            Some(PressStrength::Weak)
+        );
+        manager
+            .process_input(trigger.clone(), AxisChange::Position(0.0))
+            .unwrap();
+        manager
+            .process_input(trigger.clone(), AxisChange::Position(0.25))
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        manager
+            .process_input(trigger, AxisChange::Position(1.0))
+            .unwrap();
+        assert_eq!(
+            manager.get_press_strength(Buttons::Fire),
+            Some(PressStrength::Strong)
+        );
+    }
+
+    #[test]
+    fn mismatched_change_kind_is_an_error() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        assert_eq!(
+            manager.apply_change(
+                &Binding::Button(Buttons::Fire),
+                AxisChange::Position(1.0).into()
+            ),
+            Err(InputError::ChangeKindMismatch)
+        );
+        manager.tick(Duration::from_millis(16));
+        assert!(!manager.get_button(Buttons::Fire).unwrap().pressed);
+    }
+
+    #[test]
+    fn reports_button_release_edge() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        let fire = Binding::Button(Buttons::Fire);
+        manager
+            .apply_change(&fire, ButtonChange(true).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        assert!(!manager.get_button_released(Buttons::Fire));
+        manager
+            .apply_change(&fire, ButtonChange(false).into())
+            .unwrap();
+        assert!(manager.get_button_released(Buttons::Fire));
+        manager.tick(Duration::from_millis(16));
+        assert!(!manager.get_button_released(Buttons::Fire));
+    }
+
+    #[test]
+    #[cfg(feature = "heatmap")]
+    fn heatmap_counts_fired_buttons() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.start_heatmap(Duration::from_secs(1), 1, 1);
+        let fire = Binding::Button(Buttons::Fire);
+        manager
+            .apply_change(&fire, ButtonChange(true).into())
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        manager
+            .apply_change(&fire, ButtonChange(false).into())
+            .unwrap();
+        manager.record_pointer(0.5, 0.5);
+        let heatmap = manager.stop_heatmap().unwrap();
+        assert_eq!(heatmap.counts[0][&fire], 1);
+        assert_eq!(heatmap.region_count(0, 0), 1);
+    }
+
+    #[test]
+    fn recenters_axis_on_current_value() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        let vertical = Binding::Axis(Axes::Vertical);
+        assert!(!manager.recenter_axis(Axes::Vertical));
+        manager
+            .apply_change(&vertical, AxisChange::Velocity(Direction::Up).into())
+            .unwrap();
+        assert!(!manager.recenter_axis(Axes::Vertical));
+        manager
+            .apply_change(&vertical, AxisChange::Position(-0.5).into())
+            .unwrap();
+        assert!(manager.recenter_axis(Axes::Vertical));
+        assert_eq!(manager.get_axis(Axes::Vertical).unwrap().position, 0.0);
+        manager
+            .apply_change(&vertical, AxisChange::Position(0.25).into())
+            .unwrap();
         assert_eq!(manager.get_axis(Axes::Vertical).unwrap().position, 0.5);
     }
+
     #[test]
-    fn can_update_axis_vel() {
+    fn pressure_presses_button_past_actuation() {
         let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
-        manager.add_axis_binding(Axes::Vertical, Input::Button(Keyboard::A));
-        let binding = &manager
-            .get_binding(Input::Button(Keyboard::A))
-            .unwrap()
-            .clone();
-        manager.apply_change(binding, AxisChange::Velocity(Direction::Up));
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        let fire = Binding::Button(Buttons::Fire);
+        manager
+            .apply_change(&fire, PressureChange(0.25).into())
+            .unwrap();
+        assert_eq!(manager.get_button_value(Buttons::Fire), 0.25);
+        assert!(!manager.get_button_pressed(Buttons::Fire));
+        manager
+            .apply_change(&fire, PressureChange(0.75).into())
+            .unwrap();
+        assert_eq!(manager.get_button_value(Buttons::Fire), 0.75);
+        assert!(manager.get_button_pressed(Buttons::Fire));
+    }
+
+    #[test]
+    fn sums_weighted_activity_per_frame() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.set_activity_weight(Binding::Button(Buttons::Fire), 2.0);
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        manager
+            .apply_change(
+                &Binding::Axis(Axes::Vertical),
+                AxisChange::Position(-0.5).into(),
+            )
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        assert_eq!(manager.get_activity(), 2.5);
+        manager.tick(Duration::from_millis(16));
+        assert_eq!(manager.get_activity(), 0.0);
+    }
+
+    #[test]
+    fn trigger_binding_stays_within_unit_range() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_trigger_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Right));
+        let trigger = Binding::Trigger(Axes::Vertical);
+        manager
+            .apply_change(&trigger, TriggerChange(0.5).into())
+            .unwrap();
+        assert_eq!(manager.get_trigger(Axes::Vertical).unwrap().value, 0.5);
         assert_eq!(
-            manager.get_axis(Axes::Vertical).unwrap().velocity,
-            Some(Direction::Up)
+            manager.apply_change(&trigger, AxisChange::Position(-1.0).into()),
+            Err(InputError::ChangeKindMismatch)
         );
+        manager
+            .apply_change(&trigger, TriggerChange(-0.25).into())
+            .unwrap();
+        assert_eq!(manager.get_trigger(Axes::Vertical).unwrap().value, 0.0);
     }
 
     #[test]
-    fn can_toggle_button() {
+    fn axis2d_binding_applies_radial_dead_zone() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis2d_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager.configure_axis2d(
+            Axes::Vertical,
+            InputAxisConfig {
+                dead_zone: 0.5,
+                ..InputAxisConfig::default()
+            },
+        );
+        let stick = Binding::Axis2d(Axes::Vertical);
+        manager
+            .apply_change(&stick, Axis2dChange { x: 0.25, y: 0.25 }.into())
+            .unwrap();
+        let axis2d = manager.get_axis2d(Axes::Vertical).unwrap();
+        assert_eq!((axis2d.x, axis2d.y), (0.0, 0.0));
+        manager
+            .apply_change(&stick, Axis2dChange { x: -0.75, y: 0.0 }.into())
+            .unwrap();
+        let axis2d = manager.get_axis2d(Axes::Vertical).unwrap();
+        assert_eq!((axis2d.x, axis2d.y), (-0.5, 0.0));
+    }
+
+    #[test]
+    fn warns_when_latency_exceeds_budget() {
         let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
         manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
-        let binding = &manager
-            .get_binding(Input::Button(Keyboard::A))
-            .unwrap()
-            .clone();
-        manager.apply_change(binding, ButtonChange(true));
-        assert_eq!(manager.get_button(Buttons::Fire).unwrap().pressed, true);
-        let binding = &manager
-            .get_binding(Input::Button(Keyboard::A))
-            .unwrap()
-            .clone();
-        manager.apply_change(binding, ButtonChange(false));
-        assert_eq!(manager.get_button(Buttons::Fire).unwrap().pressed, false);
+        manager.add_button_binding(Buttons::Block, Input::Button(Keyboard::B));
+        let fire = Binding::Button(Buttons::Fire);
+        manager.set_latency_budget(fire.clone(), Some(Duration::from_millis(20)));
+        manager.tick(Duration::from_millis(100));
+        manager
+            .apply_change_at(&fire, ButtonChange(true).into(), Duration::from_millis(100))
+            .unwrap();
+        manager
+            .apply_change_at(
+                &Binding::Button(Buttons::Block),
+                ButtonChange(true).into(),
+                Duration::from_millis(50),
+            )
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        assert!(manager.get_latency_warnings().is_empty());
+        manager
+            .apply_change_at(
+                &fire,
+                ButtonChange(false).into(),
+                Duration::from_millis(100),
+            )
+            .unwrap();
+        manager.tick(Duration::from_millis(32));
+        assert_eq!(
+            manager.get_latency_warnings(),
+            &[LatencyWarning {
+                action: fire,
+                latency: Duration::from_millis(48),
+                budget: Duration::from_millis(20),
+            }]
+        );
+        manager.tick(Duration::from_millis(16));
+        assert!(manager.get_latency_warnings().is_empty());
     }
 
     #[test]
-    fn get_changed_buttons() {
+    #[cfg(feature = "filter")]
+    fn change_filters_rewrite_and_drop_changes() {
         let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
         manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
         manager.add_button_binding(Buttons::Block, Input::Button(Keyboard::B));
-        let binding = &manager
-            .get_binding(Input::Button(Keyboard::A))
-            .unwrap()
-            .clone();
-        manager.apply_change(binding, ButtonChange(true));
-        assert_eq!(manager.get_changed_buttons().len(), 1);
-        manager.tick(Duration::default());
-        assert_eq!(manager.get_changed_buttons().len(), 0);
+        manager.add_filter(Box::new(|binding, change| match binding {
+            Binding::Button(Buttons::Fire) => Some((Binding::Button(Buttons::Block), change)),
+            Binding::Button(Buttons::Block) => None,
+            binding => Some((binding, change)),
+        }));
+        manager
+            .process_input(Input::Button(Keyboard::A), ButtonChange(true))
+            .unwrap();
+        assert!(manager.get_button_pressed(Buttons::Block));
+        assert!(!manager.get_button_pressed(Buttons::Fire));
+        manager
+            .apply_change(&Binding::Button(Buttons::Block), ButtonChange(false).into())
+            .unwrap();
+        assert!(manager.get_button_pressed(Buttons::Block));
+        manager.clear_filters();
+        manager
+            .apply_change(&Binding::Button(Buttons::Block), ButtonChange(false).into())
+            .unwrap();
+        assert!(!manager.get_button(Buttons::Block).unwrap().pressed);
+    }
+
+    #[test]
+    #[cfg(feature = "filter")]
+    fn state_processors_run_after_tick() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager.add_state_processor(
+            Binding::Axis(Axes::Vertical),
+            Box::new(|state: &mut State| {
+                if let State::Axis(axis) = state {
+                    axis.position = axis.position.min(0.5);
+                }
+            }),
+        );
+        manager
+            .apply_change(
+                &Binding::Axis(Axes::Vertical),
+                AxisChange::Position(0.8).into(),
+            )
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        assert_eq!(manager.get_axis(Axes::Vertical).unwrap().position, 0.5);
+        manager.clear_state_processors();
+        manager
+            .apply_change(
+                &Binding::Axis(Axes::Vertical),
+                AxisChange::Position(0.8).into(),
+            )
+            .unwrap();
+        manager.tick(Duration::from_millis(16));
+        assert_eq!(manager.get_axis(Axes::Vertical).unwrap().position, 0.8);
+    }
+
+    #[test]
+    #[cfg(feature = "animation")]
+    fn animation_parameters_follow_actions() {
+        let mut manager: Manager<Axes, Buttons, Input> = Manager::new();
+        manager.add_axis_binding(Axes::Vertical, Input::Gamepad(GamePadInput::Left));
+        manager.add_button_binding(Buttons::Fire, Input::Button(Keyboard::A));
+        manager.add_animation_parameter(
+            "MoveSpeed".to_string(),
+            Binding::Axis(Axes::Vertical),
+            Duration::default(),
+        );
+        manager.add_animation_parameter(
+            "AimBlend".to_string(),
+            Binding::Button(Buttons::Fire),
+            Duration::from_millis(100),
+        );
+        manager
+            .apply_change(
+                &Binding::Axis(Axes::Vertical),
+                AxisChange::Position(0.75).into(),
+            )
+            .unwrap();
+        manager
+            .apply_change(&Binding::Button(Buttons::Fire), ButtonChange(true).into())
+            .unwrap();
+        assert_eq!(manager.get_animation_parameter("MoveSpeed"), Some(0.0));
+        manager.tick(Duration::from_millis(100));
+        assert_eq!(manager.get_animation_parameter("MoveSpeed"), Some(0.75));
+        let aim = manager.get_animation_parameter("AimBlend").unwrap();
+        assert!(aim > 0.5 && aim < 1.0);
+        manager.remove_animation_parameter("AimBlend");
+        assert_eq!(manager.animation_parameters(), vec![("MoveSpeed", 0.75)]);
     }
 }
@@ -1,4 +1,14 @@
+mod arena;
+mod binding_map;
 mod builder;
+mod direct;
+#[cfg(feature = "dynamic")]
+mod dynamic;
 mod manager;
+pub use self::arena::*;
+pub use self::binding_map::*;
 pub use self::builder::*;
+pub use self::direct::*;
+#[cfg(feature = "dynamic")]
+pub use self::dynamic::*;
 pub use self::manager::*;
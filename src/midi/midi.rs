@@ -0,0 +1,156 @@
+use change::{AxisChange, ButtonChange};
+use manager::Change;
+use polling::InputSource;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum MidiControl {
+    Note { channel: u8, note: u8 },
+    Control { channel: u8, controller: u8 },
+}
+
+/// Parses a raw MIDI byte stream (running status included) into changes:
+/// notes become buttons and control changes become axis positions in 0..1.
+/// Bytes come from whatever MIDI port library the game uses.
+#[derive(Debug, Clone)]
+pub struct MidiSource<C: Hash + Eq> {
+    mappings: HashMap<MidiControl, C>,
+    status: Option<u8>,
+    data: Vec<u8>,
+    in_sysex: bool,
+    queued: Vec<(C, Change)>,
+}
+
+pub trait IMidiSource<C> {
+    fn new() -> Self
+    where
+        Self: Sized;
+    fn map(&mut self, control: MidiControl, input: C);
+    fn feed(&mut self, bytes: &[u8]);
+}
+
+impl<C: Hash + Eq + Clone> IMidiSource<C> for MidiSource<C> {
+    fn new() -> Self {
+        MidiSource {
+            mappings: HashMap::new(),
+            status: None,
+            data: Vec::new(),
+            in_sysex: false,
+            queued: Vec::new(),
+        }
+    }
+    fn map(&mut self, control: MidiControl, input: C) {
+        self.mappings.insert(control, input);
+    }
+    fn feed(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            match byte {
+                0xF8..=0xFF => {}
+                0xF0 => self.in_sysex = true,
+                0xF7 => self.in_sysex = false,
+                0xF1..=0xF6 => self.status = None,
+                0x80..=0xEF => {
+                    self.in_sysex = false;
+                    self.status = Some(byte);
+                    self.data.clear();
+                }
+                _ if self.in_sysex => {}
+                _ => {
+                    self.data.push(byte);
+                    if let Some(status) = self.status {
+                        let length = match status & 0xF0 {
+                            0xC0 | 0xD0 => 1,
+                            _ => 2,
+                        };
+                        if self.data.len() == length {
+                            self.handle_message(status);
+                            self.data.clear();
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<C: Hash + Eq + Clone> MidiSource<C> {
+    fn handle_message(&mut self, status: u8) {
+        let channel = status & 0x0F;
+        let (control, change) = match (status & 0xF0, self.data[0], self.data.get(1)) {
+            (0x80, note, _) => (
+                MidiControl::Note { channel, note },
+                ButtonChange(false).into(),
+            ),
+            (0x90, note, Some(&velocity)) => (
+                MidiControl::Note { channel, note },
+                ButtonChange(velocity > 0).into(),
+            ),
+            (0xB0, controller, Some(&value)) => (
+                MidiControl::Control {
+                    channel,
+                    controller,
+                },
+                AxisChange::Position(f64::from(value) / 127.0).into(),
+            ),
+            _ => return,
+        };
+        if let Some(input) = self.mappings.get(&control) {
+            self.queued.push((input.clone(), change));
+        }
+    }
+}
+
+impl<C: Hash + Eq + Send> InputSource<C> for MidiSource<C> {
+    fn poll(&mut self) -> Vec<(C, Change)> {
+        self.queued.drain(..).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> MidiSource<&'static str> {
+        let mut source = MidiSource::new();
+        source.map(
+            MidiControl::Note {
+                channel: 0,
+                note: 60,
+            },
+            "pad",
+        );
+        source.map(
+            MidiControl::Control {
+                channel: 1,
+                controller: 7,
+            },
+            "fader",
+        );
+        source
+    }
+
+    #[test]
+    fn notes_map_to_buttons() {
+        let mut source = source();
+        source.feed(&[0x90, 60, 100, 0xF8, 60, 0, 0x90, 61, 100]);
+        assert_eq!(
+            source.poll(),
+            vec![
+                ("pad", ButtonChange(true).into()),
+                ("pad", ButtonChange(false).into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn control_changes_map_to_axes() {
+        let mut source = source();
+        source.feed(&[0xB1, 7]);
+        source.feed(&[127, 0xF0, 1, 2, 0xF7]);
+        assert_eq!(
+            source.poll(),
+            vec![("fader", AxisChange::Position(1.0).into())]
+        );
+    }
+}
@@ -0,0 +1,2 @@
+mod midi;
+pub use self::midi::*;
@@ -0,0 +1,2 @@
+mod net;
+pub use self::net::*;
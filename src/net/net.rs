@@ -0,0 +1,86 @@
+use encoding::*;
+use manager::InputFrame;
+
+pub const PACKET_MAGIC: u8 = 0x49;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InputPacket<A, B> {
+    pub tag: u8,
+    pub sequence: u32,
+    pub frame: InputFrame<A, B>,
+}
+
+/// Wraps each tick's local inputs in a tagged, sequence-numbered packet.
+#[derive(Debug, Default)]
+pub struct NetPacketBuilder {
+    tag: u8,
+    sequence: u32,
+}
+
+pub trait INetPacketBuilder {
+    fn new(tag: u8) -> Self
+    where
+        Self: Sized;
+    fn build<A: Encode, B: Encode>(&mut self, frame: &InputFrame<A, B>) -> Vec<u8>;
+}
+
+impl INetPacketBuilder for NetPacketBuilder {
+    fn new(tag: u8) -> Self {
+        NetPacketBuilder { tag, sequence: 0 }
+    }
+    fn build<A: Encode, B: Encode>(&mut self, frame: &InputFrame<A, B>) -> Vec<u8> {
+        let mut writer = BitWriter::default();
+        PACKET_MAGIC.encode(&mut writer);
+        self.tag.encode(&mut writer);
+        self.sequence.encode(&mut writer);
+        frame.encode(&mut writer);
+        self.sequence = self.sequence.wrapping_add(1);
+        writer.into_bytes()
+    }
+}
+
+pub fn parse_packet<A: Decode, B: Decode>(bytes: &[u8]) -> Result<InputPacket<A, B>, DecodeError> {
+    let mut reader = BitReader::new(bytes);
+    let magic = u8::decode(&mut reader)?;
+    if magic != PACKET_MAGIC {
+        return Err(DecodeError::InvalidTag(u64::from(magic)));
+    }
+    Ok(InputPacket {
+        tag: u8::decode(&mut reader)?,
+        sequence: u32::decode(&mut reader)?,
+        frame: InputFrame::decode(&mut reader)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use change::ButtonChange;
+    use manager::Binding;
+    use std::time::Duration;
+    #[test]
+    fn packets_round_trip_with_sequence() {
+        let frame: InputFrame<u8, u8> = InputFrame {
+            changes: vec![(Binding::Button(2), ButtonChange(true).into())],
+            delta: Duration::from_millis(16),
+        };
+        let mut builder = NetPacketBuilder::new(3);
+        builder.build(&frame);
+        let packet = parse_packet(&builder.build(&frame)).unwrap();
+        assert_eq!(
+            packet,
+            InputPacket {
+                tag: 3,
+                sequence: 1,
+                frame,
+            }
+        );
+    }
+    #[test]
+    fn rejects_foreign_packets() {
+        assert_eq!(
+            parse_packet::<u8, u8>(&[0, 0, 0]),
+            Err(DecodeError::InvalidTag(0))
+        );
+    }
+}
@@ -0,0 +1,2 @@
+mod osc;
+pub use self::osc::*;
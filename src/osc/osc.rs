@@ -0,0 +1,156 @@
+use change::{AxisChange, ButtonChange};
+use manager::Change;
+use polling::InputSource;
+use std::collections::HashMap;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+/// Listens for OSC over UDP. Float arguments become axis positions and
+/// int/bool arguments become button states; bundles are unpacked.
+pub struct OscSource<C> {
+    socket: UdpSocket,
+    mappings: HashMap<String, C>,
+}
+
+pub trait IOscSource<C> {
+    fn bind<T: ToSocketAddrs>(addr: T) -> io::Result<Self>
+    where
+        Self: Sized;
+    fn local_addr(&self) -> io::Result<SocketAddr>;
+    fn map(&mut self, address: String, input: C);
+}
+
+impl<C> IOscSource<C> for OscSource<C> {
+    fn bind<T: ToSocketAddrs>(addr: T) -> io::Result<Self> {
+        let socket = UdpSocket::bind(addr)?;
+        socket.set_nonblocking(true)?;
+        Ok(OscSource {
+            socket,
+            mappings: HashMap::new(),
+        })
+    }
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+    fn map(&mut self, address: String, input: C) {
+        self.mappings.insert(address, input);
+    }
+}
+
+impl<C: Clone + Send> InputSource<C> for OscSource<C> {
+    fn poll(&mut self) -> Vec<(C, Change)> {
+        let mut changes = Vec::new();
+        let mut packet = [0; 1536];
+        while let Ok(read) = self.socket.recv(&mut packet) {
+            for (address, change) in parse_osc_packet(&packet[..read]) {
+                if let Some(input) = self.mappings.get(&address) {
+                    changes.push((input.clone(), change));
+                }
+            }
+        }
+        changes
+    }
+}
+
+pub fn parse_osc_packet(bytes: &[u8]) -> Vec<(String, Change)> {
+    let mut changes = Vec::new();
+    if bytes.starts_with(b"#bundle\0") {
+        let mut offset = 16;
+        while let Some(size) = read_u32(bytes, offset) {
+            let start = offset + 4;
+            let end = start + size as usize;
+            if end > bytes.len() {
+                break;
+            }
+            changes.extend(parse_osc_packet(&bytes[start..end]));
+            offset = end;
+        }
+    } else if let Some(change) = parse_osc_message(bytes) {
+        changes.push(change);
+    }
+    changes
+}
+
+fn parse_osc_message(bytes: &[u8]) -> Option<(String, Change)> {
+    let (address, offset) = read_string(bytes, 0)?;
+    let (tags, offset) = read_string(bytes, offset)?;
+    let change = match tags.as_bytes().get(1)? {
+        b'f' => AxisChange::Position(f64::from(f32::from_bits(read_u32(bytes, offset)?))).into(),
+        b'i' => ButtonChange(read_u32(bytes, offset)? != 0).into(),
+        b'T' => ButtonChange(true).into(),
+        b'F' => ButtonChange(false).into(),
+        _ => return None,
+    };
+    Some((address, change))
+}
+
+fn read_string(bytes: &[u8], offset: usize) -> Option<(String, usize)> {
+    let rest = bytes.get(offset..)?;
+    let len = rest.iter().position(|&byte| byte == 0)?;
+    let value = String::from_utf8(rest[..len].to_vec()).ok()?;
+    Some((value, offset + (len / 4 + 1) * 4))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let word = bytes.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([word[0], word[1], word[2], word[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    fn message(address: &str, tags: &str, args: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for part in [address, tags] {
+            bytes.extend_from_slice(part.as_bytes());
+            bytes.resize((bytes.len() / 4 + 1) * 4, 0);
+        }
+        bytes.extend_from_slice(args);
+        bytes
+    }
+
+    #[test]
+    fn parses_messages_and_bundles() {
+        let fader = message("/fader", ",f", &0.5f32.to_bits().to_be_bytes());
+        let toggle = message("/toggle", ",T", &[]);
+        let mut bundle = b"#bundle\0".to_vec();
+        bundle.extend_from_slice(&[0; 8]);
+        for part in [&fader, &toggle] {
+            bundle.extend_from_slice(&(part.len() as u32).to_be_bytes());
+            bundle.extend_from_slice(part);
+        }
+        assert_eq!(
+            parse_osc_packet(&bundle),
+            vec![
+                ("/fader".to_string(), AxisChange::Position(0.5).into()),
+                ("/toggle".to_string(), ButtonChange(true).into()),
+            ]
+        );
+        assert_eq!(
+            parse_osc_packet(&message("/push", ",i", &[0, 0, 0, 1])),
+            vec![("/push".to_string(), ButtonChange(true).into())]
+        );
+    }
+
+    #[test]
+    fn polls_mapped_addresses_over_udp() {
+        let mut source = OscSource::bind("127.0.0.1:0").unwrap();
+        source.map("/push".to_string(), 'p');
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = source.local_addr().unwrap();
+        sender.send_to(&message("/other", ",T", &[]), addr).unwrap();
+        sender.send_to(&message("/push", ",T", &[]), addr).unwrap();
+        let mut polled = Vec::new();
+        for _ in 0..1000 {
+            polled.extend(source.poll());
+            if !polled.is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(polled, vec![('p', ButtonChange(true).into())]);
+    }
+}
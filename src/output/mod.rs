@@ -0,0 +1,2 @@
+mod output;
+pub use self::output::*;
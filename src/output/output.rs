@@ -0,0 +1,385 @@
+use device::DeviceClass;
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum TriggerSide {
+    Left,
+    Right,
+}
+
+/// Positions and strengths are normalised to `0.0..=1.0` so effects stay
+/// device-agnostic; backends scale them to whatever their hardware accepts.
+#[derive(Default, Debug, PartialEq, Clone)]
+pub enum TriggerEffect {
+    #[default]
+    Off,
+    Resistance {
+        start: f64,
+        strength: f64,
+    },
+    Curve(Vec<(f64, f64)>),
+    Vibration {
+        start: f64,
+        amplitude: f64,
+        frequency: f64,
+    },
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct LightColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct OutputCapabilities {
+    pub adaptive_triggers: bool,
+    pub rumble: bool,
+    pub light_bar: bool,
+    pub player_indicator: bool,
+}
+
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct HapticSegment {
+    pub low: f64,
+    pub high: f64,
+    pub duration: Duration,
+}
+
+/// A rumble timeline played by the router. A pattern only replaces the one
+/// already playing on a device if its `priority` is at least as high.
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct HapticPattern {
+    pub segments: Vec<HapticSegment>,
+    pub looping: bool,
+    pub priority: u8,
+}
+
+#[derive(Debug)]
+struct HapticPlayback {
+    pattern: HapticPattern,
+    segment: usize,
+    elapsed: Duration,
+}
+
+/// A device that can play output effects, e.g. a DualSense backend.
+pub trait OutputBackend {
+    fn capabilities(&self) -> OutputCapabilities;
+    fn set_trigger_effect(&mut self, _side: TriggerSide, _effect: &TriggerEffect) {}
+    fn set_rumble(&mut self, _low: f64, _high: f64) {}
+    fn set_light_color(&mut self, _color: LightColor) {}
+    fn set_player_indicator(&mut self, _player: u8) {}
+}
+
+/// Sends effects to the backends registered for a device, using the same
+/// `DeviceClass` handle the input side reports. Effects degrade for backends
+/// that lack the hardware: trigger vibration falls back to rumble, anything
+/// else unsupported is dropped.
+#[derive(Default)]
+pub struct OutputRouter {
+    backends: Vec<(DeviceClass, Box<dyn OutputBackend>)>,
+    haptics: HashMap<DeviceClass, HapticPlayback>,
+}
+
+pub trait IOutputRouter {
+    fn new() -> Self
+    where
+        Self: Sized;
+    fn add_backend(&mut self, device: DeviceClass, backend: Box<dyn OutputBackend>);
+    fn set_trigger_effect(&mut self, device: DeviceClass, side: TriggerSide, effect: TriggerEffect);
+    fn set_light_color(&mut self, device: DeviceClass, color: LightColor);
+    fn set_player_indicator(&mut self, device: DeviceClass, player: u8);
+    fn set_rumble(&mut self, device: DeviceClass, low: f64, high: f64);
+    fn play_haptic(&mut self, device: DeviceClass, pattern: HapticPattern) -> bool;
+    fn stop_haptic(&mut self, device: DeviceClass);
+    fn tick(&mut self, delta: Duration);
+}
+
+impl IOutputRouter for OutputRouter {
+    fn new() -> Self {
+        OutputRouter::default()
+    }
+    fn add_backend(&mut self, device: DeviceClass, backend: Box<dyn OutputBackend>) {
+        self.backends.push((device, backend));
+    }
+    fn set_trigger_effect(
+        &mut self,
+        device: DeviceClass,
+        side: TriggerSide,
+        effect: TriggerEffect,
+    ) {
+        for backend in self.backends_for(device) {
+            let capabilities = backend.capabilities();
+            if capabilities.adaptive_triggers {
+                backend.set_trigger_effect(side, &effect);
+            } else if capabilities.rumble {
+                let amplitude = match effect {
+                    TriggerEffect::Vibration { amplitude, .. } => amplitude,
+                    _ => 0.0,
+                };
+                match side {
+                    TriggerSide::Left => backend.set_rumble(amplitude, 0.0),
+                    TriggerSide::Right => backend.set_rumble(0.0, amplitude),
+                }
+            }
+        }
+    }
+    fn set_light_color(&mut self, device: DeviceClass, color: LightColor) {
+        for backend in self.backends_for(device) {
+            if backend.capabilities().light_bar {
+                backend.set_light_color(color);
+            }
+        }
+    }
+    fn set_player_indicator(&mut self, device: DeviceClass, player: u8) {
+        for backend in self.backends_for(device) {
+            if backend.capabilities().player_indicator {
+                backend.set_player_indicator(player);
+            }
+        }
+    }
+    fn set_rumble(&mut self, device: DeviceClass, low: f64, high: f64) {
+        for backend in self.backends_for(device) {
+            if backend.capabilities().rumble {
+                backend.set_rumble(low, high);
+            }
+        }
+    }
+    fn play_haptic(&mut self, device: DeviceClass, pattern: HapticPattern) -> bool {
+        if let Some(playing) = self.haptics.get(&device) {
+            if playing.pattern.priority > pattern.priority {
+                return false;
+            }
+        }
+        match pattern.segments.first() {
+            Some(segment) => {
+                let (low, high) = (segment.low, segment.high);
+                self.haptics.insert(
+                    device,
+                    HapticPlayback {
+                        pattern,
+                        segment: 0,
+                        elapsed: Duration::default(),
+                    },
+                );
+                self.set_rumble(device, low, high);
+            }
+            None => self.stop_haptic(device),
+        }
+        true
+    }
+    fn stop_haptic(&mut self, device: DeviceClass) {
+        if self.haptics.remove(&device).is_some() {
+            self.set_rumble(device, 0.0, 0.0);
+        }
+    }
+    fn tick(&mut self, delta: Duration) {
+        let mut updates = Vec::new();
+        for (device, playback) in self.haptics.iter_mut() {
+            let segments = &playback.pattern.segments;
+            let total: Duration = segments.iter().map(|segment| segment.duration).sum();
+            let mut advanced = false;
+            let mut finished = false;
+            playback.elapsed += delta;
+            while playback.elapsed >= segments[playback.segment].duration {
+                playback.elapsed -= segments[playback.segment].duration;
+                playback.segment += 1;
+                advanced = true;
+                if playback.segment == segments.len() {
+                    if !playback.pattern.looping || total == Duration::default() {
+                        finished = true;
+                        break;
+                    }
+                    playback.segment = 0;
+                }
+            }
+            if finished {
+                updates.push((*device, None));
+            } else if advanced {
+                let segment = &segments[playback.segment];
+                updates.push((*device, Some((segment.low, segment.high))));
+            }
+        }
+        for (device, rumble) in updates {
+            match rumble {
+                Some((low, high)) => self.set_rumble(device, low, high),
+                None => self.stop_haptic(device),
+            }
+        }
+    }
+}
+
+impl OutputRouter {
+    fn backends_for(
+        &mut self,
+        device: DeviceClass,
+    ) -> impl Iterator<Item = &mut Box<dyn OutputBackend>> {
+        self.backends
+            .iter_mut()
+            .filter(move |(class, _)| *class == device)
+            .map(|(_, backend)| backend)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Trigger(TriggerSide, TriggerEffect),
+        Rumble(f64, f64),
+        Light(LightColor),
+        Player(u8),
+    }
+
+    struct MockBackend {
+        capabilities: OutputCapabilities,
+        calls: Rc<RefCell<Vec<Call>>>,
+    }
+
+    impl OutputBackend for MockBackend {
+        fn capabilities(&self) -> OutputCapabilities {
+            self.capabilities.clone()
+        }
+        fn set_trigger_effect(&mut self, side: TriggerSide, effect: &TriggerEffect) {
+            self.calls
+                .borrow_mut()
+                .push(Call::Trigger(side, effect.clone()));
+        }
+        fn set_rumble(&mut self, low: f64, high: f64) {
+            self.calls.borrow_mut().push(Call::Rumble(low, high));
+        }
+        fn set_light_color(&mut self, color: LightColor) {
+            self.calls.borrow_mut().push(Call::Light(color));
+        }
+        fn set_player_indicator(&mut self, player: u8) {
+            self.calls.borrow_mut().push(Call::Player(player));
+        }
+    }
+
+    fn router_with(capabilities: OutputCapabilities) -> (OutputRouter, Rc<RefCell<Vec<Call>>>) {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let mut router = OutputRouter::new();
+        router.add_backend(
+            DeviceClass::Gamepad(0),
+            Box::new(MockBackend {
+                capabilities,
+                calls: calls.clone(),
+            }),
+        );
+        (router, calls)
+    }
+
+    #[test]
+    fn routes_effect_to_adaptive_backend() {
+        let (mut router, calls) = router_with(OutputCapabilities {
+            adaptive_triggers: true,
+            rumble: true,
+            ..OutputCapabilities::default()
+        });
+        let effect = TriggerEffect::Resistance {
+            start: 0.3,
+            strength: 0.8,
+        };
+        router.set_trigger_effect(DeviceClass::Gamepad(0), TriggerSide::Right, effect.clone());
+        assert_eq!(
+            *calls.borrow(),
+            vec![Call::Trigger(TriggerSide::Right, effect)]
+        );
+    }
+
+    #[test]
+    fn vibration_degrades_to_rumble() {
+        let (mut router, calls) = router_with(OutputCapabilities {
+            adaptive_triggers: false,
+            rumble: true,
+            ..OutputCapabilities::default()
+        });
+        router.set_trigger_effect(
+            DeviceClass::Gamepad(0),
+            TriggerSide::Left,
+            TriggerEffect::Vibration {
+                start: 0.0,
+                amplitude: 0.5,
+                frequency: 30.0,
+            },
+        );
+        assert_eq!(*calls.borrow(), vec![Call::Rumble(0.5, 0.0)]);
+    }
+
+    #[test]
+    fn unsupported_backend_is_skipped() {
+        let (mut router, calls) = router_with(OutputCapabilities::default());
+        router.set_trigger_effect(
+            DeviceClass::Gamepad(0),
+            TriggerSide::Left,
+            TriggerEffect::Curve(vec![(0.0, 1.0)]),
+        );
+        router.set_light_color(DeviceClass::Gamepad(0), LightColor::default());
+        assert!(calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn lights_only_reach_the_addressed_device() {
+        let (mut router, calls) = router_with(OutputCapabilities {
+            light_bar: true,
+            player_indicator: true,
+            ..OutputCapabilities::default()
+        });
+        let red = LightColor { r: 255, g: 0, b: 0 };
+        router.set_light_color(DeviceClass::Gamepad(1), red);
+        router.set_light_color(DeviceClass::Gamepad(0), red);
+        router.set_player_indicator(DeviceClass::Gamepad(0), 2);
+        assert_eq!(*calls.borrow(), vec![Call::Light(red), Call::Player(2)]);
+    }
+
+    #[test]
+    fn haptic_patterns_advance_and_respect_priority() {
+        let (mut router, calls) = router_with(OutputCapabilities {
+            rumble: true,
+            ..OutputCapabilities::default()
+        });
+        let pad = DeviceClass::Gamepad(0);
+        let segment = |strength: f64| HapticSegment {
+            low: strength,
+            high: strength,
+            duration: Duration::from_millis(100),
+        };
+        let heartbeat = HapticPattern {
+            segments: vec![segment(1.0), segment(0.0)],
+            looping: false,
+            priority: 1,
+        };
+        assert!(router.play_haptic(pad, heartbeat));
+        assert!(!router.play_haptic(
+            pad,
+            HapticPattern {
+                segments: vec![segment(0.5)],
+                ..HapticPattern::default()
+            }
+        ));
+        router.tick(Duration::from_millis(50));
+        router.tick(Duration::from_millis(100));
+        router.tick(Duration::from_millis(100));
+        assert_eq!(
+            *calls.borrow(),
+            vec![
+                Call::Rumble(1.0, 1.0),
+                Call::Rumble(0.0, 0.0),
+                Call::Rumble(0.0, 0.0)
+            ]
+        );
+        assert!(router.play_haptic(
+            pad,
+            HapticPattern {
+                segments: vec![segment(0.5)],
+                ..HapticPattern::default()
+            }
+        ));
+    }
+}
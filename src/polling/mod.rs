@@ -0,0 +1,2 @@
+mod polling;
+pub use self::polling::*;
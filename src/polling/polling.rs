@@ -0,0 +1,104 @@
+use manager::Change;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Anything that can be drained for raw inputs, e.g. a gamepad backend.
+pub trait InputSource<C>: Send {
+    fn poll(&mut self) -> Vec<(C, Change)>;
+}
+
+/// Drains sources on a dedicated thread, stamping every change with the
+/// time since polling started.
+pub struct Poller<C> {
+    receiver: Receiver<(C, Change, Duration)>,
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+pub trait IPoller<C> {
+    fn spawn(sources: Vec<Box<dyn InputSource<C>>>, interval: Duration) -> Self
+    where
+        Self: Sized,
+        C: Send + 'static;
+    fn drain(&self) -> Vec<(C, Change, Duration)>;
+    fn stop(&mut self);
+}
+
+impl<C> IPoller<C> for Poller<C> {
+    fn spawn(mut sources: Vec<Box<dyn InputSource<C>>>, interval: Duration) -> Self
+    where
+        C: Send + 'static,
+    {
+        let (sender, receiver) = channel();
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = running.clone();
+        let handle = thread::spawn(move || {
+            let start = Instant::now();
+            while thread_running.load(Ordering::Relaxed) {
+                for source in sources.iter_mut() {
+                    for (input, change) in source.poll() {
+                        if sender.send((input, change, start.elapsed())).is_err() {
+                            return;
+                        }
+                    }
+                }
+                thread::sleep(interval);
+            }
+        });
+        Poller {
+            receiver,
+            running,
+            handle: Some(handle),
+        }
+    }
+    fn drain(&self) -> Vec<(C, Change, Duration)> {
+        self.receiver.try_iter().collect()
+    }
+    fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl<C> Drop for Poller<C> {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use change::ButtonChange;
+    struct Once(bool);
+    impl InputSource<char> for Once {
+        fn poll(&mut self) -> Vec<(char, Change)> {
+            if self.0 {
+                vec![]
+            } else {
+                self.0 = true;
+                vec![('a', ButtonChange(true).into())]
+            }
+        }
+    }
+    #[test]
+    fn drains_polled_changes() {
+        let mut poller = Poller::spawn(vec![Box::new(Once(false))], Duration::from_millis(1));
+        let mut drained = Vec::new();
+        for _ in 0..1000 {
+            drained.extend(poller.drain());
+            if !drained.is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        poller.stop();
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0].0, 'a');
+    }
+}
@@ -0,0 +1,264 @@
+use manager::Binding;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+/// A set of bindings, optionally layered over a parent profile. Only the
+/// overrides are stored; lookups fall through to the parent.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BindingProfile<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> {
+    parent: Option<Rc<BindingProfile<A, B, C>>>,
+    bindings: HashMap<C, Option<Binding<A, B>>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ChangedBinding<A, B, C> {
+    pub input: C,
+    pub from: Binding<A, B>,
+    pub to: Binding<A, B>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ProfileDiff<A, B, C> {
+    pub added: Vec<(C, Binding<A, B>)>,
+    pub removed: Vec<(C, Binding<A, B>)>,
+    pub changed: Vec<ChangedBinding<A, B, C>>,
+}
+
+pub trait IBindingProfile<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> {
+    fn new() -> Self
+    where
+        Self: Sized;
+    fn from_bindings(bindings: HashMap<C, Binding<A, B>>) -> Self
+    where
+        Self: Sized;
+    fn with_parent(parent: Rc<Self>) -> Self
+    where
+        Self: Sized;
+    fn add_axis_binding(&mut self, axis: A, input: C);
+    fn add_button_binding(&mut self, button: B, input: C);
+    fn add_binding(&mut self, binding: Binding<A, B>, input: C);
+    fn remove_binding(&mut self, input: C);
+    fn get_binding(&self, input: &C) -> Option<&Binding<A, B>>;
+    fn get_bindings(&self) -> HashMap<C, Binding<A, B>>;
+    /// The bindings set or removed on this profile itself, without the
+    /// parent's.
+    fn get_overrides(&self) -> &HashMap<C, Option<Binding<A, B>>>;
+    fn diff(&self, other: &Self) -> ProfileDiff<A, B, C>;
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq + Clone> IBindingProfile<A, B, C>
+    for BindingProfile<A, B, C>
+{
+    fn new() -> Self {
+        BindingProfile {
+            parent: None,
+            bindings: HashMap::new(),
+        }
+    }
+    fn from_bindings(bindings: HashMap<C, Binding<A, B>>) -> Self {
+        BindingProfile {
+            parent: None,
+            bindings: bindings
+                .into_iter()
+                .map(|(input, binding)| (input, Some(binding)))
+                .collect(),
+        }
+    }
+    fn with_parent(parent: Rc<Self>) -> Self {
+        BindingProfile {
+            parent: Some(parent),
+            bindings: HashMap::new(),
+        }
+    }
+    fn add_axis_binding(&mut self, axis: A, input: C) {
+        self.bindings.insert(input, Some(Binding::Axis(axis)));
+    }
+    fn add_button_binding(&mut self, button: B, input: C) {
+        self.bindings.insert(input, Some(Binding::Button(button)));
+    }
+    fn add_binding(&mut self, binding: Binding<A, B>, input: C) {
+        self.bindings.insert(input, Some(binding));
+    }
+    fn remove_binding(&mut self, input: C) {
+        let inherited = self
+            .parent
+            .as_ref()
+            .and_then(|parent| parent.get_binding(&input))
+            .is_some();
+        if inherited {
+            self.bindings.insert(input, None);
+        } else {
+            self.bindings.remove(&input);
+        }
+    }
+    fn get_binding(&self, input: &C) -> Option<&Binding<A, B>> {
+        match self.bindings.get(input) {
+            Some(binding) => binding.as_ref(),
+            None => self
+                .parent
+                .as_ref()
+                .and_then(|parent| parent.get_binding(input)),
+        }
+    }
+    fn get_bindings(&self) -> HashMap<C, Binding<A, B>> {
+        let mut bindings = self
+            .parent
+            .as_ref()
+            .map(|parent| parent.get_bindings())
+            .unwrap_or_default();
+        for (input, binding) in self.bindings.iter() {
+            match binding {
+                Some(binding) => bindings.insert(input.clone(), binding.clone()),
+                None => bindings.remove(input),
+            };
+        }
+        bindings
+    }
+    fn get_overrides(&self) -> &HashMap<C, Option<Binding<A, B>>> {
+        &self.bindings
+    }
+    fn diff(&self, other: &Self) -> ProfileDiff<A, B, C> {
+        let mine = self.get_bindings();
+        let theirs = other.get_bindings();
+        let mut diff = ProfileDiff {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        };
+        for (input, binding) in theirs.iter() {
+            match mine.get(input) {
+                None => diff.added.push((input.clone(), binding.clone())),
+                Some(previous) if previous != binding => diff.changed.push(ChangedBinding {
+                    input: input.clone(),
+                    from: previous.clone(),
+                    to: binding.clone(),
+                }),
+                _ => {}
+            }
+        }
+        for (input, binding) in mine.iter() {
+            if !theirs.contains_key(input) {
+                diff.removed.push((input.clone(), binding.clone()));
+            }
+        }
+        diff
+    }
+}
+
+/// Every player's bindings in one document. Each player's profile is layered
+/// over `shared`, so only their overrides are stored and players without a
+/// section get the shared bindings.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PlayerProfiles<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> {
+    pub shared: Rc<BindingProfile<A, B, C>>,
+    pub players: Vec<(String, BindingProfile<A, B, C>)>,
+}
+
+pub trait IPlayerProfiles<A: Hash + Eq, B: Hash + Eq, C: Hash + Eq> {
+    fn new(shared: BindingProfile<A, B, C>) -> Self
+    where
+        Self: Sized;
+    fn player_mut(&mut self, player: &str) -> &mut BindingProfile<A, B, C>;
+    fn profile_for(&self, player: &str) -> BindingProfile<A, B, C>;
+}
+
+impl<A: Hash + Eq + Clone, B: Hash + Eq + Clone, C: Hash + Eq + Clone> IPlayerProfiles<A, B, C>
+    for PlayerProfiles<A, B, C>
+{
+    fn new(shared: BindingProfile<A, B, C>) -> Self {
+        PlayerProfiles {
+            shared: Rc::new(shared),
+            players: Vec::new(),
+        }
+    }
+    fn player_mut(&mut self, player: &str) -> &mut BindingProfile<A, B, C> {
+        let index = match self.players.iter().position(|(name, _)| name == player) {
+            Some(index) => index,
+            None => {
+                let profile = BindingProfile::with_parent(self.shared.clone());
+                self.players.push((player.to_string(), profile));
+                self.players.len() - 1
+            }
+        };
+        &mut self.players[index].1
+    }
+    fn profile_for(&self, player: &str) -> BindingProfile<A, B, C> {
+        self.players
+            .iter()
+            .find(|(name, _)| name == player)
+            .map(|(_, profile)| profile.clone())
+            .unwrap_or_else(|| BindingProfile::with_parent(self.shared.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn diff_lists_added_removed_and_changed() {
+        let mut before: BindingProfile<&str, &str, char> = BindingProfile::new();
+        before.add_axis_binding("Vertical", 'w');
+        before.add_button_binding("Jump", ' ');
+        before.add_button_binding("Fire", 'f');
+        let mut after = before.clone();
+        after.add_button_binding("Block", 'b');
+        after.add_button_binding("Fire", ' ');
+        after.remove_binding('f');
+        let diff = before.diff(&after);
+        assert_eq!(diff.added, vec![('b', Binding::Button("Block"))]);
+        assert_eq!(diff.removed, vec![('f', Binding::Button("Fire"))]);
+        assert_eq!(
+            diff.changed,
+            vec![ChangedBinding {
+                input: ' ',
+                from: Binding::Button("Jump"),
+                to: Binding::Button("Fire"),
+            }]
+        );
+    }
+    #[test]
+    fn identical_profiles_have_empty_diff() {
+        let mut profile: BindingProfile<&str, &str, char> = BindingProfile::new();
+        profile.add_button_binding("Jump", ' ');
+        assert_eq!(
+            profile.diff(&profile.clone()),
+            ProfileDiff {
+                added: vec![],
+                removed: vec![],
+                changed: vec![],
+            }
+        );
+    }
+    #[test]
+    fn child_falls_back_to_parent() {
+        let mut base: BindingProfile<&str, &str, char> = BindingProfile::new();
+        base.add_button_binding("Pause", 'p');
+        base.add_button_binding("Map", 'm');
+        let base = Rc::new(base);
+        let mut child = BindingProfile::with_parent(base.clone());
+        child.add_button_binding("Jump", ' ');
+        child.add_button_binding("Inventory", 'm');
+        child.remove_binding('p');
+        assert_eq!(child.get_binding(&' '), Some(&Binding::Button("Jump")));
+        assert_eq!(child.get_binding(&'m'), Some(&Binding::Button("Inventory")));
+        assert_eq!(child.get_binding(&'p'), None);
+        assert_eq!(base.get_binding(&'p'), Some(&Binding::Button("Pause")));
+        assert_eq!(child.get_bindings().len(), 2);
+    }
+
+    #[test]
+    fn players_fall_back_to_shared_profile() {
+        let mut shared: BindingProfile<&str, &str, char> = BindingProfile::new();
+        shared.add_button_binding("Jump", ' ');
+        shared.add_button_binding("Pause", 'p');
+        let mut profiles = PlayerProfiles::new(shared);
+        profiles.player_mut("p2").add_button_binding("Jump", 'j');
+        profiles.player_mut("p2").remove_binding('p');
+        let p2 = profiles.profile_for("p2");
+        assert_eq!(p2.get_binding(&'j'), Some(&Binding::Button("Jump")));
+        assert_eq!(p2.get_binding(&'p'), None);
+        let p1 = profiles.profile_for("p1");
+        assert_eq!(p1.get_bindings(), profiles.shared.get_bindings());
+    }
+}
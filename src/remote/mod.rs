@@ -0,0 +1,2 @@
+mod remote;
+pub use self::remote::*;
@@ -0,0 +1,106 @@
+use encoding::{decode_from_slice, encode_to_vec};
+use manager::Change;
+use polling::InputSource;
+use std::collections::HashMap;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+pub const REMOTE_MAGIC: u8 = 0x52;
+
+/// Accepts inputs from a companion app over UDP. Each datagram is
+/// `REMOTE_MAGIC` followed by an encoded `Vec<(u16, Change)>`; the ids are
+/// mapped to the game's own inputs so the remote can be classified as its own
+/// device (e.g. `DeviceClass::Remote`).
+pub struct RemoteSource<C> {
+    socket: UdpSocket,
+    mappings: HashMap<u16, C>,
+}
+
+pub trait IRemoteSource<C> {
+    fn bind<T: ToSocketAddrs>(addr: T) -> io::Result<Self>
+    where
+        Self: Sized;
+    fn local_addr(&self) -> io::Result<SocketAddr>;
+    fn map(&mut self, id: u16, input: C);
+}
+
+impl<C> IRemoteSource<C> for RemoteSource<C> {
+    fn bind<T: ToSocketAddrs>(addr: T) -> io::Result<Self> {
+        let socket = UdpSocket::bind(addr)?;
+        socket.set_nonblocking(true)?;
+        Ok(RemoteSource {
+            socket,
+            mappings: HashMap::new(),
+        })
+    }
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+    fn map(&mut self, id: u16, input: C) {
+        self.mappings.insert(id, input);
+    }
+}
+
+impl<C: Clone + Send> InputSource<C> for RemoteSource<C> {
+    fn poll(&mut self) -> Vec<(C, Change)> {
+        let mut changes = Vec::new();
+        let mut packet = [0; 1536];
+        while let Ok(read) = self.socket.recv(&mut packet) {
+            let inputs: Vec<(u16, Change)> = match packet[..read].split_first() {
+                Some((&REMOTE_MAGIC, body)) => decode_from_slice(body).unwrap_or_default(),
+                _ => continue,
+            };
+            for (id, change) in inputs {
+                if let Some(input) = self.mappings.get(&id) {
+                    changes.push((input.clone(), change));
+                }
+            }
+        }
+        changes
+    }
+}
+
+pub fn encode_remote_packet(inputs: Vec<(u16, Change)>) -> Vec<u8> {
+    let mut packet = vec![REMOTE_MAGIC];
+    packet.extend(encode_to_vec(&inputs));
+    packet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use change::{AxisChange, ButtonChange};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn receives_mapped_remote_inputs() {
+        let mut source = RemoteSource::bind("127.0.0.1:0").unwrap();
+        source.map(1, "jump");
+        source.map(2, "steer");
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = source.local_addr().unwrap();
+        sender.send_to(&[0, 1, 2], addr).unwrap();
+        let packet = encode_remote_packet(vec![
+            (1, ButtonChange(true).into()),
+            (2, AxisChange::Position(-0.5).into()),
+            (3, ButtonChange(true).into()),
+        ]);
+        sender.send_to(&packet, addr).unwrap();
+        let mut polled = Vec::new();
+        for _ in 0..1000 {
+            polled.extend(source.poll());
+            if !polled.is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(
+            polled,
+            vec![
+                ("jump", ButtonChange(true).into()),
+                ("steer", AxisChange::Position(-0.5).into()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,2 @@
+mod serial;
+pub use self::serial::*;
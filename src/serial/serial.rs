@@ -0,0 +1,85 @@
+use manager::Change;
+use polling::InputSource;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Consumes complete frames from the front of `buffer`, leaving any partial
+/// frame in place for the next read.
+pub trait FrameParser<C>: Send {
+    fn parse(&mut self, buffer: &mut Vec<u8>) -> Vec<(C, Change)>;
+}
+
+impl<C, F: FnMut(&mut Vec<u8>) -> Vec<(C, Change)> + Send> FrameParser<C> for F {
+    fn parse(&mut self, buffer: &mut Vec<u8>) -> Vec<(C, Change)> {
+        self(buffer)
+    }
+}
+
+/// Reads raw bytes from a serial device (or any reader) and hands them to a
+/// parser. Ports should be configured with a read timeout so polling does not
+/// block shutdown.
+pub struct SerialSource<R, C> {
+    reader: R,
+    parser: Box<dyn FrameParser<C>>,
+    buffer: Vec<u8>,
+}
+
+pub trait ISerialSource<R, C> {
+    fn new(reader: R, parser: Box<dyn FrameParser<C>>) -> Self
+    where
+        Self: Sized;
+}
+
+impl<R: Read, C> ISerialSource<R, C> for SerialSource<R, C> {
+    fn new(reader: R, parser: Box<dyn FrameParser<C>>) -> Self {
+        SerialSource {
+            reader,
+            parser,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+pub fn open_serial<C>(
+    path: impl AsRef<Path>,
+    parser: Box<dyn FrameParser<C>>,
+) -> io::Result<SerialSource<File, C>> {
+    Ok(SerialSource::new(File::open(path)?, parser))
+}
+
+impl<R: Read + Send, C> InputSource<C> for SerialSource<R, C> {
+    fn poll(&mut self) -> Vec<(C, Change)> {
+        let mut chunk = [0; 256];
+        match self.reader.read(&mut chunk) {
+            Ok(read) if read > 0 => self.buffer.extend_from_slice(&chunk[..read]),
+            _ => return Vec::new(),
+        }
+        self.parser.parse(&mut self.buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use change::ButtonChange;
+    use std::io::Cursor;
+
+    fn button_frames(buffer: &mut Vec<u8>) -> Vec<(u8, Change)> {
+        let complete = buffer.len() / 2 * 2;
+        buffer
+            .drain(..complete)
+            .collect::<Vec<u8>>()
+            .chunks(2)
+            .map(|frame| (frame[0], ButtonChange(frame[1] != 0).into()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_frames_and_keeps_partial_ones() {
+        let mut source = SerialSource::new(Cursor::new(vec![1, 1, 2]), Box::new(button_frames));
+        assert_eq!(source.poll(), vec![(1, ButtonChange(true).into())]);
+        assert!(source.poll().is_empty());
+        assert_eq!(source.buffer, vec![2]);
+    }
+}
@@ -0,0 +1,2 @@
+mod tilt;
+pub use self::tilt::*;
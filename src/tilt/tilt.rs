@@ -0,0 +1,113 @@
+use change::AxisChange;
+use manager::Change;
+
+/// `max_angle` is the tilt in radians that maps to a full axis deflection.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TiltConfig {
+    pub max_angle: f64,
+    pub dead_zone: f64,
+}
+
+impl Default for TiltConfig {
+    fn default() -> Self {
+        TiltConfig {
+            max_angle: 0.5,
+            dead_zone: 0.05,
+        }
+    }
+}
+
+/// Converts device roll/pitch into two axis positions relative to a
+/// recenterable neutral orientation.
+#[derive(Debug, Clone)]
+pub struct TiltControl<C> {
+    roll: C,
+    pitch: C,
+    config: TiltConfig,
+    center: (f64, f64),
+    last: (f64, f64),
+}
+
+pub trait ITiltControl<C> {
+    fn new(roll: C, pitch: C, config: TiltConfig) -> Self
+    where
+        Self: Sized;
+    fn update(&mut self, roll: f64, pitch: f64) -> Vec<(C, Change)>;
+    fn recenter(&mut self);
+}
+
+impl<C: Clone> ITiltControl<C> for TiltControl<C> {
+    fn new(roll: C, pitch: C, config: TiltConfig) -> Self {
+        TiltControl {
+            roll,
+            pitch,
+            config,
+            center: (0.0, 0.0),
+            last: (0.0, 0.0),
+        }
+    }
+    fn update(&mut self, roll: f64, pitch: f64) -> Vec<(C, Change)> {
+        self.last = (roll, pitch);
+        vec![
+            (
+                self.roll.clone(),
+                AxisChange::Position(self.calibrate(roll - self.center.0)).into(),
+            ),
+            (
+                self.pitch.clone(),
+                AxisChange::Position(self.calibrate(pitch - self.center.1)).into(),
+            ),
+        ]
+    }
+    fn recenter(&mut self) {
+        self.center = self.last;
+    }
+}
+
+impl<C> TiltControl<C> {
+    fn calibrate(&self, angle: f64) -> f64 {
+        let value = (angle / self.config.max_angle).clamp(-1.0, 1.0);
+        if value.abs() < self.config.dead_zone {
+            0.0
+        } else {
+            value.signum() * (value.abs() - self.config.dead_zone) / (1.0 - self.config.dead_zone)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(changes: Vec<(char, Change)>) -> Vec<f64> {
+        changes
+            .into_iter()
+            .map(|(_, change)| match change {
+                Change::Axis(AxisChange::Position(position)) => position,
+                _ => panic!("expected a position"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn scales_and_dead_zones_tilt() {
+        let mut tilt = TiltControl::new(
+            'r',
+            'p',
+            TiltConfig {
+                max_angle: 1.0,
+                dead_zone: 0.2,
+            },
+        );
+        assert_eq!(positions(tilt.update(0.1, -2.0)), vec![0.0, -1.0]);
+        assert!((positions(tilt.update(0.6, 0.0))[0] - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn recenter_uses_current_orientation() {
+        let mut tilt = TiltControl::new('r', 'p', TiltConfig::default());
+        tilt.update(0.3, 0.2);
+        tilt.recenter();
+        assert_eq!(positions(tilt.update(0.3, 0.2)), vec![0.0, 0.0]);
+    }
+}
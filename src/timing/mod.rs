@@ -0,0 +1,2 @@
+mod timing;
+pub use self::timing::*;
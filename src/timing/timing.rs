@@ -0,0 +1,95 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Rolling press statistics for one button, sampled once per tick. Durations
+/// and intervals are kept for the last `window` presses.
+#[derive(Debug, Default, Clone)]
+pub struct ButtonTimings {
+    window: usize,
+    durations: VecDeque<Duration>,
+    intervals: VecDeque<Duration>,
+    was_pressed: bool,
+    held_for: Duration,
+    since_press: Option<Duration>,
+}
+
+pub trait IButtonTimings {
+    fn new(window: usize) -> Self
+    where
+        Self: Sized;
+    fn sample(&mut self, pressed: bool, delta: Duration);
+    fn reset(&mut self);
+    fn average_press_duration(&self) -> Option<Duration>;
+    fn average_interval(&self) -> Option<Duration>;
+}
+
+fn push_windowed(samples: &mut VecDeque<Duration>, sample: Duration, window: usize) {
+    samples.push_back(sample);
+    while samples.len() > window {
+        samples.pop_front();
+    }
+}
+
+fn average(samples: &VecDeque<Duration>) -> Option<Duration> {
+    if samples.is_empty() {
+        return None;
+    }
+    Some(samples.iter().sum::<Duration>() / samples.len() as u32)
+}
+
+impl IButtonTimings for ButtonTimings {
+    fn new(window: usize) -> Self {
+        ButtonTimings {
+            window,
+            ..ButtonTimings::default()
+        }
+    }
+    fn sample(&mut self, pressed: bool, delta: Duration) {
+        if let Some(ref mut since_press) = self.since_press {
+            *since_press += delta;
+        }
+        if self.was_pressed {
+            self.held_for += delta;
+        }
+        match (self.was_pressed, pressed) {
+            (false, true) => {
+                if let Some(since_press) = self.since_press {
+                    push_windowed(&mut self.intervals, since_press, self.window);
+                }
+                self.since_press = Some(Duration::default());
+                self.held_for = Duration::default();
+            }
+            (true, false) => push_windowed(&mut self.durations, self.held_for, self.window),
+            _ => {}
+        }
+        self.was_pressed = pressed;
+    }
+    fn reset(&mut self) {
+        *self = ButtonTimings::new(self.window);
+    }
+    fn average_press_duration(&self) -> Option<Duration> {
+        average(&self.durations)
+    }
+    fn average_interval(&self) -> Option<Duration> {
+        average(&self.intervals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn averages_over_window() {
+        let mut timings = ButtonTimings::new(2);
+        let frame = Duration::from_millis(100);
+        for &pressed in &[
+            true, true, false, false, true, false, true, true, true, false,
+        ] {
+            timings.sample(pressed, frame);
+        }
+        assert_eq!(timings.average_press_duration(), Some(frame * 2));
+        assert_eq!(timings.average_interval(), Some(frame * 3));
+        timings.reset();
+        assert_eq!(timings.average_interval(), None);
+    }
+}
@@ -0,0 +1,2 @@
+mod touch;
+pub use self::touch::*;
@@ -0,0 +1,226 @@
+use change::{AxisChange, ButtonChange};
+use manager::Change;
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TouchRegion {
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+    Circle {
+        x: f64,
+        y: f64,
+        radius: f64,
+    },
+}
+
+impl TouchRegion {
+    pub fn contains(&self, px: f64, py: f64) -> bool {
+        match *self {
+            TouchRegion::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => px >= x && px <= x + width && py >= y && py <= y + height,
+            TouchRegion::Circle { x, y, radius } => {
+                (px - x).powi(2) + (py - y).powi(2) <= radius.powi(2)
+            }
+        }
+    }
+    fn offset(&self, px: f64, py: f64) -> (f64, f64) {
+        let (cx, cy, rx, ry) = match *self {
+            TouchRegion::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => (x + width / 2.0, y + height / 2.0, width / 2.0, height / 2.0),
+            TouchRegion::Circle { x, y, radius } => (x, y, radius, radius),
+        };
+        (
+            ((px - cx) / rx).clamp(-1.0, 1.0),
+            ((py - cy) / ry).clamp(-1.0, 1.0),
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum OverlayControl<C> {
+    Button(C),
+    Stick { x: C, y: C },
+}
+
+/// Maps touches on screen regions to raw inputs. Each touch is captured by
+/// the control it started on, so a thumb on the stick and another on a
+/// button are tracked independently.
+#[derive(Debug, Clone)]
+pub struct TouchOverlay<C> {
+    controls: Vec<(TouchRegion, OverlayControl<C>)>,
+    touches: HashMap<u64, usize>,
+}
+
+pub trait ITouchOverlay<C> {
+    fn new() -> Self
+    where
+        Self: Sized;
+    fn add_control(&mut self, region: TouchRegion, control: OverlayControl<C>);
+    fn touch_start(&mut self, id: u64, x: f64, y: f64) -> Vec<(C, Change)>;
+    fn touch_move(&mut self, id: u64, x: f64, y: f64) -> Vec<(C, Change)>;
+    fn touch_end(&mut self, id: u64) -> Vec<(C, Change)>;
+}
+
+impl<C: Clone> ITouchOverlay<C> for TouchOverlay<C> {
+    fn new() -> Self {
+        TouchOverlay {
+            controls: Vec::new(),
+            touches: HashMap::new(),
+        }
+    }
+    fn add_control(&mut self, region: TouchRegion, control: OverlayControl<C>) {
+        self.controls.push((region, control));
+    }
+    fn touch_start(&mut self, id: u64, x: f64, y: f64) -> Vec<(C, Change)> {
+        let index = match self
+            .controls
+            .iter()
+            .position(|(region, _)| region.contains(x, y))
+        {
+            Some(index) => index,
+            None => return Vec::new(),
+        };
+        let already_pressed = self.is_touched(index);
+        self.touches.insert(id, index);
+        match self.controls[index].1 {
+            OverlayControl::Button(ref input) if !already_pressed => {
+                vec![(input.clone(), ButtonChange(true).into())]
+            }
+            OverlayControl::Button(_) => Vec::new(),
+            OverlayControl::Stick { .. } => self.stick_changes(index, x, y),
+        }
+    }
+    fn touch_move(&mut self, id: u64, x: f64, y: f64) -> Vec<(C, Change)> {
+        match self.touches.get(&id) {
+            Some(&index) => match self.controls[index].1 {
+                OverlayControl::Stick { .. } => self.stick_changes(index, x, y),
+                OverlayControl::Button(_) => Vec::new(),
+            },
+            None => Vec::new(),
+        }
+    }
+    fn touch_end(&mut self, id: u64) -> Vec<(C, Change)> {
+        let index = match self.touches.remove(&id) {
+            Some(index) => index,
+            None => return Vec::new(),
+        };
+        if self.is_touched(index) {
+            return Vec::new();
+        }
+        match self.controls[index].1 {
+            OverlayControl::Button(ref input) => {
+                vec![(input.clone(), ButtonChange(false).into())]
+            }
+            OverlayControl::Stick { ref x, ref y } => vec![
+                (x.clone(), AxisChange::Position(0.0).into()),
+                (y.clone(), AxisChange::Position(0.0).into()),
+            ],
+        }
+    }
+}
+
+impl<C: Clone> TouchOverlay<C> {
+    fn is_touched(&self, index: usize) -> bool {
+        self.touches.values().any(|&touched| touched == index)
+    }
+    fn stick_changes(&self, index: usize, px: f64, py: f64) -> Vec<(C, Change)> {
+        let (region, control) = &self.controls[index];
+        match *control {
+            OverlayControl::Stick { ref x, ref y } => {
+                let (dx, dy) = region.offset(px, py);
+                vec![
+                    (x.clone(), AxisChange::Position(dx).into()),
+                    (y.clone(), AxisChange::Position(dy).into()),
+                ]
+            }
+            OverlayControl::Button(_) => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Clone)]
+    enum Input {
+        StickX,
+        StickY,
+        Fire,
+    }
+
+    fn overlay() -> TouchOverlay<Input> {
+        let mut overlay = TouchOverlay::new();
+        overlay.add_control(
+            TouchRegion::Circle {
+                x: 100.0,
+                y: 100.0,
+                radius: 50.0,
+            },
+            OverlayControl::Stick {
+                x: Input::StickX,
+                y: Input::StickY,
+            },
+        );
+        overlay.add_control(
+            TouchRegion::Rect {
+                x: 300.0,
+                y: 80.0,
+                width: 40.0,
+                height: 40.0,
+            },
+            OverlayControl::Button(Input::Fire),
+        );
+        overlay
+    }
+
+    #[test]
+    fn stick_and_button_work_simultaneously() {
+        let mut overlay = overlay();
+        overlay.touch_start(1, 100.0, 100.0);
+        assert_eq!(
+            overlay.touch_start(2, 320.0, 100.0),
+            vec![(Input::Fire, ButtonChange(true).into())]
+        );
+        assert_eq!(
+            overlay.touch_move(1, 125.0, 200.0),
+            vec![
+                (Input::StickX, AxisChange::Position(0.5).into()),
+                (Input::StickY, AxisChange::Position(1.0).into()),
+            ]
+        );
+        assert_eq!(
+            overlay.touch_end(2),
+            vec![(Input::Fire, ButtonChange(false).into())]
+        );
+        assert_eq!(
+            overlay.touch_end(1),
+            vec![
+                (Input::StickX, AxisChange::Position(0.0).into()),
+                (Input::StickY, AxisChange::Position(0.0).into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn button_stays_pressed_until_last_touch_lifts() {
+        let mut overlay = overlay();
+        overlay.touch_start(1, 310.0, 90.0);
+        assert!(overlay.touch_start(2, 330.0, 110.0).is_empty());
+        assert!(overlay.touch_end(1).is_empty());
+        assert_eq!(overlay.touch_end(2).len(), 1);
+        assert!(overlay.touch_start(3, 0.0, 0.0).is_empty());
+    }
+}
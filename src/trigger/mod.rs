@@ -0,0 +1,2 @@
+mod trigger;
+pub use self::trigger::*;
@@ -0,0 +1,99 @@
+use change::TriggerChange;
+#[cfg(feature = "encoding")]
+use encoding::{BitReader, BitWriter, Decode, DecodeError, Encode};
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+/// A one-directional analog input such as a gamepad trigger or a pedal.
+/// Unlike an axis it rests at 0 and is clamped to 0..1, so it never falls
+/// through zero to the other side.
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct Trigger {
+    pub value: f64,
+    pub new_event: bool,
+    last_changed_at: Option<Duration>,
+}
+
+#[cfg(feature = "encoding")]
+impl Encode for Trigger {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.value.encode(writer);
+        self.new_event.encode(writer);
+        self.last_changed_at.encode(writer);
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl Decode for Trigger {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(Trigger {
+            value: f64::decode(reader)?,
+            new_event: bool::decode(reader)?,
+            last_changed_at: Option::decode(reader)?,
+        })
+    }
+}
+
+impl Hash for Trigger {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.to_bits().hash(state);
+        self.new_event.hash(state);
+        self.last_changed_at.hash(state);
+    }
+}
+
+pub trait ITrigger {
+    fn apply(&mut self, change: TriggerChange);
+    fn apply_at(&mut self, change: TriggerChange, at: Duration);
+    fn tick(&mut self, delta: Duration);
+    fn end_frame(&mut self);
+    fn last_changed_at(&self) -> Option<Duration>;
+    #[cfg(feature = "strict-invariants")]
+    fn assert_invariants(&self);
+}
+
+impl ITrigger for Trigger {
+    fn apply(&mut self, change: TriggerChange) {
+        self.last_changed_at = None;
+        let value = change.0.clamp(0.0, 1.0);
+        if value != self.value {
+            self.value = value;
+            self.new_event = true;
+        }
+    }
+    fn apply_at(&mut self, change: TriggerChange, at: Duration) {
+        self.apply(change);
+        self.last_changed_at = Some(at);
+    }
+    fn tick(&mut self, _delta: Duration) {
+        self.end_frame();
+    }
+    fn end_frame(&mut self) {
+        self.new_event = false;
+    }
+    fn last_changed_at(&self) -> Option<Duration> {
+        self.last_changed_at
+    }
+    #[cfg(feature = "strict-invariants")]
+    fn assert_invariants(&self) {
+        debug_assert!((0.0..=1.0).contains(&self.value), "trigger outside 0..1");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn clamps_to_unit_range() {
+        let mut trigger = Trigger::default();
+        trigger.apply(TriggerChange(0.75));
+        assert!(trigger.new_event);
+        assert_eq!(trigger.value, 0.75);
+        trigger.tick(Duration::from_millis(16));
+        assert!(!trigger.new_event);
+        trigger.apply(TriggerChange(-0.5));
+        assert_eq!(trigger.value, 0.0);
+        trigger.apply(TriggerChange(1.5));
+        assert_eq!(trigger.value, 1.0);
+    }
+}
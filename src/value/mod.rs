@@ -0,0 +1,2 @@
+mod value;
+pub use self::value::*;
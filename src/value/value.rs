@@ -0,0 +1,99 @@
+use change::ValueChange;
+#[cfg(feature = "encoding")]
+use encoding::{BitReader, BitWriter, Decode, DecodeError, Encode};
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+/// An absolute scalar such as a volume knob or throttle lever. Unlike an
+/// axis it is never clamped and does not fall back to center.
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct Value {
+    pub value: f64,
+    pub new_event: bool,
+    last_changed_at: Option<Duration>,
+}
+
+#[cfg(feature = "encoding")]
+impl Encode for Value {
+    fn encode(&self, writer: &mut BitWriter) {
+        self.value.encode(writer);
+        self.new_event.encode(writer);
+        self.last_changed_at.encode(writer);
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl Decode for Value {
+    fn decode(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Ok(Value {
+            value: f64::decode(reader)?,
+            new_event: bool::decode(reader)?,
+            last_changed_at: Option::decode(reader)?,
+        })
+    }
+}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.to_bits().hash(state);
+        self.new_event.hash(state);
+        self.last_changed_at.hash(state);
+    }
+}
+
+pub trait IValue {
+    fn apply(&mut self, change: ValueChange);
+    fn apply_at(&mut self, change: ValueChange, at: Duration);
+    fn tick(&mut self, delta: Duration);
+    fn end_frame(&mut self);
+    fn last_changed_at(&self) -> Option<Duration>;
+    #[cfg(feature = "strict-invariants")]
+    fn assert_invariants(&self);
+}
+
+impl IValue for Value {
+    fn apply(&mut self, change: ValueChange) {
+        self.last_changed_at = None;
+        let value = match change {
+            ValueChange::Set(value) => value,
+            ValueChange::Offset(offset) => self.value + offset,
+        };
+        if value != self.value {
+            self.value = value;
+            self.new_event = true;
+        }
+    }
+    fn apply_at(&mut self, change: ValueChange, at: Duration) {
+        self.apply(change);
+        self.last_changed_at = Some(at);
+    }
+    fn tick(&mut self, _delta: Duration) {
+        self.end_frame();
+    }
+    fn end_frame(&mut self) {
+        self.new_event = false;
+    }
+    fn last_changed_at(&self) -> Option<Duration> {
+        self.last_changed_at
+    }
+    #[cfg(feature = "strict-invariants")]
+    fn assert_invariants(&self) {
+        debug_assert!(!self.value.is_nan(), "value is NaN");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn holds_value_without_clamping_or_falling() {
+        let mut value = Value::default();
+        value.apply(ValueChange::Set(3.5));
+        assert!(value.new_event);
+        value.tick(Duration::from_secs(10));
+        assert_eq!(value.value, 3.5);
+        assert!(!value.new_event);
+        value.apply(ValueChange::Offset(-5.0));
+        assert_eq!(value.value, -1.5);
+    }
+}
@@ -0,0 +1,2 @@
+mod virtual_device;
+pub use self::virtual_device::*;
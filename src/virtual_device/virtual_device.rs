@@ -0,0 +1,145 @@
+use encoding::{decode_from_slice, encode_to_vec, Decode, Encode};
+use manager::Change;
+use polling::InputSource;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// An `InputSource` fed by tests instead of hardware. Clones share the same
+/// queue, so keep one to inject with after handing the other to the poller.
+pub struct VirtualDevice<C> {
+    queued: Arc<Mutex<Vec<(C, Change)>>>,
+}
+
+impl<C> Clone for VirtualDevice<C> {
+    fn clone(&self) -> Self {
+        VirtualDevice {
+            queued: self.queued.clone(),
+        }
+    }
+}
+
+pub trait IVirtualDevice<C> {
+    fn new() -> Self
+    where
+        Self: Sized;
+    fn inject(&self, input: C, change: Change);
+    fn listen<T: ToSocketAddrs>(&self, addr: T) -> io::Result<SocketAddr>
+    where
+        Self: Sized,
+        C: Decode + Send + 'static;
+}
+
+impl<C> IVirtualDevice<C> for VirtualDevice<C> {
+    fn new() -> Self {
+        VirtualDevice {
+            queued: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+    fn inject(&self, input: C, change: Change) {
+        if let Ok(mut queued) = self.queued.lock() {
+            queued.push((input, change));
+        }
+    }
+    fn listen<T: ToSocketAddrs>(&self, addr: T) -> io::Result<SocketAddr>
+    where
+        C: Decode + Send + 'static,
+    {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let queued = self.queued.clone();
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                while let Ok(inputs) = read_virtual_inputs::<C>(&mut stream) {
+                    match queued.lock() {
+                        Ok(mut queued) => queued.extend(inputs),
+                        Err(_) => return,
+                    }
+                }
+            }
+        });
+        Ok(local_addr)
+    }
+}
+
+impl<C: Send> InputSource<C> for VirtualDevice<C> {
+    fn poll(&mut self) -> Vec<(C, Change)> {
+        self.queued
+            .lock()
+            .map(|mut queued| queued.drain(..).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// The largest message body the socket accepts. Longer lengths are rejected
+/// as `InvalidData` rather than allocated.
+pub const MAX_VIRTUAL_INPUT_LEN: usize = 64 * 1024;
+
+/// Messages on the socket are a little-endian `u32` byte length followed by
+/// an encoded `Vec<(C, Change)>`.
+pub fn write_virtual_inputs<C: Encode>(
+    writer: &mut impl Write,
+    inputs: Vec<(C, Change)>,
+) -> io::Result<()> {
+    let bytes = encode_to_vec(&inputs);
+    writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    writer.write_all(&bytes)
+}
+
+fn read_virtual_inputs<C: Decode>(reader: &mut impl Read) -> io::Result<Vec<(C, Change)>> {
+    let mut len = [0; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_VIRTUAL_INPUT_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("message of {} bytes exceeds the limit", len),
+        ));
+    }
+    let mut bytes = vec![0; len];
+    reader.read_exact(&mut bytes)?;
+    decode_from_slice(&bytes)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use change::ButtonChange;
+    use std::net::TcpStream;
+    use std::time::Duration;
+
+    #[test]
+    fn injected_inputs_are_polled_once() {
+        let device = VirtualDevice::new();
+        let mut source = device.clone();
+        device.inject('a', ButtonChange(true).into());
+        assert_eq!(source.poll(), vec![('a', ButtonChange(true).into())]);
+        assert!(source.poll().is_empty());
+    }
+
+    #[test]
+    fn injects_over_socket() {
+        let mut device: VirtualDevice<u8> = VirtualDevice::new();
+        let addr = device.listen("127.0.0.1:0").unwrap();
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write_virtual_inputs(&mut stream, vec![(7u8, ButtonChange(true).into())]).unwrap();
+        let mut polled = Vec::new();
+        for _ in 0..1000 {
+            polled.extend(device.poll());
+            if !polled.is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(polled, vec![(7u8, ButtonChange(true).into())]);
+    }
+
+    #[test]
+    fn rejects_oversized_messages() {
+        let len = (MAX_VIRTUAL_INPUT_LEN as u32 + 1).to_le_bytes();
+        let error = read_virtual_inputs::<u8>(&mut &len[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,2 @@
+mod virtual_keyboard;
+pub use self::virtual_keyboard::*;
@@ -0,0 +1,160 @@
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum VirtualKey {
+    Char(char),
+    Backspace,
+    Submit,
+}
+
+impl VirtualKey {
+    /// One key per character, for building layouts from strings.
+    pub fn row(chars: &str) -> Vec<VirtualKey> {
+        chars.chars().map(VirtualKey::Char).collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TextEvent {
+    Insert(char),
+    Backspace,
+    Submit,
+    Cancel,
+}
+
+/// On-screen keyboard for gamepad text entry. The stick moves the selection
+/// one key each time it is pushed past `threshold` and returns, wrapping at
+/// the edges of the grid. Confirm presses the selected key and cancel emits
+/// `TextEvent::Cancel`. The entered text is kept so name entry screens can
+/// show it, and every change is also reported as a `TextEvent`.
+#[derive(Debug, Clone)]
+pub struct VirtualKeyboard {
+    pub rows: Vec<Vec<VirtualKey>>,
+    pub threshold: f64,
+    cursor: (usize, usize),
+    latched: bool,
+    text: String,
+    events: Vec<TextEvent>,
+}
+
+pub trait IVirtualKeyboard {
+    fn new(rows: Vec<Vec<VirtualKey>>) -> Self
+    where
+        Self: Sized;
+    /// `confirm` and `cancel` are this frame's presses, not whether the
+    /// buttons are held.
+    fn update(&mut self, x: f64, y: f64, confirm: bool, cancel: bool);
+    fn cursor(&self) -> (usize, usize);
+    fn selected(&self) -> Option<VirtualKey>;
+    fn text(&self) -> &str;
+    fn drain_events(&mut self) -> Vec<TextEvent>;
+}
+
+impl VirtualKeyboard {
+    fn step(&mut self, dx: isize, dy: isize) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let (mut row, mut column) = self.cursor;
+        if dy != 0 {
+            let rows = self.rows.len() as isize;
+            row = ((row as isize + dy).rem_euclid(rows)) as usize;
+        }
+        let columns = self.rows[row].len().max(1) as isize;
+        if dx != 0 {
+            column = ((column as isize + dx).rem_euclid(columns)) as usize;
+        }
+        self.cursor = (row, column.min(columns as usize - 1));
+    }
+    fn press(&mut self, key: VirtualKey) {
+        let event = match key {
+            VirtualKey::Char(c) => {
+                self.text.push(c);
+                TextEvent::Insert(c)
+            }
+            VirtualKey::Backspace => {
+                self.text.pop();
+                TextEvent::Backspace
+            }
+            VirtualKey::Submit => TextEvent::Submit,
+        };
+        self.events.push(event);
+    }
+}
+
+impl IVirtualKeyboard for VirtualKeyboard {
+    fn new(rows: Vec<Vec<VirtualKey>>) -> Self {
+        VirtualKeyboard {
+            rows,
+            threshold: 0.5,
+            cursor: (0, 0),
+            latched: false,
+            text: String::new(),
+            events: Vec::new(),
+        }
+    }
+    fn update(&mut self, x: f64, y: f64, confirm: bool, cancel: bool) {
+        let pushed = x.abs().max(y.abs()) >= self.threshold;
+        if pushed && !self.latched {
+            if x.abs() >= y.abs() {
+                self.step(x.signum() as isize, 0);
+            } else {
+                self.step(0, -y.signum() as isize);
+            }
+        }
+        self.latched = pushed;
+        if confirm {
+            if let Some(key) = self.selected() {
+                self.press(key);
+            }
+        }
+        if cancel {
+            self.events.push(TextEvent::Cancel);
+        }
+    }
+    fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+    fn selected(&self) -> Option<VirtualKey> {
+        let (row, column) = self.cursor;
+        self.rows.get(row)?.get(column).cloned()
+    }
+    fn text(&self) -> &str {
+        &self.text
+    }
+    fn drain_events(&mut self) -> Vec<TextEvent> {
+        self.events.drain(..).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn navigates_and_types() {
+        let mut keyboard = VirtualKeyboard::new(vec![
+            VirtualKey::row("abc"),
+            vec![VirtualKey::Backspace, VirtualKey::Submit],
+        ]);
+        keyboard.update(1.0, 0.0, false, false);
+        keyboard.update(1.0, 0.0, true, false);
+        assert_eq!(keyboard.cursor(), (0, 1));
+        keyboard.update(0.0, 0.0, false, false);
+        keyboard.update(-1.0, 0.0, false, false);
+        keyboard.update(0.0, 0.0, false, false);
+        keyboard.update(-1.0, 0.0, true, false);
+        assert_eq!(keyboard.text(), "bc");
+        keyboard.update(0.0, 0.0, false, false);
+        keyboard.update(0.0, -1.0, false, false);
+        assert_eq!(keyboard.selected(), Some(VirtualKey::Submit));
+        keyboard.update(0.0, 0.0, true, true);
+        assert_eq!(
+            keyboard.drain_events(),
+            vec![
+                TextEvent::Insert('b'),
+                TextEvent::Insert('c'),
+                TextEvent::Submit,
+                TextEvent::Cancel
+            ]
+        );
+        assert!(keyboard.drain_events().is_empty());
+    }
+}